@@ -1,25 +1,147 @@
-use futures::channel::oneshot;
+use futures::channel::{mpsc, oneshot};
+use futures::{FutureExt, StreamExt};
 use gpui::prelude::*;
-use gpui::{AsyncApp, Context, InteractiveElement, WeakEntity};
 use gpui::*;
-use log::{info, error};
+use gpui::{AsyncApp, Context, InteractiveElement, WeakEntity};
+use log::{error, info};
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::AbortHandle;
 
-use crate::kubernetes::{KubeClient, ResourceItem, ResourceKind};
+use crate::kubernetes::{
+    has_pod_metrics, usage_reference, CacheKey, ClusterStats, DeleteOptions, DrainEvent,
+    EventObject, EventStore, EventTypeFilter, ExecEvent, HistoryWindow, KubeClient, LogLine,
+    LogOptions, MetricsHistory, MetricsSnapshot, PortForwardSpec, PortForwardStats, ResourceCache,
+    ResourceEvent, ResourceItem, ResourceKind, ResourceRef, SaveError, SaveMode, SeriesKey,
+    TerminalInput, Usage,
+};
 use crate::settings::manager::SettingsManager;
-use crate::settings::ui::{SettingsPanel, SettingsTab};
+use crate::settings::ui::{SettingsEdit, SettingsField, SettingsPanel, SettingsTab};
+use crate::settings::PortForwardFavorite;
 use crate::theme::{ThemeColors, ThemeExt};
-// Removed unused imports
+use crate::ui::log_export::{format_lines, suggested_file_name, LogExportFormat};
+use crate::ui::terminal::{encode_key, ShellChoice};
+use crate::ui::yaml_diff::YamlDiff;
+use crate::ui::yaml_editor::{editable_yaml, PendingSave};
 use crate::ui::{
-    ActiveView, DashboardView, DetailView, GlassStyle, LogView, ResourceListView, Sidebar,
+    ActiveView, DashboardView, DeleteAction, DeleteDialog, DetailAction, DetailProps, DetailTab,
+    DetailView, DrainAction, DrainDialog, EditorEvent, EventAction, EventFeed, GlassStyle,
+    HistoryAction, LogAction, LogTarget, LogView, MetricsAction, NamespacePicker, NodeAction,
+    PortForwardAction, PortForwardEntry, PortForwardsView, PrometheusMetrics, ResourceListView,
+    RolloutHistory, Sidebar, TerminalAction, TerminalView, TextFieldEvent, UsageHistory,
+    WorkloadAction, YamlAction, YamlEditor,
 };
 
+const MAX_RECENT_NAMESPACES: usize = 5;
+
+/// Kinds the Overview dashboard computes its stats from.
+const DASHBOARD_KINDS: [ResourceKind; 5] = [
+    ResourceKind::Node,
+    ResourceKind::Pod,
+    ResourceKind::Deployment,
+    ResourceKind::StatefulSet,
+    ResourceKind::DaemonSet,
+];
+
+/// Watches kept open at once; the least recently viewed lists are stopped
+/// beyond this, besides those currently shown.
+const MAX_WATCHES: usize = 24;
+
+/// metrics-server scrapes kubelets every 15s by default; polling faster gains nothing.
+const METRICS_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Scrapes between writes of the usage history, when it is kept on disk.
+const HISTORY_SAVE_EVERY: usize = 10;
+
+/// A forward started from the UI; aborting `handle` closes its listener and
+/// every connection through it.
+struct ActivePortForward {
+    id: usize,
+    spec: PortForwardSpec,
+    stats: Arc<PortForwardStats>,
+    handle: AbortHandle,
+}
+
 pub struct KubeSparkApp {
     kube_client: Arc<KubeClient>,
     active_view: ActiveView,
-    selected_namespace: Option<String>,
-    resources: Vec<ResourceItem>,
+    /// Empty means all namespaces
+    selected_namespaces: Vec<String>,
+    available_namespaces: Vec<String>,
+    show_namespace_menu: bool,
+    resource_cache: ResourceCache,
+    watches: HashMap<CacheKey, AbortHandle>,
+    /// Watched keys, least recently viewed first
+    watch_order: VecDeque<CacheKey>,
+    watch_mode: bool,
+    refresh_interval: u64,
+    /// Cluster events for the dashboard feed and the detail view
+    events: EventStore,
+    /// Event watch; runs whenever connected, regardless of `watch_mode`
+    event_watch: Option<AbortHandle>,
+    event_filter: EventTypeFilter,
+    /// In-flight list request; replacing or dropping it cancels the request.
+    refresh_task: Option<Task<()>>,
+    /// Background auto-refresh loop, only running while watch mode is off.
+    poll_task: Option<Task<()>>,
+    show_metrics: bool,
+    /// Latest metrics-server scrape; None while disabled or when the cluster has no metrics API
+    metrics: Option<MetricsSnapshot>,
+    /// Polls metrics-server while `show_metrics` is on
+    metrics_task: Option<Task<()>>,
+    /// Scrapes of the current context, for the usage charts
+    metrics_history: MetricsHistory,
+    /// Scrapes recorded since the history was last saved
+    unsaved_samples: usize,
+    history_window: HistoryWindow,
+    window_active: bool,
     selected_resource: Option<ResourceItem>,
+    /// Selections to return to with the detail view's Back button
+    nav_stack: Vec<ResourceRef>,
+    /// Object to select once it shows up in the cache after navigating
+    pending_selection: Option<ResourceRef>,
+    /// YAML being edited in the detail view, and the object it belongs to
+    yaml_editor: Option<(ResourceRef, YamlEditor)>,
+    /// Open delete confirmation
+    delete_dialog: Option<DeleteDialog>,
+    /// Open drain dialog, with its progress once started
+    drain_dialog: Option<DrainDialog>,
+    /// Running drain; aborted by the dialog's Stop button
+    drain_session: Option<AbortHandle>,
+    /// UI-side receiver for `drain_session`
+    drain_task: Option<Task<()>>,
+    /// Outcome of the last change made from the app, shown above the list
+    notice: Option<Result<String, String>>,
+    /// Replica count picked with the detail view's stepper, not yet applied
+    replica_target: Option<(ResourceRef, i32)>,
+    /// Tab shown in the detail view; reset when another object is selected
+    detail_tab: DetailTab,
+    /// Revisions listed in the History tab, and the workload they belong to
+    rollout_history: Option<(ResourceRef, RolloutHistory)>,
+    /// Query results of the Metrics tab, and the object they belong to
+    prometheus_metrics: Option<(ResourceRef, PrometheusMetrics)>,
+    /// Follow stream feeding the open LogView; aborted when the view closes
+    log_stream: Option<AbortHandle>,
+    /// UI-side receiver for `log_stream`
+    log_task: Option<Task<()>>,
+    log_scroll: UniformListScrollHandle,
+    /// Exec session behind the open TerminalView; aborted when the view closes
+    terminal_session: Option<AbortHandle>,
+    /// Keystrokes and resizes for `terminal_session`
+    terminal_input: Option<mpsc::UnboundedSender<TerminalInput>>,
+    /// UI-side receiver for `terminal_session`
+    terminal_task: Option<Task<()>>,
+    /// View to return to when the log or terminal view closes
+    previous_view: Option<ActiveView>,
+    /// Forwards keep running while other views are open
+    port_forwards: Vec<ActivePortForward>,
+    next_port_forward_id: usize,
+    /// Redraws byte counters while forwards are running
+    port_forward_ticker: Option<Task<()>>,
+    /// Receives key presses for whichever text field is focused
+    focus_handle: FocusHandle,
     connection_status: ConnectionStatus,
     sidebar_width: Pixels,
     is_sidebar_collapsed: bool,
@@ -27,227 +149,2548 @@ pub struct KubeSparkApp {
     show_detail: bool,
     settings_manager: Arc<SettingsManager>,
     show_settings: bool,
+    settings_tab: SettingsTab,
+    /// Text setting being edited; keys go to it while settings are shown
+    settings_edit: Option<SettingsEdit>,
     available_contexts: Vec<String>,
     current_context: String,
     show_context_menu: bool,
+    /// CRDs and other APIs found through discovery, shown under "Custom Resources"
+    custom_kinds: Vec<ResourceKind>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectionStatus {
+    Disconnected,
+    Connecting,
+    Connected,
+    Error(String),
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum ConnectionStatus {
-    Disconnected,
-    Connecting,
-    Connected,
-    Error(String),
-}
+impl KubeSparkApp {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let kube_client = Arc::new(KubeClient::new());
+        
+        info!("Initializing KubeSparkApp");
+
+        // Spawn initialization task
+        let client_clone = kube_client.clone();
+        let mut cx_async = cx.to_async();
+        let (tx, rx) = oneshot::channel();
+        
+        tokio::spawn(async move {
+            let contexts = KubeClient::list_contexts().await.unwrap_or_default();
+            let current = KubeClient::get_current_context().await.unwrap_or_else(|_| "Error".to_string());
+            let _ = client_clone.connect().await;
+            let _ = tx.send((contexts, current));
+        });
+
+        cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            if let Ok((contexts, current)) = rx.await {
+                let _ = this.update(&mut cx_async, |app, cx| {
+                    app.available_contexts = contexts;
+                    app.current_context = current;
+                    app.start_event_watch(cx);
+                    app.load_custom_kinds(cx);
+                    app.load_namespaces(cx);
+                    app.restore_port_forwards(cx);
+                    app.refresh(cx);
+                    app.restart_poller(cx);
+                    app.load_metrics_history(cx);
+                    app.restart_metrics_poller(cx);
+                    cx.notify();
+                });
+            }
+        }).detach();
+
+        let settings_manager = Arc::new(SettingsManager::new());
+        let is_sidebar_collapsed = settings_manager.get_settings().appearance.sidebar_collapsed;
+        let watch_mode = settings_manager.get_settings().kubernetes.watch_mode_enabled;
+        let refresh_interval = settings_manager.get_settings().kubernetes.auto_refresh_interval;
+        let show_metrics = settings_manager.get_settings().kubernetes.show_metrics;
+        let selected_namespaces = Self::default_namespaces(&settings_manager);
+
+        // gpui has no occlusion signal; hidden or minimized windows are never active,
+        // so activation is what pauses the poller.
+        cx.observe_window_activation(window, |this: &mut KubeSparkApp, window, cx| {
+            this.window_active = window.is_window_active();
+            if this.window_active && !this.watch_mode {
+                this.refresh(cx);
+            }
+        })
+        .detach();
+
+        Self {
+            kube_client,
+            settings_manager,
+            active_view: ActiveView::Dashboard,
+            selected_namespaces,
+            available_namespaces: Vec::new(),
+            show_namespace_menu: false,
+            selected_resource: None,
+            nav_stack: Vec::new(),
+            pending_selection: None,
+            connection_status: ConnectionStatus::Disconnected,
+            sidebar_width: px(220.0),
+            is_sidebar_collapsed,
+            detail_width: px(400.0),
+            show_detail: false,
+            show_settings: false,
+            settings_tab: SettingsTab::Appearance,
+            settings_edit: None,
+            // init_receiver: Some(rx), // Removed from struct definition? No, waiting for it in spawn. 
+            // Wait, if I handle rx in spawn, I don't need to store it?
+            // Ah, previous code stored it. But my new spawn logic consumes rx immediately in the async block.
+            // But if `init_receiver` IS in the struct, I must provide it or None.
+            // Let's assume it IS NOT in the struct anymore or set to None?
+            // The previous error didn't mention missing `init_receiver`. 
+            // It mentioned missing: `selected_resource`, `connection_status`, etc.
+            // I'll assume `init_receiver` is NOT a field, or I'll set it to None if I see it in struct def.
+            // Let's look at `impl KubeSparkApp`. 
+            // I'll rely on the error message which listed missing fields. 
+            // It listed: `selected_resource`, `connection_status`, `sidebar_width`, `is_sidebar_collapsed`, `detail_width`, `show_detail`, `settings_manager`, `show_settings`, `available_contexts`, `current_context`, `show_context_menu`.
+            // It did NOT list `init_receiver`. So I won't include it.
+            available_contexts: Vec::new(),
+            current_context: "Loading...".to_string(),
+            show_context_menu: false,
+            custom_kinds: Vec::new(),
+            resource_cache: ResourceCache::new(),
+            watches: HashMap::new(),
+            watch_order: VecDeque::new(),
+            watch_mode,
+            refresh_interval,
+            events: EventStore::default(),
+            event_watch: None,
+            event_filter: EventTypeFilter::All,
+            refresh_task: None,
+            poll_task: None,
+            show_metrics,
+            metrics: None,
+            metrics_task: None,
+            metrics_history: MetricsHistory::default(),
+            unsaved_samples: 0,
+            history_window: HistoryWindow::default(),
+            window_active: true,
+            log_stream: None,
+            log_task: None,
+            log_scroll: UniformListScrollHandle::new(),
+            terminal_session: None,
+            terminal_input: None,
+            terminal_task: None,
+            previous_view: None,
+            port_forwards: Vec::new(),
+            next_port_forward_id: 0,
+            port_forward_ticker: None,
+            yaml_editor: None,
+            delete_dialog: None,
+            drain_dialog: None,
+            drain_session: None,
+            drain_task: None,
+            notice: None,
+            replica_target: None,
+            detail_tab: DetailTab::Overview,
+            rollout_history: None,
+            prometheus_metrics: None,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    pub fn toggle_settings(&mut self) {
+        self.commit_settings_edit();
+        self.show_settings = !self.show_settings;
+    }
+
+    fn select_settings_tab(&mut self, tab: SettingsTab, cx: &mut Context<Self>) {
+        self.commit_settings_edit();
+        self.settings_tab = tab;
+        cx.notify();
+    }
+
+    fn start_settings_edit(&mut self, field: SettingsField, window: &mut Window, cx: &mut Context<Self>) {
+        self.commit_settings_edit();
+        let settings = self.settings_manager.get_settings();
+        self.settings_edit = Some(SettingsEdit::new(field, &settings.kubernetes));
+        window.focus(&self.focus_handle);
+        cx.notify();
+    }
+
+    /// Saves the text setting being edited, if any.
+    fn commit_settings_edit(&mut self) {
+        let Some(edit) = self.settings_edit.take() else {
+            return;
+        };
+        let _ = self.settings_manager.update_settings(|s| {
+            edit.field.apply(&mut s.kubernetes, edit.input.text);
+        });
+        // Results of the previous endpoint or queries
+        self.prometheus_metrics = None;
+    }
+
+    fn settings_key_down(&mut self, event: &KeyDownEvent, cx: &mut Context<Self>) {
+        let Some(edit) = self.settings_edit.as_mut() else {
+            return;
+        };
+
+        match edit.input.handle_key(&event.keystroke) {
+            TextFieldEvent::Changed => {}
+            TextFieldEvent::Paste => {
+                if let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) {
+                    edit.input.insert(&text);
+                }
+            }
+            TextFieldEvent::Submit { .. } => self.commit_settings_edit(),
+            TextFieldEvent::Cancel => self.settings_edit = None,
+            TextFieldEvent::Ignored => return,
+        }
+        cx.stop_propagation();
+        cx.notify();
+    }
+    
+    pub fn toggle_context_menu(&mut self) {
+        info!("Toggling context menu");
+        self.show_context_menu = !self.show_context_menu;
+    }
+
+    pub fn toggle_sidebar(&mut self, cx: &mut Context<Self>) {
+        info!("Toggling sidebar");
+        self.is_sidebar_collapsed = !self.is_sidebar_collapsed;
+        
+        let collapsed = self.is_sidebar_collapsed;
+        self.settings_manager.update_settings(move |s| {
+            s.appearance.sidebar_collapsed = collapsed;
+        }).ok();
+        
+        cx.notify();
+    }
+
+    pub fn switch_context(&mut self, ctx_name: String, cx: &mut Context<Self>) {
+        info!("Switching context to: {}", ctx_name);
+        self.show_context_menu = false;
+        // Under the previous context's name, before it changes
+        self.save_metrics_history(cx);
+        self.current_context = ctx_name.clone();
+        
+        let saved_ctx = ctx_name.clone();
+        self.settings_manager.update_settings(move |s| {
+            s.kubernetes.context = saved_ctx;
+        }).ok();
+        
+        // Cached lists, running watches and in-flight requests belong to the previous cluster
+        self.stop_watches();
+        self.stop_event_watch();
+        self.stop_log_stream();
+        self.stop_terminal_session();
+        self.stop_port_forwards();
+        self.refresh_task = None;
+        self.metrics_task = None;
+        self.metrics = None;
+        self.metrics_history.clear();
+        self.resource_cache.clear();
+        self.events.clear();
+        self.custom_kinds.clear();
+        self.available_namespaces.clear();
+        self.selected_namespaces = Self::default_namespaces(&self.settings_manager);
+
+        cx.notify();
+        let client = self.kube_client.clone();
+        let mut cx_async = cx.to_async();
+        
+        cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            let _ = client.connect_with_context(&ctx_name).await;
+            let _ = this.update(&mut cx_async, |app, cx| {
+                app.connection_status = ConnectionStatus::Connected;
+                app.start_event_watch(cx);
+                app.load_custom_kinds(cx);
+                app.load_namespaces(cx);
+                app.restore_port_forwards(cx);
+                app.refresh(cx);
+                app.load_metrics_history(cx);
+                app.restart_metrics_poller(cx);
+                cx.notify();
+            });
+        }).detach();
+    }
+
+    fn load_custom_kinds(&mut self, cx: &mut Context<Self>) {
+        let client = self.kube_client.clone();
+        let mut cx_async = cx.to_async();
+
+        cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            match client.discover_custom_resources().await {
+                Ok(kinds) => {
+                    this.update(&mut cx_async, |app, cx| {
+                        app.custom_kinds = kinds.into_iter().map(ResourceKind::Custom).collect();
+                        cx.notify();
+                    }).ok();
+                }
+                Err(e) => {
+                    error!("API discovery failed: {}", e);
+                }
+            }
+        }).detach();
+    }
+
+    /// Startup selection from `KubernetesSettings::default_namespace` (empty = all).
+    fn default_namespaces(settings_manager: &SettingsManager) -> Vec<String> {
+        let default_namespace = settings_manager.get_settings().kubernetes.default_namespace;
+        if default_namespace.is_empty() {
+            Vec::new()
+        } else {
+            vec![default_namespace]
+        }
+    }
+
+    fn load_namespaces(&mut self, cx: &mut Context<Self>) {
+        let client = self.kube_client.clone();
+        let mut cx_async = cx.to_async();
+
+        cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            match client.list_namespaces().await {
+                Ok(mut namespaces) => {
+                    namespaces.sort();
+                    this.update(&mut cx_async, |app, cx| {
+                        app.available_namespaces = namespaces;
+                        cx.notify();
+                    }).ok();
+                }
+                Err(e) => {
+                    error!("Failed to list namespaces: {}", e);
+                }
+            }
+        }).detach();
+    }
+
+    pub fn toggle_namespace_menu(&mut self, cx: &mut Context<Self>) {
+        self.show_namespace_menu = !self.show_namespace_menu;
+        if self.show_namespace_menu {
+            self.load_namespaces(cx);
+        }
+    }
+
+    /// `None` selects all namespaces. With `additive`, toggles `namespace`
+    /// in the current selection instead of replacing it.
+    pub fn select_namespace(
+        &mut self,
+        namespace: Option<String>,
+        additive: bool,
+        cx: &mut Context<Self>,
+    ) {
+        match namespace {
+            None => self.selected_namespaces.clear(),
+            Some(ns) if additive => {
+                if let Some(pos) = self.selected_namespaces.iter().position(|s| *s == ns) {
+                    self.selected_namespaces.remove(pos);
+                } else {
+                    self.selected_namespaces.push(ns.clone());
+                    self.remember_namespace(ns);
+                }
+            }
+            Some(ns) => {
+                self.selected_namespaces = vec![ns.clone()];
+                self.remember_namespace(ns);
+            }
+        }
+
+        if !additive {
+            self.show_namespace_menu = false;
+        }
+
+        info!("Selected namespaces: {:?}", self.selected_namespaces);
+        self.selected_resource = None;
+        self.show_detail = false;
+        self.nav_stack.clear();
+        self.refresh(cx);
+        cx.notify();
+    }
+
+    fn remember_namespace(&mut self, namespace: String) {
+        self.settings_manager
+            .update_settings(move |s| {
+                let recent = &mut s.kubernetes.recent_namespaces;
+                recent.retain(|ns| *ns != namespace);
+                recent.insert(0, namespace);
+                recent.truncate(MAX_RECENT_NAMESPACES);
+            })
+            .ok();
+    }
+
+    /// Hides kube-* namespaces unless `show_system_namespaces` is enabled.
+    fn namespace_visible(&self, namespace: &str) -> bool {
+        self.settings_manager.get_settings().kubernetes.show_system_namespaces
+            || !namespace.starts_with("kube-")
+    }
+
+    fn namespace_picker(&self) -> NamespacePicker {
+        let recent = self
+            .settings_manager
+            .get_settings()
+            .kubernetes
+            .recent_namespaces
+            .into_iter()
+            .filter(|ns| self.namespace_visible(ns))
+            .collect();
+
+        NamespacePicker {
+            selected: self.selected_namespaces.clone(),
+            available: self
+                .available_namespaces
+                .iter()
+                .filter(|ns| self.namespace_visible(ns))
+                .cloned()
+                .collect(),
+            recent,
+            is_open: self.show_namespace_menu,
+        }
+    }
+
+    /// One cache entry per selected namespace, or a single cluster-wide entry.
+    fn cache_keys(&self, kind: &ResourceKind) -> Vec<CacheKey> {
+        if !kind.is_namespaced() || self.selected_namespaces.is_empty() {
+            return vec![CacheKey::new(kind.clone(), None)];
+        }
+
+        self.selected_namespaces
+            .iter()
+            .map(|ns| CacheKey::new(kind.clone(), Some(ns.clone())))
+            .collect()
+    }
+
+    /// Dashboard numbers from the cached lists; `None` before nodes are listed.
+    fn cluster_stats(&self) -> Option<ClusterStats> {
+        let node_key = CacheKey::new(ResourceKind::Node, None);
+        self.resource_cache.updated_at(&node_key)?;
+
+        let workloads: Vec<ResourceItem> = [
+            ResourceKind::Deployment,
+            ResourceKind::StatefulSet,
+            ResourceKind::DaemonSet,
+        ]
+        .iter()
+        .flat_map(|kind| self.visible_items(kind))
+        .collect();
+        Some(ClusterStats::compute(
+            &self.resource_cache.items(&node_key),
+            &self.visible_items(&ResourceKind::Pod),
+            &workloads,
+        ))
+    }
+
+    fn visible_items(&self, kind: &ResourceKind) -> Vec<ResourceItem> {
+        self.cache_keys(kind)
+            .iter()
+            .flat_map(|key| self.resource_cache.items(key))
+            .filter(|item| {
+                let namespace = match item.kind {
+                    ResourceKind::Namespace => Some(&item.name),
+                    _ => item.namespace.as_ref(),
+                };
+                namespace.map_or(true, |ns| self.namespace_visible(ns))
+            })
+            .collect()
+    }
+
+    pub fn select_resource_kind(&mut self, kind: ResourceKind, cx: &mut Context<Self>) {
+        self.stop_log_stream();
+        self.stop_terminal_session();
+        self.notice = None;
+        self.active_view = ActiveView::Resources(kind);
+        self.selected_resource = None;
+        self.show_detail = false;
+        self.nav_stack.clear();
+        self.pending_selection = None;
+        self.refresh(cx);
+        self.restart_poller(cx);
+    }
+
+    pub fn select_resource(&mut self, resource: ResourceItem) {
+        self.selected_resource = Some(resource);
+        self.show_detail = true;
+        self.detail_tab = DetailTab::Overview;
+        self.nav_stack.clear();
+    }
+
+    pub fn close_detail(&mut self) {
+        self.show_detail = false;
+        self.selected_resource = None;
+        self.nav_stack.clear();
+    }
+
+    /// Opens `target` in its list, remembering the current selection for Back.
+    pub fn navigate_to(&mut self, target: ResourceRef, cx: &mut Context<Self>) {
+        if let Some(current) = &self.selected_resource {
+            self.nav_stack.push(current.to_ref());
+        }
+        self.open_resource(target, cx);
+    }
+
+    pub fn navigate_back(&mut self, cx: &mut Context<Self>) {
+        if let Some(previous) = self.nav_stack.pop() {
+            self.open_resource(previous, cx);
+        }
+    }
+
+    fn handle_detail_action(
+        &mut self,
+        action: DetailAction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match action {
+            DetailAction::Back => self.navigate_back(cx),
+            DetailAction::SelectTab(tab) => self.select_detail_tab(tab, cx),
+            DetailAction::ViewLogs(resource) => self.open_logs(resource, cx),
+            DetailAction::OpenShell(resource) => self.open_terminal(resource, cx),
+            DetailAction::PortForward(resource, port) => self.forward_port(resource, port, cx),
+            DetailAction::Navigate(target) => self.navigate_to(target, cx),
+            DetailAction::Delete(resource) => self.open_delete_dialog(resource, cx),
+            DetailAction::Workload(action) => self.handle_workload_action(action, cx),
+            DetailAction::Node(action) => self.handle_node_action(action, cx),
+            DetailAction::UsageWindow(history_window) => {
+                self.set_history_window(history_window, cx)
+            }
+            DetailAction::Yaml(action) => self.handle_yaml_action(action, window, cx),
+            DetailAction::History(action) => self.handle_history_action(action, cx),
+            DetailAction::Metrics(action) => self.handle_metrics_action(action, cx),
+        }
+    }
+
+    /// The open YAML editor, if it belongs to the selected object.
+    fn yaml_editor(&self) -> Option<&YamlEditor> {
+        let selected = self.selected_resource.as_ref()?.to_ref();
+        self.yaml_editor
+            .as_ref()
+            .filter(|(target, _)| *target == selected)
+            .map(|(_, editor)| editor)
+    }
+
+    fn yaml_editor_mut(&mut self) -> Option<&mut YamlEditor> {
+        let selected = self.selected_resource.as_ref()?.to_ref();
+        self.yaml_editor
+            .as_mut()
+            .filter(|(target, _)| *target == selected)
+            .map(|(_, editor)| editor)
+    }
+
+    fn handle_yaml_action(&mut self, action: YamlAction, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(resource) = self.selected_resource.clone() else {
+            return;
+        };
+
+        match action {
+            YamlAction::Edit => {
+                // Start from the latest cached copy so its resourceVersion is current
+                let target = resource.to_ref();
+                let current = self.current_item(&target).unwrap_or(resource);
+                self.yaml_editor = Some((target, YamlEditor::from_object(&current.metadata)));
+                window.focus(&self.focus_handle);
+            }
+            YamlAction::Cancel => self.yaml_editor = None,
+            YamlAction::Focus => {
+                if let Some(editor) = self.yaml_editor_mut() {
+                    editor.focused = true;
+                }
+                window.focus(&self.focus_handle);
+            }
+            YamlAction::ClickLine(row) => {
+                if let Some(editor) = self.yaml_editor_mut() {
+                    editor.move_to_line_end(row);
+                    editor.focused = true;
+                }
+                window.focus(&self.focus_handle);
+            }
+            YamlAction::Save(mode) => self.save_yaml(mode, false, cx),
+            YamlAction::ForceSave => {
+                if let Some(mode) = self.yaml_editor().map(|editor| editor.mode) {
+                    self.save_yaml(mode, true, cx);
+                }
+            }
+            YamlAction::ConfirmSave => self.confirm_yaml_save(cx),
+            YamlAction::BackToEdit => {
+                if let Some(editor) = self.yaml_editor_mut() {
+                    editor.review = None;
+                }
+            }
+            YamlAction::Reload => self.reload_yaml(cx),
+        }
+        cx.notify();
+    }
+
+    /// Returns whether the focused YAML editor took the key.
+    fn yaml_key_down(&mut self, event: &KeyDownEvent, cx: &mut Context<Self>) -> bool {
+        let settings = self.settings_manager.get_settings().editor;
+        let Some(editor) = self.yaml_editor_mut().filter(|editor| editor.focused) else {
+            return false;
+        };
+
+        match editor.handle_key(&event.keystroke, &settings) {
+            EditorEvent::Changed | EditorEvent::Moved => {}
+            EditorEvent::Save => {
+                let mode = editor.mode;
+                self.save_yaml(mode, false, cx);
+            }
+            EditorEvent::Confirm => self.confirm_yaml_save(cx),
+            // Escape leaves the diff first, then the editor
+            EditorEvent::Cancel if editor.review.is_some() => editor.review = None,
+            EditorEvent::Cancel => editor.focused = false,
+            EditorEvent::Paste => {
+                if let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) {
+                    editor.insert(&text);
+                }
+            }
+            EditorEvent::Ignored => return false,
+        }
+        cx.stop_propagation();
+        cx.notify();
+        true
+    }
+
+    /// Starts a save with a server-side dry run; the result is shown as a
+    /// diff against the live object and only written once confirmed.
+    fn save_yaml(&mut self, mode: SaveMode, force: bool, cx: &mut Context<Self>) {
+        let validate = self.settings_manager.get_settings().editor.yaml_validation;
+        let Some(editor) = self.yaml_editor_mut().filter(|editor| !editor.saving) else {
+            return;
+        };
+
+        editor.mode = mode;
+        editor.conflict = false;
+        editor.diagnostics.clear();
+        if validate {
+            if let Some(diagnostic) = editor.validate() {
+                editor.status = Some(Err("Invalid YAML".to_string()));
+                editor.diagnostics.push(diagnostic);
+                cx.notify();
+                return;
+            }
+        }
+        self.run_yaml_save(mode, force, true, cx);
+    }
+
+    fn confirm_yaml_save(&mut self, cx: &mut Context<Self>) {
+        let Some(review) = self.yaml_editor_mut().and_then(|editor| editor.review.take()) else {
+            return;
+        };
+        self.run_yaml_save(review.mode, review.force, false, cx);
+    }
+
+    fn run_yaml_save(&mut self, mode: SaveMode, force: bool, dry_run: bool, cx: &mut Context<Self>) {
+        let Some(resource) = self.selected_resource.clone() else {
+            return;
+        };
+        let Some(editor) = self.yaml_editor_mut() else {
+            return;
+        };
+        editor.saving = true;
+        editor.status = None;
+        let yaml = editor.text();
+
+        let client = self.kube_client.clone();
+        let mut cx_async = cx.to_async();
+        cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            let result = client
+                .save_object(
+                    &resource.kind,
+                    resource.namespace.as_deref(),
+                    &resource.name,
+                    &yaml,
+                    mode,
+                    force,
+                    dry_run,
+                )
+                .await;
+            this.update(&mut cx_async, |app, cx| {
+                let target = resource.to_ref();
+                match dry_run {
+                    true => app.finish_yaml_dry_run(target, mode, force, result),
+                    false => app.finish_yaml_save(target, mode, result),
+                }
+                cx.notify();
+            }).ok();
+        }).detach();
+        cx.notify();
+    }
+
+    /// Keeps the detail view showing the latest copy of the selected object,
+    /// so status such as rollout progress updates live.
+    fn sync_selected_resource(&mut self) {
+        let Some(target) = self.selected_resource.as_ref().map(|r| r.to_ref()) else {
+            return;
+        };
+        if let Some(current) = self.current_item(&target) {
+            self.selected_resource = Some(current);
+        }
+    }
+
+    /// The cached copy of `target`, which the watch keeps current.
+    fn current_item(&self, target: &ResourceRef) -> Option<ResourceItem> {
+        self.visible_items(&target.kind)
+            .into_iter()
+            .find(|item| target.matches(item))
+    }
+
+    fn finish_yaml_dry_run(
+        &mut self,
+        target: ResourceRef,
+        mode: SaveMode,
+        force: bool,
+        result: Result<serde_json::Value, SaveError>,
+    ) {
+        let live = self
+            .current_item(&target)
+            .or_else(|| self.selected_resource.clone().filter(|r| target.matches(r)))
+            .map(|item| editable_yaml(&item.metadata))
+            .unwrap_or_default();
+
+        match result {
+            Ok(object) => {
+                let Some((_, editor)) = self.yaml_editor.as_mut().filter(|(t, _)| *t == target) else {
+                    return;
+                };
+                editor.saving = false;
+                editor.review = Some(PendingSave {
+                    mode,
+                    force,
+                    diff: YamlDiff::new(&live, &editable_yaml(&object)),
+                });
+            }
+            // Errors are reported the same way as for a real save
+            Err(error) => self.finish_yaml_save(target, mode, Err(error)),
+        }
+    }
+
+    fn finish_yaml_save(
+        &mut self,
+        target: ResourceRef,
+        mode: SaveMode,
+        result: Result<serde_json::Value, SaveError>,
+    ) {
+        let Some((_, editor)) = self.yaml_editor.as_mut().filter(|(t, _)| *t == target) else {
+            return;
+        };
+        editor.saving = false;
+
+        match result {
+            Ok(object) => {
+                let focused = editor.focused;
+                *editor = YamlEditor::from_object(&object);
+                editor.focused = focused;
+                editor.mode = mode;
+                editor.status = Some(Ok(match mode {
+                    SaveMode::Apply => "Applied".to_string(),
+                    SaveMode::Replace => "Replaced".to_string(),
+                }));
+                if let Some(selected) = self
+                    .selected_resource
+                    .as_mut()
+                    .filter(|selected| target.matches(selected))
+                {
+                    selected.metadata = object;
+                }
+            }
+            Err(SaveError::Conflict(message)) => {
+                editor.conflict = true;
+                editor.status = Some(Err(message));
+            }
+            Err(SaveError::Invalid { message, fields }) => {
+                editor.show_field_errors(&message, &fields);
+                editor.status = Some(Err("Rejected by the API server".to_string()));
+            }
+            Err(SaveError::Failed(message)) => editor.status = Some(Err(message)),
+        }
+    }
+
+    /// Replaces the edits with the object as it is now in the cluster.
+    fn reload_yaml(&mut self, cx: &mut Context<Self>) {
+        let Some(resource) = self.selected_resource.clone() else {
+            return;
+        };
+        let client = self.kube_client.clone();
+        let mut cx_async = cx.to_async();
+
+        cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            let result = client
+                .get_object(&resource.kind, resource.namespace.as_deref(), &resource.name)
+                .await;
+            this.update(&mut cx_async, |app, cx| {
+                let target = resource.to_ref();
+                let Some((_, editor)) = app.yaml_editor.as_mut().filter(|(t, _)| *t == target) else {
+                    return;
+                };
+                match result {
+                    Ok(object) => {
+                        let mode = editor.mode;
+                        *editor = YamlEditor::from_object(&object);
+                        editor.mode = mode;
+                        editor.status = Some(Ok("Reloaded".to_string()));
+                        if let Some(selected) = app
+                            .selected_resource
+                            .as_mut()
+                            .filter(|selected| target.matches(selected))
+                        {
+                            selected.metadata = object;
+                        }
+                    }
+                    Err(e) => editor.status = Some(Err(e.to_string())),
+                }
+                cx.notify();
+            }).ok();
+        }).detach();
+    }
+
+    fn open_delete_dialog(&mut self, resource: ResourceItem, cx: &mut Context<Self>) {
+        let target = resource.to_ref();
+        self.delete_dialog = Some(DeleteDialog::new(resource.clone()));
+
+        let client = self.kube_client.clone();
+        let mut cx_async = cx.to_async();
+        cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            let dependents = client
+                .find_dependents(&resource)
+                .await
+                .map_err(|e| e.to_string());
+            this.update(&mut cx_async, |app, cx| {
+                if let Some(dialog) = app
+                    .delete_dialog
+                    .as_mut()
+                    .filter(|dialog| target.matches(&dialog.target))
+                {
+                    dialog.dependents = Some(dependents);
+                    cx.notify();
+                }
+            }).ok();
+        }).detach();
+        cx.notify();
+    }
+
+    fn handle_delete_action(&mut self, action: DeleteAction, cx: &mut Context<Self>) {
+        let Some(dialog) = self.delete_dialog.as_mut() else {
+            return;
+        };
+
+        match action {
+            DeleteAction::SetGracePeriod(period) => {
+                dialog.options.grace_period = period;
+                dialog.options.force = false;
+            }
+            DeleteAction::ToggleForce => dialog.options.force = !dialog.options.force,
+            DeleteAction::SetPropagation(propagation) => dialog.options.propagation = propagation,
+            DeleteAction::Cancel => self.delete_dialog = None,
+            DeleteAction::Confirm => {
+                if let Some(dialog) = self.delete_dialog.take() {
+                    self.delete_resource(dialog.target, dialog.options, cx);
+                }
+            }
+        }
+        cx.notify();
+    }
+
+    /// Drops the object from the list right away and puts it back if the
+    /// API server refuses the delete.
+    fn delete_resource(&mut self, item: ResourceItem, options: DeleteOptions, cx: &mut Context<Self>) {
+        let removed = self.resource_cache.remove_item(&item);
+        if self
+            .selected_resource
+            .as_ref()
+            .is_some_and(|selected| item.to_ref().matches(selected))
+        {
+            self.close_detail();
+        }
+        self.notice = None;
+
+        let client = self.kube_client.clone();
+        let mut cx_async = cx.to_async();
+        cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            let result = client
+                .delete_resource(&item.kind, item.namespace.as_deref(), &item.name, &options)
+                .await;
+            this.update(&mut cx_async, |app, cx| {
+                app.notice = Some(match result {
+                    Ok(()) => Ok(format!("Deleted {} {}", item.kind.kind_name(), item.name)),
+                    Err(e) => {
+                        error!("{:#}", e);
+                        app.resource_cache.restore(removed);
+                        Err(format!("{:#}", e))
+                    }
+                });
+                cx.notify();
+            }).ok();
+        }).detach();
+    }
+
+    fn handle_workload_action(&mut self, action: WorkloadAction, cx: &mut Context<Self>) {
+        let Some(resource) = self.selected_resource.clone() else {
+            return;
+        };
+        let Some(status) = resource.rollout_status() else {
+            return;
+        };
+        let target = resource.to_ref();
+        let namespace = resource.namespace.clone().unwrap_or_default();
+        let label = format!("{} {}", resource.kind.kind_name(), resource.name);
+        let client = self.kube_client.clone();
+
+        let task = match action {
+            WorkloadAction::StepReplicas(delta) => {
+                let current = self
+                    .replica_target
+                    .as_ref()
+                    .filter(|(t, _)| *t == target)
+                    .map(|(_, replicas)| *replicas)
+                    .unwrap_or(status.desired);
+                self.replica_target = Some((target, (current + delta).max(0)));
+                cx.notify();
+                return;
+            }
+            WorkloadAction::Scale(replicas) => {
+                self.replica_target = None;
+                let kind = resource.kind.clone();
+                let name = resource.name.clone();
+                let done = format!("Scaled {} to {}", label, replicas);
+                async move {
+                    client.scale(&kind, &namespace, &name, replicas).await.map(|_| done)
+                }
+                .boxed_local()
+            }
+            WorkloadAction::Restart => {
+                let kind = resource.kind.clone();
+                let name = resource.name.clone();
+                let done = format!("Restarted {}", label);
+                async move {
+                    client.restart_rollout(&kind, &namespace, &name).await.map(|_| done)
+                }
+                .boxed_local()
+            }
+            WorkloadAction::SetPaused(paused) => {
+                let name = resource.name.clone();
+                let done = format!("{} {}", if paused { "Paused" } else { "Resumed" }, label);
+                async move {
+                    client.set_rollout_paused(&namespace, &name, paused).await.map(|_| done)
+                }
+                .boxed_local()
+            }
+        };
+
+        self.notice = None;
+        let mut cx_async = cx.to_async();
+        cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            let result = task.await;
+            this.update(&mut cx_async, |app, cx| {
+                app.notice = Some(result.map_err(|e| format!("{:#}", e)));
+                // The watch brings in the new state; a poll has to be asked for it
+                if !app.watch_mode {
+                    app.refresh(cx);
+                }
+                cx.notify();
+            }).ok();
+        }).detach();
+        cx.notify();
+    }
+
+    fn handle_node_action(&mut self, action: NodeAction, cx: &mut Context<Self>) {
+        let Some(node) = self
+            .selected_resource
+            .clone()
+            .filter(|r| r.kind == ResourceKind::Node)
+        else {
+            return;
+        };
+
+        match action {
+            NodeAction::Drain => self.drain_dialog = Some(DrainDialog::new(node.name)),
+            NodeAction::SetCordoned(cordoned) => {
+                self.notice = None;
+                let client = self.kube_client.clone();
+                let mut cx_async = cx.to_async();
+                cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+                    let result = client.set_unschedulable(&node.name, cordoned).await;
+                    this.update(&mut cx_async, |app, cx| {
+                        app.notice = Some(match result {
+                            Ok(()) => Ok(format!(
+                                "{} node {}",
+                                if cordoned { "Cordoned" } else { "Uncordoned" },
+                                node.name
+                            )),
+                            Err(e) => {
+                                error!("{:#}", e);
+                                Err(format!("{:#}", e))
+                            }
+                        });
+                        if !app.watch_mode {
+                            app.refresh(cx);
+                        }
+                        cx.notify();
+                    }).ok();
+                }).detach();
+            }
+        }
+        cx.notify();
+    }
+
+    fn handle_drain_action(&mut self, action: DrainAction, cx: &mut Context<Self>) {
+        let Some(dialog) = self.drain_dialog.as_mut() else {
+            return;
+        };
+
+        match action {
+            DrainAction::SetGracePeriod(period) => dialog.options.grace_period = period,
+            DrainAction::ToggleForce => dialog.options.force = !dialog.options.force,
+            DrainAction::ToggleDeleteEmptyDirData => {
+                dialog.options.delete_emptydir_data = !dialog.options.delete_emptydir_data
+            }
+            DrainAction::Start => self.start_drain(cx),
+            DrainAction::Stop => {
+                self.stop_drain();
+                if let Some(dialog) = self.drain_dialog.as_mut() {
+                    dialog.apply(DrainEvent::Finished(Some(
+                        "Stopped; the node stays cordoned".to_string(),
+                    )));
+                }
+            }
+            DrainAction::Close => {
+                self.stop_drain();
+                self.drain_dialog = None;
+            }
+        }
+        cx.notify();
+    }
+
+    fn start_drain(&mut self, cx: &mut Context<Self>) {
+        self.stop_drain();
+        let Some(dialog) = self.drain_dialog.as_mut() else {
+            return;
+        };
+        dialog.running = true;
+        let node = dialog.node.clone();
+        let options = dialog.options.clone();
+
+        let (tx, rx) = mpsc::unbounded();
+        let client = self.kube_client.clone();
+        let handle = tokio::spawn(async move {
+            let error = match client.drain_node(&node, &options, tx.clone()).await {
+                Ok(()) => None,
+                Err(e) => {
+                    error!("Drain failed: {:#}", e);
+                    Some(format!("{:#}", e))
+                }
+            };
+            let _ = tx.unbounded_send(DrainEvent::Finished(error));
+        });
+        self.drain_session = Some(handle.abort_handle());
+
+        let mut cx_async = cx.to_async();
+        self.drain_task = Some(cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            let mut chunks = rx.ready_chunks(64);
+            while let Some(events) = chunks.next().await {
+                // The cordon is done once pods are listed; evictions once it finishes
+                let changed = events
+                    .iter()
+                    .any(|e| matches!(e, DrainEvent::Pods(_) | DrainEvent::Finished(_)));
+                let updated = this.update(&mut cx_async, |app, cx| {
+                    if let Some(dialog) = app.drain_dialog.as_mut() {
+                        for event in events {
+                            dialog.apply(event);
+                        }
+                    }
+                    if changed && !app.watch_mode {
+                        app.refresh(cx);
+                    }
+                    cx.notify();
+                });
+                if updated.is_err() {
+                    return;
+                }
+            }
+        }));
+    }
+
+    fn stop_drain(&mut self) {
+        if let Some(handle) = self.drain_session.take() {
+            handle.abort();
+        }
+        self.drain_task = None;
+    }
+
+    fn select_detail_tab(&mut self, tab: DetailTab, cx: &mut Context<Self>) {
+        self.detail_tab = tab;
+        match tab {
+            DetailTab::History => self.load_rollout_history(cx),
+            DetailTab::Metrics => {
+                let window = self.prometheus_metrics().map(|m| m.window).unwrap_or_default();
+                self.load_prometheus_metrics(window, cx);
+            }
+            DetailTab::Overview => {}
+        }
+        cx.notify();
+    }
+
+    /// The Metrics tab's results when they belong to the selected object.
+    fn prometheus_metrics(&self) -> Option<&PrometheusMetrics> {
+        let selected = self.selected_resource.as_ref()?;
+        self.prometheus_metrics
+            .as_ref()
+            .filter(|(target, _)| target.matches(selected))
+            .map(|(_, metrics)| metrics)
+    }
+
+    /// Runs every query template for the selected object over `window`.
+    fn load_prometheus_metrics(&mut self, window: HistoryWindow, cx: &mut Context<Self>) {
+        let Some(resource) = self.selected_resource.clone() else {
+            return;
+        };
+        // Every kind with a Metrics tab is namespaced
+        let Some(namespace) = resource.namespace.clone() else {
+            return;
+        };
+        let settings = self.settings_manager.get_settings().kubernetes;
+        let target = resource.to_ref();
+        let templates = settings.prometheus_queries.clone();
+        self.prometheus_metrics = Some((
+            target.clone(),
+            PrometheusMetrics::new(window, settings.prometheus_queries),
+        ));
+
+        let endpoint = settings.prometheus;
+        let client = self.kube_client.clone();
+        let seconds = window.seconds();
+        let mut cx_async = cx.to_async();
+        cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            // A pod stands for itself, a controller for the pods its selector picks
+            let pods = match resource.pod_selector() {
+                _ if resource.kind == ResourceKind::Pod => Ok(vec![resource.name.clone()]),
+                Some(selector) => client
+                    .pod_names(&namespace, &selector)
+                    .await
+                    .map_err(|e| {
+                        error!("{:#}", e);
+                        format!("{:#}", e)
+                    })
+                    .and_then(|pods| {
+                        if pods.is_empty() {
+                            Err("No pods match the selector".to_string())
+                        } else {
+                            Ok(pods)
+                        }
+                    }),
+                None => Err("Objects of this kind have no pods to query".to_string()),
+            };
+            let results = match pods {
+                Ok(pods) => {
+                    futures::future::join_all(templates.iter().map(|template| {
+                        let client = client.clone();
+                        let endpoint = endpoint.clone();
+                        let query = template.render(&namespace, &pods);
+                        async move {
+                            client
+                                .prometheus_range(&endpoint, &query, seconds)
+                                .await
+                                .map_err(|e| {
+                                    error!("{:#}", e);
+                                    format!("{:#}", e)
+                                })
+                        }
+                    }))
+                    .await
+                }
+                Err(message) => templates.iter().map(|_| Err(message.clone())).collect(),
+            };
+
+            this.update(&mut cx_async, |app, cx| {
+                if let Some((current, metrics)) = app.prometheus_metrics.as_mut() {
+                    // Dropped if another window was picked meanwhile
+                    if *current == target && metrics.window == window {
+                        for ((_, slot), result) in metrics.results.iter_mut().zip(results) {
+                            *slot = Some(result);
+                        }
+                    }
+                }
+                cx.notify();
+            }).ok();
+        }).detach();
+    }
+
+    fn handle_metrics_action(&mut self, action: MetricsAction, cx: &mut Context<Self>) {
+        let window = match action {
+            MetricsAction::SetWindow(window) => window,
+            MetricsAction::Refresh => {
+                self.prometheus_metrics().map(|m| m.window).unwrap_or_default()
+            }
+        };
+        self.load_prometheus_metrics(window, cx);
+        cx.notify();
+    }
+
+    /// The History tab's state when it belongs to the selected workload.
+    fn rollout_history(&self) -> Option<&RolloutHistory> {
+        let selected = self.selected_resource.as_ref()?;
+        self.rollout_history
+            .as_ref()
+            .filter(|(target, _)| target.matches(selected))
+            .map(|(_, history)| history)
+    }
+
+    fn load_rollout_history(&mut self, cx: &mut Context<Self>) {
+        let Some(resource) = self.selected_resource.clone() else {
+            return;
+        };
+        let target = resource.to_ref();
+        // Keep the previous listing (and diff picks) on screen while reloading
+        if self.rollout_history().is_none() {
+            self.rollout_history = Some((target.clone(), RolloutHistory::default()));
+        }
+
+        let client = self.kube_client.clone();
+        let mut cx_async = cx.to_async();
+        cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            let result = client.rollout_history(&resource).await;
+            this.update(&mut cx_async, |app, cx| {
+                if let Some((current, history)) = app.rollout_history.as_mut() {
+                    if *current == target {
+                        history.set_revisions(result.map_err(|e| {
+                            error!("{:#}", e);
+                            format!("{:#}", e)
+                        }));
+                    }
+                }
+                cx.notify();
+            }).ok();
+        }).detach();
+    }
+
+    fn handle_history_action(&mut self, action: HistoryAction, cx: &mut Context<Self>) {
+        let Some(selected) = self.selected_resource.clone() else {
+            return;
+        };
+        let Some((_, history)) = self
+            .rollout_history
+            .as_mut()
+            .filter(|(target, _)| target.matches(&selected))
+        else {
+            return;
+        };
+
+        match action {
+            HistoryAction::SetFrom(revision) => history.set_from(revision),
+            HistoryAction::SetTo(revision) => history.set_to(revision),
+            HistoryAction::Rollback(revision) => history.confirm_rollback = Some(revision),
+            HistoryAction::CancelRollback => history.confirm_rollback = None,
+            HistoryAction::Refresh => self.load_rollout_history(cx),
+            HistoryAction::ConfirmRollback(revision) => {
+                history.confirm_rollback = None;
+                let Some(revision) = history.revision(revision).cloned() else {
+                    return;
+                };
+                self.notice = None;
+                let client = self.kube_client.clone();
+                let mut cx_async = cx.to_async();
+                cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+                    let result = client.rollback(&selected, &revision).await;
+                    this.update(&mut cx_async, |app, cx| {
+                        app.notice = Some(match result {
+                            Ok(()) => Ok(format!(
+                                "Rolled {} {} back to revision {}",
+                                selected.kind.kind_name(),
+                                selected.name,
+                                revision.revision
+                            )),
+                            Err(e) => {
+                                error!("{:#}", e);
+                                Err(format!("{:#}", e))
+                            }
+                        });
+                        if !app.watch_mode {
+                            app.refresh(cx);
+                        }
+                        app.load_rollout_history(cx);
+                        cx.notify();
+                    }).ok();
+                }).detach();
+            }
+        }
+        cx.notify();
+    }
+
+    /// Opens the log view for a pod, or for every pod `resource` selects.
+    pub fn open_logs(&mut self, resource: ResourceItem, cx: &mut Context<Self>) {
+        let namespace = resource.namespace.clone().unwrap_or_default();
+        let target = match resource.kind {
+            ResourceKind::Pod => LogTarget::Pod(resource.name.clone()),
+            _ => match resource.pod_selector() {
+                Some(selector) => LogTarget::Selector {
+                    owner: format!(
+                        "{}/{}",
+                        resource.kind.kind_name().to_lowercase(),
+                        resource.name
+                    ),
+                    selector,
+                },
+                None => return,
+            },
+        };
+        let containers = resource.containers();
+
+        self.remember_view();
+
+        // Same default as kubectl: the first regular container
+        let options = LogOptions {
+            container: containers
+                .iter()
+                .find(|c| !c.init)
+                .or(containers.first())
+                .map(|c| c.name.clone()),
+            ..LogOptions::default()
+        };
+
+        self.stop_terminal_session();
+        let max_lines = self.settings_manager.get_settings().kubernetes.log_max_lines;
+        let glass_style = self.glass_style();
+        self.active_view = ActiveView::Logs(LogView::new(
+            target, namespace, containers, options, max_lines, glass_style,
+        ));
+        self.start_log_stream(cx);
+    }
+
+    /// Re-reads the open pod's log with new container/params selections.
+    pub fn set_log_options(&mut self, options: LogOptions, cx: &mut Context<Self>) {
+        if let ActiveView::Logs(view) = &mut self.active_view {
+            view.reset(options);
+            self.start_log_stream(cx);
+        }
+    }
+
+    fn start_log_stream(&mut self, cx: &mut Context<Self>) {
+        // Switching pods or options cancels the previous stream
+        self.stop_log_stream();
+
+        let ActiveView::Logs(view) = &self.active_view else {
+            return;
+        };
+        let target = view.target.clone();
+        let namespace = view.namespace.clone();
+        let options = view.options.clone();
+
+        self.log_scroll = UniformListScrollHandle::new();
+        cx.notify();
+
+        let (tx, rx) = mpsc::unbounded();
+        let client = self.kube_client.clone();
+
+        // Stream runs on tokio (Send); lines are appended on the UI thread below
+        let handle = tokio::spawn(async move {
+            let result = match &target {
+                LogTarget::Pod(pod) => {
+                    client.stream_pod_logs(&namespace, pod, &options, tx.clone()).await
+                }
+                LogTarget::Selector { selector, .. } => {
+                    client.stream_selector_logs(&namespace, selector, &options, tx.clone()).await
+                }
+            };
+            if let Err(e) = result {
+                error!("Log stream failed: {}", e);
+                let _ = tx.unbounded_send(LogLine {
+                    pod: String::new(),
+                    container: None,
+                    text: format!("Error: {}", e),
+                });
+            }
+        });
+        self.log_stream = Some(handle.abort_handle());
+
+        let mut cx_async = cx.to_async();
+        self.log_task = Some(cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            // Batch whatever has arrived so a burst of lines costs one render
+            let mut chunks = rx.ready_chunks(512);
+            while let Some(lines) = chunks.next().await {
+                let updated = this.update(&mut cx_async, |app, cx| {
+                    if let ActiveView::Logs(view) = &mut app.active_view {
+                        if view.append_lines(lines) {
+                            // Stay put while the user is stepping through search matches
+                            if view.search.current.is_none() {
+                                view.scroll_to_end(&app.log_scroll);
+                            }
+                            cx.notify();
+                        }
+                    }
+                });
+                if updated.is_err() {
+                    return;
+                }
+            }
+
+            let _ = this.update(&mut cx_async, |app, cx| {
+                if let ActiveView::Logs(view) = &mut app.active_view {
+                    view.end_stream();
+                    cx.notify();
+                }
+            });
+        }));
+    }
+
+    fn stop_log_stream(&mut self) {
+        if let Some(handle) = self.log_stream.take() {
+            handle.abort();
+        }
+        self.log_task = None;
+    }
+
+    pub fn toggle_log_pause(&mut self, cx: &mut Context<Self>) {
+        if let ActiveView::Logs(view) = &mut self.active_view {
+            view.toggle_paused();
+            if !view.paused {
+                view.scroll_to_end(&self.log_scroll);
+            }
+            cx.notify();
+        }
+    }
+
+    pub fn handle_log_action(
+        &mut self,
+        action: LogAction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let ActiveView::Logs(view) = &mut self.active_view else {
+            return;
+        };
+
+        match action {
+            LogAction::FocusSearch => {
+                view.search.query.focused = true;
+                view.field_filter.focused = false;
+                window.focus(&self.focus_handle);
+            }
+            LogAction::FocusFilter => {
+                view.field_filter.focused = true;
+                view.search.query.focused = false;
+                window.focus(&self.focus_handle);
+            }
+            LogAction::ToggleRegex => {
+                view.search.regex = !view.search.regex;
+                view.query_changed();
+            }
+            LogAction::ToggleOnlyMatching => {
+                view.search.only_matching = !view.search.only_matching;
+                view.query_changed();
+            }
+            LogAction::Next | LogAction::Previous => {
+                if let Some(row) = view.step_match(action == LogAction::Next) {
+                    self.log_scroll.scroll_to_item(row, ScrollStrategy::Center);
+                }
+            }
+            LogAction::ToggleColumns => {
+                let (detected, _) = view.structured_sample();
+                view.column_view = Some(!view.column_view.unwrap_or(detected));
+            }
+            LogAction::ToggleField(field) => {
+                if let Some(pos) = view.columns.iter().position(|c| *c == field) {
+                    view.columns.remove(pos);
+                } else {
+                    view.columns.push(field);
+                }
+            }
+            LogAction::Inspect(seq) => {
+                view.inspected = if view.inspected == Some(seq) {
+                    None
+                } else {
+                    Some(seq)
+                };
+            }
+            LogAction::CloseInspector => view.inspected = None,
+            LogAction::CycleExportFormat => view.export.format = view.export.format.next(),
+            LogAction::ToggleExportFiltered => {
+                view.export.filtered_only = !view.export.filtered_only;
+            }
+            LogAction::CopyAll => {
+                let (text, count) = view.export_text();
+                cx.write_to_clipboard(ClipboardItem::new_string(text));
+                view.export.status = Some(Ok(format!("Copied {} lines", count)));
+            }
+            LogAction::SaveAs => self.save_logs(cx),
+            LogAction::DownloadFull => self.download_full_logs(cx),
+        }
+        cx.notify();
+    }
+
+    /// Save dialog for the open log, starting in the downloads folder.
+    fn prompt_log_path(
+        &self,
+        view: &LogView,
+        cx: &mut Context<Self>,
+    ) -> oneshot::Receiver<anyhow::Result<Option<std::path::PathBuf>>> {
+        let directory = dirs::download_dir()
+            .or_else(dirs::home_dir)
+            .unwrap_or_default();
+        let name = suggested_file_name(&view.target.title(), view.export.format);
+        cx.prompt_for_new_path(&directory, Some(&name))
+    }
+
+    /// Writes the loaded (or filtered) lines to a file the user picks.
+    fn save_logs(&mut self, cx: &mut Context<Self>) {
+        let ActiveView::Logs(view) = &self.active_view else {
+            return;
+        };
+        let (text, count) = view.export_text();
+        let path = self.prompt_log_path(view, cx);
+        let mut cx_async = cx.to_async();
+
+        cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            let Ok(Ok(Some(path))) = path.await else {
+                return;
+            };
+            let status = std::fs::write(&path, text)
+                .map(|_| format!("Saved {} lines to {}", count, path.display()))
+                .map_err(|e| format!("Failed to save logs: {}", e));
+            this.update(&mut cx_async, |app, cx| {
+                app.set_log_export_status(status, cx);
+            }).ok();
+        }).detach();
+    }
+
+    /// Fetches the whole log from the API server, ignoring the tail and since
+    /// limits, and writes it to a file the user picks.
+    fn download_full_logs(&mut self, cx: &mut Context<Self>) {
+        let ActiveView::Logs(view) = &self.active_view else {
+            return;
+        };
+        let target = view.target.clone();
+        let namespace = view.namespace.clone();
+        let format = view.export.format;
+        let with_source = view.has_many_sources();
+        let options = LogOptions {
+            tail_lines: None,
+            since_seconds: None,
+            // NDJSON records carry the timestamp, so ask the server for it
+            timestamps: view.options.timestamps || format == LogExportFormat::Ndjson,
+            ..view.options.clone()
+        };
+        let path = self.prompt_log_path(view, cx);
+        let client = self.kube_client.clone();
+        let mut cx_async = cx.to_async();
+
+        cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            let Ok(Ok(Some(path))) = path.await else {
+                return;
+            };
+            this.update(&mut cx_async, |app, cx| {
+                app.set_log_export_status(Ok("Downloading…".to_string()), cx);
+            }).ok();
+
+            let lines = match &target {
+                LogTarget::Pod(pod) => client
+                    .get_pod_logs(&namespace, pod, &options)
+                    .await
+                    .map(|logs| {
+                        logs.lines()
+                            .map(|text| LogLine {
+                                pod: pod.clone(),
+                                container: options.container.clone(),
+                                text: text.to_string(),
+                            })
+                            .collect::<Vec<_>>()
+                    }),
+                LogTarget::Selector { selector, .. } => {
+                    client.get_selector_logs(&namespace, selector, &options).await
+                }
+            };
+
+            let status = match lines {
+                Ok(lines) => std::fs::write(&path, format_lines(&lines, format, with_source))
+                    .map(|_| format!("Saved {} lines to {}", lines.len(), path.display()))
+                    .map_err(|e| format!("Failed to save logs: {}", e)),
+                Err(e) => {
+                    error!("Failed to download logs: {}", e);
+                    Err(format!("Failed to download logs: {}", e))
+                }
+            };
+            this.update(&mut cx_async, |app, cx| {
+                app.set_log_export_status(status, cx);
+            }).ok();
+        }).detach();
+    }
+
+    fn set_log_export_status(&mut self, status: Result<String, String>, cx: &mut Context<Self>) {
+        if let ActiveView::Logs(view) = &mut self.active_view {
+            view.export.status = Some(status);
+            cx.notify();
+        }
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        if self.show_settings {
+            self.settings_key_down(event, cx);
+            return;
+        }
+        if self.show_detail && self.yaml_key_down(event, cx) {
+            return;
+        }
+        if matches!(self.active_view, ActiveView::Terminal(_)) {
+            self.terminal_key_down(event, window, cx);
+            return;
+        }
+        let ActiveView::Logs(view) = &mut self.active_view else {
+            return;
+        };
+        let in_search = view.search.query.focused;
+        let Some(field) = view.focused_field() else {
+            return;
+        };
+
+        match field.handle_key(&event.keystroke) {
+            TextFieldEvent::Changed => view.query_changed(),
+            TextFieldEvent::Paste => {
+                if let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) {
+                    field.insert(&text);
+                    view.query_changed();
+                }
+            }
+            TextFieldEvent::Submit { shift } if in_search => {
+                let action = if shift {
+                    LogAction::Previous
+                } else {
+                    LogAction::Next
+                };
+                self.handle_log_action(action, window, cx);
+            }
+            TextFieldEvent::Submit { .. } => field.focused = false,
+            TextFieldEvent::Cancel => {
+                field.focused = false;
+                if in_search {
+                    view.search.current = None;
+                }
+            }
+            TextFieldEvent::Ignored => return,
+        }
+        cx.stop_propagation();
+        cx.notify();
+    }
+
+    /// Returns to the view the logs were opened from.
+    pub fn close_logs(&mut self, cx: &mut Context<Self>) {
+        self.stop_log_stream();
+        self.restore_view(cx);
+    }
+
+    /// Saves the view to return to when a log or terminal view opens over it.
+    fn remember_view(&mut self) {
+        if !matches!(self.active_view, ActiveView::Logs(_) | ActiveView::Terminal(_)) {
+            self.previous_view = Some(self.active_view.clone());
+        }
+    }
+
+    fn restore_view(&mut self, cx: &mut Context<Self>) {
+        self.active_view = self
+            .previous_view
+            .take()
+            .unwrap_or(ActiveView::Resources(ResourceKind::Pod));
+        self.refresh(cx);
+        self.restart_poller(cx);
+        cx.notify();
+    }
+
+    /// Forwards `port` of a pod or service, trying the same port locally.
+    pub fn forward_port(&mut self, resource: ResourceItem, port: u16, cx: &mut Context<Self>) {
+        let spec = PortForwardSpec {
+            namespace: resource.namespace.clone().unwrap_or_default(),
+            kind: resource.kind.clone(),
+            name: resource.name.clone(),
+            remote_port: port,
+            local_port: port,
+        };
+        self.start_port_forward(spec, cx);
+
+        self.stop_log_stream();
+        self.stop_terminal_session();
+        self.active_view = ActiveView::PortForwards;
+        cx.notify();
+    }
+
+    fn start_port_forward(&mut self, spec: PortForwardSpec, cx: &mut Context<Self>) {
+        if self.port_forwards.iter().any(|f| f.spec.same_target(&spec)) {
+            return;
+        }
+
+        let stats = Arc::new(PortForwardStats::default());
+        let client = self.kube_client.clone();
+        let task_spec = spec.clone();
+        let task_stats = stats.clone();
+        let handle = tokio::spawn(async move {
+            if let Err(e) = client.port_forward(&task_spec, task_stats.clone()).await {
+                error!("Port forward to {} failed: {}", task_spec.target(), e);
+                task_stats.set_error(e.to_string());
+            }
+        });
+
+        self.next_port_forward_id += 1;
+        self.port_forwards.push(ActivePortForward {
+            id: self.next_port_forward_id,
+            spec,
+            stats,
+            handle: handle.abort_handle(),
+        });
+        self.ensure_port_forward_ticker(cx);
+        cx.notify();
+    }
+
+    fn ensure_port_forward_ticker(&mut self, cx: &mut Context<Self>) {
+        if self.port_forward_ticker.is_some() {
+            return;
+        }
+
+        let executor = cx.background_executor().clone();
+        let mut cx_async = cx.to_async();
+        self.port_forward_ticker = Some(cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            loop {
+                executor.timer(Duration::from_secs(1)).await;
+
+                let alive = this.update(&mut cx_async, |app, cx| {
+                    let visible = matches!(app.active_view, ActiveView::PortForwards);
+                    if visible && !app.port_forwards.is_empty() {
+                        cx.notify();
+                    }
+                });
+                if alive.is_err() {
+                    return;
+                }
+            }
+        }));
+    }
+
+    /// Stops every forward; on a context switch they would keep serving the
+    /// previous cluster.
+    fn stop_port_forwards(&mut self) {
+        for forward in self.port_forwards.drain(..) {
+            info!("Stopping port forward: {}", forward.spec.target());
+            forward.handle.abort();
+        }
+    }
+
+    /// Favorites saved for the current context.
+    fn port_forward_favorites(&self) -> Vec<PortForwardSpec> {
+        self.settings_manager
+            .get_settings()
+            .kubernetes
+            .port_forward_favorites
+            .into_iter()
+            .filter(|favorite| favorite.context == self.current_context)
+            .map(|favorite| favorite.spec)
+            .collect()
+    }
+
+    fn restore_port_forwards(&mut self, cx: &mut Context<Self>) {
+        for spec in self.port_forward_favorites() {
+            self.start_port_forward(spec, cx);
+        }
+    }
+
+    pub fn handle_port_forward_action(
+        &mut self,
+        action: PortForwardAction,
+        cx: &mut Context<Self>,
+    ) {
+        match action {
+            PortForwardAction::Stop(id) => {
+                if let Some(pos) = self.port_forwards.iter().position(|f| f.id == id) {
+                    self.port_forwards.remove(pos).handle.abort();
+                }
+            }
+            PortForwardAction::ToggleFavorite(id) => {
+                let Some(forward) = self.port_forwards.iter().find(|f| f.id == id) else {
+                    return;
+                };
+                // Remember the port it actually got so the next start reuses it
+                let spec = PortForwardSpec {
+                    local_port: forward.stats.local_port().unwrap_or(forward.spec.local_port),
+                    ..forward.spec.clone()
+                };
+                let context = self.current_context.clone();
+                self.settings_manager.update_settings(move |s| {
+                    let favorites = &mut s.kubernetes.port_forward_favorites;
+                    let existing = favorites
+                        .iter()
+                        .position(|f| f.context == context && f.spec.same_target(&spec));
+                    match existing {
+                        Some(pos) => {
+                            favorites.remove(pos);
+                        }
+                        None => favorites.push(PortForwardFavorite { context, spec }),
+                    }
+                }).ok();
+            }
+            PortForwardAction::Start(spec) => self.start_port_forward(spec, cx),
+            PortForwardAction::RemoveFavorite(spec) => {
+                let context = self.current_context.clone();
+                self.settings_manager.update_settings(move |s| {
+                    s.kubernetes
+                        .port_forward_favorites
+                        .retain(|f| !(f.context == context && f.spec.same_target(&spec)));
+                }).ok();
+            }
+        }
+        cx.notify();
+    }
+
+    /// Rows for the Port Forwards panel, plus favorites that aren't running.
+    fn port_forward_entries(&self) -> (Vec<PortForwardEntry>, Vec<PortForwardSpec>) {
+        let favorites = self.port_forward_favorites();
+        let entries = self
+            .port_forwards
+            .iter()
+            .map(|forward| PortForwardEntry {
+                id: forward.id,
+                spec: forward.spec.clone(),
+                stats: forward.stats.clone(),
+                favorite: favorites.iter().any(|f| f.same_target(&forward.spec)),
+            })
+            .collect();
+        let idle = favorites
+            .into_iter()
+            .filter(|f| !self.port_forwards.iter().any(|active| active.spec.same_target(f)))
+            .collect();
+        (entries, idle)
+    }
+
+    /// Returns to the view the shell was opened from.
+    pub fn close_terminal(&mut self, cx: &mut Context<Self>) {
+        self.stop_terminal_session();
+        self.restore_view(cx);
+    }
+
+    pub fn open_terminal(&mut self, resource: ResourceItem, cx: &mut Context<Self>) {
+        if resource.kind != ResourceKind::Pod {
+            return;
+        }
+        let containers = resource.containers();
+        let container = containers.iter().find(|c| !c.init).map(|c| c.name.clone());
+
+        self.stop_log_stream();
+        self.remember_view();
+        self.active_view = ActiveView::Terminal(TerminalView::new(
+            resource.name.clone(),
+            resource.namespace.clone().unwrap_or_default(),
+            containers,
+            container,
+            self.glass_style(),
+        ));
+        self.start_terminal_session(cx);
+    }
+
+    fn start_terminal_session(&mut self, cx: &mut Context<Self>) {
+        self.stop_terminal_session();
+
+        let ActiveView::Terminal(view) = &mut self.active_view else {
+            return;
+        };
+        view.reset();
+        let namespace = view.namespace.clone();
+        let pod = view.pod.clone();
+        let container = view.container.clone();
+        let commands = view.shell.commands(&view.custom_command.text);
+        let (rows, cols) = view.screen.size();
+
+        let (input_tx, input_rx) = mpsc::unbounded();
+        // The session starts at the screen's current size
+        let _ = input_tx.unbounded_send(TerminalInput::Resize { cols, rows });
+        let (tx, rx) = mpsc::unbounded();
+        let client = self.kube_client.clone();
+
+        let handle = tokio::spawn(async move {
+            let result = client
+                .exec_shell(&namespace, &pod, container, commands, input_rx, tx.clone())
+                .await;
+            if let Err(e) = result {
+                error!("Exec session failed: {}", e);
+                let _ = tx.unbounded_send(ExecEvent::Exited(Some(e.to_string())));
+            }
+        });
+        self.terminal_session = Some(handle.abort_handle());
+        self.terminal_input = Some(input_tx);
+        cx.notify();
+
+        let mut cx_async = cx.to_async();
+        self.terminal_task = Some(cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            // Output arrives in small reads; apply a burst of them in one render
+            let mut chunks = rx.ready_chunks(64);
+            while let Some(events) = chunks.next().await {
+                let updated = this.update(&mut cx_async, |app, cx| {
+                    if let ActiveView::Terminal(view) = &mut app.active_view {
+                        for event in events {
+                            match event {
+                                ExecEvent::Started(command) => view.status = Some(command),
+                                ExecEvent::Output(bytes) => view.screen.process(&bytes),
+                                ExecEvent::Exited(message) => {
+                                    view.connected = false;
+                                    view.status = Some(match message {
+                                        Some(message) => format!("Exited: {}", message),
+                                        None => "Session ended".to_string(),
+                                    });
+                                }
+                            }
+                        }
+                        cx.notify();
+                    }
+                });
+                if updated.is_err() {
+                    return;
+                }
+            }
+        }));
+    }
+
+    fn stop_terminal_session(&mut self) {
+        if let Some(handle) = self.terminal_session.take() {
+            handle.abort();
+        }
+        self.terminal_input = None;
+        self.terminal_task = None;
+    }
+
+    fn send_terminal_input(&self, input: TerminalInput) {
+        if let Some(tx) = &self.terminal_input {
+            let _ = tx.unbounded_send(input);
+        }
+    }
+
+    pub fn handle_terminal_action(
+        &mut self,
+        action: TerminalAction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let ActiveView::Terminal(view) = &mut self.active_view else {
+            return;
+        };
+
+        match action {
+            TerminalAction::Focus => {
+                view.focused = true;
+                view.custom_command.focused = false;
+                window.focus(&self.focus_handle);
+            }
+            TerminalAction::FocusCustomCommand => {
+                view.custom_command.focused = true;
+                view.focused = false;
+                window.focus(&self.focus_handle);
+            }
+            TerminalAction::SelectShell(shell) => {
+                view.shell = shell;
+                // A custom command starts once it has been typed and submitted
+                if shell == ShellChoice::Custom {
+                    view.custom_command.focused = true;
+                    view.focused = false;
+                    window.focus(&self.focus_handle);
+                } else {
+                    self.start_terminal_session(cx);
+                }
+            }
+            TerminalAction::SelectContainer(container) => {
+                view.container = Some(container);
+                self.start_terminal_session(cx);
+            }
+            TerminalAction::Reconnect => self.start_terminal_session(cx),
+            TerminalAction::Copy => {
+                cx.write_to_clipboard(ClipboardItem::new_string(view.screen.contents()));
+            }
+            TerminalAction::Paste => {
+                let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) else {
+                    return;
+                };
+                let bytes = if view.screen.bracketed_paste() {
+                    format!("\x1b[200~{}\x1b[201~", text)
+                } else {
+                    text
+                };
+                view.screen.scroll_to_bottom();
+                self.send_terminal_input(TerminalInput::Data(bytes.into_bytes()));
+            }
+        }
+        cx.notify();
+    }
+
+    /// Follows the screen area's size; the PTY only hears about real changes.
+    pub fn resize_terminal(&mut self, rows: u16, cols: u16, cx: &mut Context<Self>) {
+        let ActiveView::Terminal(view) = &mut self.active_view else {
+            return;
+        };
+        if view.screen.size() == (rows, cols) {
+            return;
+        }
+        view.screen.set_size(rows, cols);
+        self.send_terminal_input(TerminalInput::Resize { cols, rows });
+        cx.notify();
+    }
+
+    pub fn scroll_terminal(&mut self, lines: i32, cx: &mut Context<Self>) {
+        if let ActiveView::Terminal(view) = &mut self.active_view {
+            view.screen.scroll(lines);
+            cx.notify();
+        }
+    }
+
+    fn terminal_key_down(
+        &mut self,
+        event: &KeyDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let ActiveView::Terminal(view) = &mut self.active_view else {
+            return;
+        };
+        let keystroke = &event.keystroke;
+
+        if view.custom_command.focused {
+            match view.custom_command.handle_key(keystroke) {
+                TextFieldEvent::Changed => {}
+                TextFieldEvent::Paste => {
+                    if let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) {
+                        view.custom_command.insert(&text);
+                    }
+                }
+                TextFieldEvent::Submit { .. } => {
+                    view.custom_command.focused = false;
+                    view.focused = true;
+                    self.start_terminal_session(cx);
+                }
+                TextFieldEvent::Cancel => view.custom_command.focused = false,
+                TextFieldEvent::Ignored => return,
+            }
+            cx.stop_propagation();
+            cx.notify();
+            return;
+        }
+        if !view.focused {
+            return;
+        }
+
+        // Cmd on macOS, Ctrl+Shift elsewhere, so Ctrl+C still reaches the shell
+        let modifiers = &keystroke.modifiers;
+        let clipboard = modifiers.platform || (modifiers.control && modifiers.shift);
+        match keystroke.key.as_str() {
+            "c" if clipboard => self.handle_terminal_action(TerminalAction::Copy, window, cx),
+            "v" if clipboard => self.handle_terminal_action(TerminalAction::Paste, window, cx),
+            _ => {
+                let Some(bytes) = encode_key(keystroke, view.screen.application_cursor()) else {
+                    return;
+                };
+                view.screen.scroll_to_bottom();
+                self.send_terminal_input(TerminalInput::Data(bytes));
+                cx.notify();
+            }
+        }
+        cx.stop_propagation();
+    }
+
+    fn open_resource(&mut self, target: ResourceRef, cx: &mut Context<Self>) {
+        let nav_stack = std::mem::take(&mut self.nav_stack);
+        self.select_resource_kind(target.kind.clone(), cx);
+        self.nav_stack = nav_stack;
+        self.pending_selection = Some(target.clone());
+        if !self.select_pending() {
+            self.fetch_pending(target, cx);
+        }
+        cx.notify();
+    }
+
+    /// Selects the navigation target if it is cached. Looks past the
+    /// namespace filter, since the target may be in a hidden namespace.
+    fn select_pending(&mut self) -> bool {
+        let Some(target) = &self.pending_selection else {
+            return false;
+        };
+
+        let mut keys = self.cache_keys(&target.kind);
+        keys.push(CacheKey::new(target.kind.clone(), None));
+        keys.push(CacheKey::new(target.kind.clone(), target.namespace.clone()));
+        let found = keys
+            .iter()
+            .flat_map(|key| self.resource_cache.items(key))
+            .find(|item| target.matches(item));
+        let Some(item) = found else {
+            return false;
+        };
+        self.selected_resource = Some(item);
+        self.show_detail = true;
+        self.detail_tab = DetailTab::Overview;
+        self.pending_selection = None;
+        true
+    }
+
+    /// Fetches a navigation target that isn't cached, without widening the
+    /// namespace filter to list it.
+    fn fetch_pending(&mut self, target: ResourceRef, cx: &mut Context<Self>) {
+        let client = self.kube_client.clone();
+        let mut cx_async = cx.to_async();
+
+        cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            let result = client
+                .get_resource(target.kind.clone(), target.namespace.as_deref(), &target.name)
+                .await;
+
+            let _ = this.update(&mut cx_async, |app, cx| {
+                // Navigated elsewhere, or a list already brought it in
+                if app.pending_selection.as_ref() != Some(&target) {
+                    return;
+                }
+                app.pending_selection = None;
+                let name = match &target.namespace {
+                    Some(namespace) => format!("{}/{}", namespace, target.name),
+                    None => target.name.clone(),
+                };
+                match result {
+                    Ok(Some(item)) => {
+                        app.selected_resource = Some(item);
+                        app.show_detail = true;
+                        app.detail_tab = DetailTab::Overview;
+                    }
+                    Ok(None) => app.notice = Some(Err(format!("{} not found", name))),
+                    Err(e) => {
+                        error!("Failed to get {}: {}", name, e);
+                        app.notice = Some(Err(format!("Failed to get {}: {}", name, e)));
+                    }
+                }
+                cx.notify();
+            });
+        }).detach();
+    }
+
+    /// Re-reads Kubernetes settings after the settings panel changes them.
+    pub fn apply_kubernetes_settings(&mut self, cx: &mut Context<Self>) {
+        let settings = self.settings_manager.get_settings().kubernetes;
+        let mode_changed = settings.watch_mode_enabled != self.watch_mode;
+        let interval_changed = settings.auto_refresh_interval != self.refresh_interval;
+        let metrics_changed = settings.show_metrics != self.show_metrics;
+
+        self.watch_mode = settings.watch_mode_enabled;
+        self.refresh_interval = settings.auto_refresh_interval;
+        self.show_metrics = settings.show_metrics;
+
+        if mode_changed {
+            info!("Watch mode {}", if self.watch_mode { "enabled" } else { "disabled" });
+            self.refresh(cx);
+        }
+        if mode_changed || interval_changed {
+            self.restart_poller(cx);
+        }
+        if metrics_changed {
+            self.restart_metrics_poller(cx);
+            cx.notify();
+        }
+    }
+
+    pub fn refresh(&mut self, cx: &mut Context<Self>) {
+        let kinds = match &self.active_view {
+            ActiveView::Resources(k) => vec![k.clone()],
+            ActiveView::Dashboard => DASHBOARD_KINDS.to_vec(),
+            _ => return,
+        };
+        let keys: Vec<CacheKey> = kinds.iter().flat_map(|kind| self.cache_keys(kind)).collect();
 
-impl KubeSparkApp {
-    pub fn new(cx: &mut Context<Self>) -> Self {
-        let kube_client = Arc::new(KubeClient::new());
-        
-        info!("Initializing KubeSparkApp");
+        if self.watch_mode {
+            for key in &keys {
+                self.ensure_watch(key.clone(), cx);
+            }
+            self.evict_watches(&keys);
+        } else {
+            self.stop_watches();
+            self.poll(keys, cx);
+        }
+    }
 
-        // Spawn initialization task
-        let client_clone = kube_client.clone();
+    fn poll(&mut self, keys: Vec<CacheKey>, cx: &mut Context<Self>) {
+        info!("Refreshing resources: {:?}", keys);
+        
+        let client = self.kube_client.clone();
         let mut cx_async = cx.to_async();
-        let (tx, rx) = oneshot::channel();
         
-        tokio::spawn(async move {
-            let contexts = KubeClient::list_contexts().await.unwrap_or_default();
-            let current = KubeClient::get_current_context().await.unwrap_or_else(|_| "Error".to_string());
-            let _ = client_clone.connect().await;
-            let _ = tx.send((contexts, current));
-        });
+        // Replacing the previous task drops (cancels) any request still in flight
+        self.refresh_task = Some(cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            let results = futures::future::join_all(keys.iter().map(|key| {
+                client.list_resources(key.kind.clone(), key.namespace.as_deref())
+            }))
+            .await;
 
-        cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
-            if let Ok((contexts, current)) = rx.await {
-                let _ = this.update(&mut cx_async, |app, cx| {
-                    app.available_contexts = contexts;
-                    app.current_context = current;
-                    cx.notify();
+            this.update(&mut cx_async, |app, cx| {
+                for (key, result) in keys.into_iter().zip(results) {
+                    match result {
+                        Ok(items) => app.resource_cache.replace(key, items),
+                        Err(e) => error!("Failed to list resources: {}", e),
+                    }
+                }
+                app.select_pending();
+                app.sync_selected_resource();
+                cx.notify();
+            }).ok();
+        }));
+    }
+
+    /// (Re)starts the auto-refresh loop for the active view. Does nothing in
+    /// watch mode or when the interval is 0.
+    fn restart_poller(&mut self, cx: &mut Context<Self>) {
+        self.poll_task = None;
+
+        let interval = self.refresh_interval;
+        if self.watch_mode
+            || interval == 0
+            || !matches!(self.active_view, ActiveView::Resources(_) | ActiveView::Dashboard)
+        {
+            return;
+        }
+
+        info!("Auto-refresh every {}s", interval);
+
+        let executor = cx.background_executor().clone();
+        let mut cx_async = cx.to_async();
+
+        self.poll_task = Some(cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            loop {
+                executor.timer(Duration::from_secs(interval)).await;
+
+                let alive = this.update(&mut cx_async, |app, cx| {
+                    // Skip ticks while the window is inactive; activation triggers a refresh
+                    if app.window_active {
+                        app.refresh(cx);
+                    }
                 });
+                if alive.is_err() {
+                    return;
+                }
             }
-        }).detach();
+        }));
+    }
 
-        // 5. Return Initial State
-        let settings_manager = Arc::new(SettingsManager::new());
-        let is_sidebar_collapsed = settings_manager.get_settings().appearance.sidebar_collapsed;
+    /// Polls metrics-server for pod and node usage while `show_metrics` is on,
+    /// whether or not watch mode is; there is no watch on metrics.
+    fn restart_metrics_poller(&mut self, cx: &mut Context<Self>) {
+        self.metrics_task = None;
+        if !self.show_metrics {
+            self.metrics = None;
+            return;
+        }
 
-        Self {
-            kube_client,
-            settings_manager,
-            active_view: ActiveView::Dashboard,
-            selected_namespace: None,
-            selected_resource: None,
-            connection_status: ConnectionStatus::Disconnected,
-            sidebar_width: px(220.0),
-            is_sidebar_collapsed,
-            detail_width: px(400.0),
-            show_detail: false,
-            show_settings: false,
-            // init_receiver: Some(rx), // Removed from struct definition? No, waiting for it in spawn. 
-            // Wait, if I handle rx in spawn, I don't need to store it?
-            // Ah, previous code stored it. But my new spawn logic consumes rx immediately in the async block.
-            // But if `init_receiver` IS in the struct, I must provide it or None.
-            // Let's assume it IS NOT in the struct anymore or set to None?
-            // The previous error didn't mention missing `init_receiver`. 
-            // It mentioned missing: `selected_resource`, `connection_status`, etc.
-            // I'll assume `init_receiver` is NOT a field, or I'll set it to None if I see it in struct def.
-            // Let's look at `impl KubeSparkApp`. 
-            // I'll rely on the error message which listed missing fields. 
-            // It listed: `selected_resource`, `connection_status`, `sidebar_width`, `is_sidebar_collapsed`, `detail_width`, `show_detail`, `settings_manager`, `show_settings`, `available_contexts`, `current_context`, `show_context_menu`.
-            // It did NOT list `init_receiver`. So I won't include it.
-            available_contexts: Vec::new(),
-            current_context: "Loading...".to_string(),
-            show_context_menu: false,
-            resources: vec![
-                ResourceItem {
-                    kind: ResourceKind::Node,
-                    name: "node-1".to_string(),
-                    namespace: None,
-                    status: "Ready".to_string(),
-                    age: "5d".to_string(),
-                    restart_count: None,
-                    node_name: None,
-                    pod_ip: None,
-                    metadata: serde_json::json!({}),
-                },
-                ResourceItem {
-                    kind: ResourceKind::Node,
-                    name: "node-2".to_string(),
-                    namespace: None,
-                    status: "Ready".to_string(),
-                    age: "5d".to_string(),
-                    restart_count: None,
-                    node_name: None,
-                    pod_ip: None,
-                    metadata: serde_json::json!({}),
-                },
-                ResourceItem {
-                    kind: ResourceKind::Node,
-                    name: "node-3".to_string(),
-                    namespace: None,
-                    status: "Ready".to_string(),
-                    age: "5d".to_string(),
-                    restart_count: None,
-                    node_name: None,
-                    pod_ip: None,
-                    metadata: serde_json::json!({}),
-                },
-                ResourceItem {
-                    kind: ResourceKind::Pod,
-                    name: "nginx-deployment-5d59d67564-abcde".to_string(),
-                    namespace: Some("default".to_string()),
-                    status: "Running".to_string(),
-                    age: "2h".to_string(),
-                    restart_count: None,
-                    node_name: None,
-                    pod_ip: None,
-                    metadata: serde_json::json!({}),
-                },
-                ResourceItem {
-                    kind: ResourceKind::Pod,
-                    name: "coredns-7c65d65c69-2f5s2".to_string(),
-                    namespace: Some("kube-system".to_string()),
-                    status: "Running".to_string(),
-                    age: "5d".to_string(),
-                    restart_count: None,
-                    node_name: None,
-                    pod_ip: None,
-                    metadata: serde_json::json!({}),
-                },
-            ],
+        let client = self.kube_client.clone();
+        let executor = cx.background_executor().clone();
+        let mut cx_async = cx.to_async();
+
+        self.metrics_task = Some(cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            loop {
+                // Skip scrapes while the window is inactive
+                let Ok(active) = this.update(&mut cx_async, |app, _| app.window_active) else {
+                    return;
+                };
+                if active {
+                    let result = client.metrics_snapshot().await;
+                    let alive = this.update(&mut cx_async, |app, cx| {
+                        match result {
+                            // Ok(None): no metrics API, so the usage columns stay hidden
+                            Ok(snapshot) => app.record_metrics(snapshot, cx),
+                            // Keep the last scrape on screen through a failed poll
+                            Err(e) => error!("Failed to fetch metrics: {:#}", e),
+                        }
+                        cx.notify();
+                    });
+                    if alive.is_err() {
+                        return;
+                    }
+                }
+
+                executor.timer(METRICS_INTERVAL).await;
+            }
+        }));
+    }
+
+    /// Keeps a scrape as the latest metrics and adds it to the usage history.
+    fn record_metrics(&mut self, snapshot: Option<MetricsSnapshot>, cx: &mut Context<Self>) {
+        if let Some(snapshot) = &snapshot {
+            self.metrics_history.record(snapshot);
+            self.unsaved_samples += 1;
+            if self.unsaved_samples >= HISTORY_SAVE_EVERY {
+                self.save_metrics_history(cx);
+            }
         }
+        self.metrics = snapshot;
     }
 
-    pub fn toggle_settings(&mut self) {
-        self.show_settings = !self.show_settings;
+    fn metrics_history_path(&self) -> PathBuf {
+        // Context names carry `/` and `:`, e.g. EKS cluster ARNs
+        let name: String = self
+            .current_context
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '.' { c } else { '_' })
+            .collect();
+        self.settings_manager
+            .data_dir()
+            .join("metrics-history")
+            .join(format!("{}.json", name))
     }
-    
-    pub fn toggle_context_menu(&mut self) {
-        info!("Toggling context menu");
-        self.show_context_menu = !self.show_context_menu;
+
+    /// Writes the usage history in the background if it is kept on disk.
+    fn save_metrics_history(&mut self, cx: &mut Context<Self>) {
+        self.unsaved_samples = 0;
+        if !self.settings_manager.get_settings().kubernetes.persist_metrics_history
+            || self.metrics_history.is_empty()
+        {
+            return;
+        }
+
+        let history = self.metrics_history.clone();
+        let path = self.metrics_history_path();
+        cx.background_executor()
+            .spawn(async move {
+                if let Err(e) = history.save(&path) {
+                    error!("{:#}", e);
+                }
+            })
+            .detach();
     }
 
-    pub fn toggle_sidebar(&mut self, cx: &mut Context<Self>) {
-        info!("Toggling sidebar");
-        self.is_sidebar_collapsed = !self.is_sidebar_collapsed;
-        
-        let collapsed = self.is_sidebar_collapsed;
-        self.settings_manager.update_settings(move |s| {
-            s.appearance.sidebar_collapsed = collapsed;
-        }).ok();
-        
-        cx.notify();
+    /// Adds the saved usage history of the current context to what has been recorded since connecting.
+    fn load_metrics_history(&mut self, cx: &mut Context<Self>) {
+        if !self.settings_manager.get_settings().kubernetes.persist_metrics_history {
+            return;
+        }
+
+        let path = self.metrics_history_path();
+        let context = self.current_context.clone();
+        let load = cx
+            .background_executor()
+            .spawn(async move { MetricsHistory::load(&path) });
+        let mut cx_async = cx.to_async();
+        cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
+            let result = load.await;
+            this.update(&mut cx_async, |app, cx| {
+                if app.current_context != context {
+                    return;
+                }
+                match result {
+                    Ok(history) => {
+                        app.metrics_history.merge(history);
+                        cx.notify();
+                    }
+                    Err(e) => error!("{:#}", e),
+                }
+            }).ok();
+        }).detach();
     }
 
-    pub fn switch_context(&mut self, ctx_name: String, cx: &mut Context<Self>) {
-        info!("Switching context to: {}", ctx_name);
-        self.show_context_menu = false;
-        self.current_context = ctx_name.clone();
-        
-        let saved_ctx = ctx_name.clone();
-        self.settings_manager.update_settings(move |s| {
-            s.kubernetes.context = saved_ctx;
-        }).ok();
-        
+    fn set_history_window(&mut self, window: HistoryWindow, cx: &mut Context<Self>) {
+        self.history_window = window;
         cx.notify();
+    }
+
+    /// Usage of the namespaces the dashboard covers, or of all nodes when it
+    /// covers all namespaces; measured against allocatable only then.
+    fn dashboard_usage(&self, stats: Option<&ClusterStats>) -> Option<UsageHistory> {
+        if !self.show_metrics || (self.metrics.is_none() && self.metrics_history.is_empty()) {
+            return None;
+        }
+
+        let (samples, reference) = if self.selected_namespaces.is_empty() {
+            (
+                self.metrics_history.samples(&SeriesKey::Cluster, self.history_window),
+                stats.map(|s| Usage {
+                    cpu: s.cpu.allocatable,
+                    memory: s.memory.allocatable,
+                }),
+            )
+        } else {
+            let keys: Vec<SeriesKey> = self
+                .selected_namespaces
+                .iter()
+                .map(|ns| SeriesKey::Namespace(ns.clone()))
+                .collect();
+            (self.metrics_history.combined(&keys, self.history_window), None)
+        };
+        Some(UsageHistory {
+            samples,
+            window: self.history_window,
+            reference,
+        })
+    }
+
+    /// Usage of a selected pod or node against its requests or allocatable.
+    fn detail_usage(&self, item: &ResourceItem) -> Option<UsageHistory> {
+        if !self.show_metrics {
+            return None;
+        }
+        let key = match item.kind {
+            ResourceKind::Pod => SeriesKey::Pod {
+                namespace: item.namespace.clone()?,
+                name: item.name.clone(),
+            },
+            ResourceKind::Node => SeriesKey::Node(item.name.clone()),
+            _ => return None,
+        };
+
+        let samples = self.metrics_history.samples(&key, self.history_window);
+        if samples.is_empty() && self.metrics.is_none() {
+            return None;
+        }
+        Some(UsageHistory {
+            samples,
+            window: self.history_window,
+            reference: usage_reference(item),
+        })
+    }
+
+    /// Starts a watch for `key` unless one is already streaming into the cache.
+    /// Watches stay alive when switching kinds so returning to a list is
+    /// instant, up to `MAX_WATCHES`.
+    fn ensure_watch(&mut self, key: CacheKey, cx: &mut Context<Self>) {
+        if self.watches.contains_key(&key) {
+            return;
+        }
+
+        info!("Starting watch: {:?} in {:?}", key.kind, key.namespace);
+
+        let (tx, mut rx) = mpsc::unbounded();
         let client = self.kube_client.clone();
+        let kind = key.kind.clone();
+        let namespace = key.namespace.clone();
+
+        // Watch runs on tokio (Send); events are applied on the UI thread below
+        let handle = tokio::spawn(async move {
+            if let Err(e) = client.watch_resources(kind, namespace.as_deref(), tx).await {
+                error!("Failed to start watch: {}", e);
+            }
+        });
+        self.watches.insert(key.clone(), handle.abort_handle());
+
         let mut cx_async = cx.to_async();
-        
         cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
-            let _ = client.connect_with_context(&ctx_name).await;
-            let _ = this.update(&mut cx_async, |app, cx| {
-                app.connection_status = ConnectionStatus::Connected;
-                app.refresh(cx);
-                cx.notify();
+            while let Some(event) = rx.next().await {
+                let applied = this.update(&mut cx_async, |app, cx| {
+                    // Stopped; events still queued would refill a dropped list
+                    if !app.watches.contains_key(&key) {
+                        return;
+                    }
+                    if let ResourceEvent::Error(msg) = &event {
+                        error!("Watch {:?} error: {}", key.kind, msg);
+                    }
+                    if app.resource_cache.apply(&key, event) {
+                        app.select_pending();
+                        app.sync_selected_resource();
+                        cx.notify();
+                    }
+                });
+                if applied.is_err() {
+                    return;
+                }
+            }
+
+            // Stream ended on its own (e.g. not connected); allow a later retry
+            let _ = this.update(&mut cx_async, |app, _| {
+                if app.watches.get(&key).is_some_and(|h| h.is_finished()) {
+                    app.watches.remove(&key);
+                }
             });
         }).detach();
     }
 
-    pub fn select_resource_kind(&mut self, kind: ResourceKind, cx: &mut Context<Self>) {
-        self.active_view = ActiveView::Resources(kind);
-        self.selected_resource = None;
-        self.show_detail = false;
-        self.refresh(cx);
-    }
+    /// Marks `keys` as just viewed and stops the least recently viewed
+    /// watches past `MAX_WATCHES`, dropping their lists since nothing keeps
+    /// them current any more.
+    fn evict_watches(&mut self, keys: &[CacheKey]) {
+        let watches = &self.watches;
+        self.watch_order
+            .retain(|key| !keys.contains(key) && watches.contains_key(key));
+        self.watch_order.extend(keys.iter().cloned());
 
-    pub fn select_resource(&mut self, resource: ResourceItem) {
-        self.selected_resource = Some(resource);
-        self.show_detail = true;
+        while self.watch_order.len() > MAX_WATCHES.max(keys.len()) {
+            let Some(key) = self.watch_order.pop_front() else {
+                break;
+            };
+            if let Some(handle) = self.watches.remove(&key) {
+                info!("Stopping idle watch: {:?} in {:?}", key.kind, key.namespace);
+                handle.abort();
+            }
+            self.resource_cache.remove(&key);
+        }
     }
 
-    pub fn close_detail(&mut self) {
-        self.show_detail = false;
-        self.selected_resource = None;
-    }
+    fn start_event_watch(&mut self, cx: &mut Context<Self>) {
+        self.stop_event_watch();
 
-    pub fn refresh(&mut self, cx: &mut Context<Self>) {
-        let kind = match &self.active_view {
-            ActiveView::Resources(k) => k.clone(),
-            _ => return,
-        };
-        
-        info!("Refreshing resources: {:?}", kind);
-        
-        let namespace = self.selected_namespace.clone();
+        let (tx, mut rx) = mpsc::unbounded();
         let client = self.kube_client.clone();
+        let handle = tokio::spawn(async move {
+            if let Err(e) = client.watch_events(tx).await {
+                error!("Failed to start event watch: {}", e);
+            }
+        });
+        self.event_watch = Some(handle.abort_handle());
+
         let mut cx_async = cx.to_async();
-        
         cx.spawn(move |this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
-            match client.list_resources(kind, namespace.as_deref()).await {
-                Ok(items) => {
-                    this.update(&mut cx_async, |app, cx| {
-                         app.resources = items;
-                         cx.notify();
-                    }).ok();
-                }
-                Err(e) => {
-                    error!("Failed to list resources: {}", e);
+            while let Some(event) = rx.next().await {
+                let applied = this.update(&mut cx_async, |app, cx| {
+                    if app.events.apply(event) {
+                        cx.notify();
+                    }
+                });
+                if applied.is_err() {
+                    return;
                 }
             }
         }).detach();
     }
 
+    fn stop_event_watch(&mut self) {
+        if let Some(handle) = self.event_watch.take() {
+            handle.abort();
+        }
+    }
+
+    fn handle_event_action(&mut self, action: EventAction, cx: &mut Context<Self>) {
+        match action {
+            EventAction::SetFilter(filter) => self.event_filter = filter,
+            EventAction::Open(object) => {
+                if let Some(target) = self.event_target(&object) {
+                    self.navigate_to(target, cx);
+                }
+            }
+        }
+        cx.notify();
+    }
+
+    /// The listed kind an event's object belongs to, if the app has a list for it.
+    fn event_target(&self, object: &EventObject) -> Option<ResourceRef> {
+        let group = object
+            .api_version
+            .rsplit_once('/')
+            .map(|(group, _)| group)
+            .unwrap_or_default();
+        let kind = ResourceKind::all()
+            .into_iter()
+            .chain(self.custom_kinds.iter().cloned())
+            .find(|kind| {
+                kind.kind_name() == object.kind
+                    && match kind {
+                        ResourceKind::Custom(custom) => custom.group == group,
+                        _ => true,
+                    }
+            })?;
+        Some(ResourceRef {
+            namespace: object.namespace.clone().filter(|_| kind.is_namespaced()),
+            name: object.name.clone(),
+            kind,
+        })
+    }
+
+    fn stop_watches(&mut self) {
+        for (key, handle) in self.watches.drain() {
+            info!("Stopping watch: {:?} in {:?}", key.kind, key.namespace);
+            handle.abort();
+        }
+        self.watch_order.clear();
+    }
+
+    fn glass_style(&self) -> GlassStyle {
+        let appearance = self.settings_manager.get_settings().appearance;
+        GlassStyle::new(
+            appearance.glassomorphism_enabled,
+            appearance.blur_intensity,
+            appearance.glass_opacity,
+        )
+    }
+
     fn render_title_bar(&self, cx: &mut Context<Self>, colors: &ThemeColors) -> impl IntoElement {
         let is_macos = cfg!(target_os = "macos");
 
@@ -440,11 +2883,7 @@ impl Render for KubeSparkApp {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         // Get current settings for styling
         let settings = self.settings_manager.get_settings();
-        let glass_style = GlassStyle::new(
-            settings.appearance.glassomorphism_enabled,
-            settings.appearance.blur_intensity,
-            settings.appearance.glass_opacity,
-        );
+        let glass_style = self.glass_style();
 
         // If settings are shown, render settings panel instead
         if self.show_settings {
@@ -454,19 +2893,41 @@ impl Render for KubeSparkApp {
                 .size_full()
                 .bg(settings.appearance.theme.colors().bg_app)
                 .text_color(settings.appearance.theme.colors().text_primary)
+                .track_focus(&self.focus_handle)
+                .on_key_down(cx.listener(Self::on_key_down))
                 .child(self.render_title_bar(cx, &settings.appearance.theme.colors()))
                 .child(
                     SettingsPanel::render_panel(
                         self.settings_manager.clone(),
                         settings,
-                        SettingsTab::Appearance,
+                        self.settings_tab,
+                        {
+                            let weak = cx.entity().downgrade();
+                            move |tab, _win, cx| {
+                                let _ = weak.update(cx, |this, cx| {
+                                    this.select_settings_tab(tab, cx);
+                                });
+                            }
+                        },
+                        self.settings_edit.as_ref(),
+                        {
+                            let weak = cx.entity().downgrade();
+                            move |field, win, cx| {
+                                let _ = weak.update(cx, |this, cx| {
+                                    this.start_settings_edit(field, win, cx);
+                                });
+                            }
+                        },
                         cx.listener(|this, _, _, _| {
                             this.toggle_settings();
                         }),
                         {
                             let weak = cx.entity().downgrade();
                             move |_, cx| {
-                                let _ = weak.update(cx, |_, cx| cx.notify());
+                                let _ = weak.update(cx, |this, cx| {
+                                    this.apply_kubernetes_settings(cx);
+                                    cx.notify();
+                                });
                             }
                         },
                     )
@@ -491,6 +2952,7 @@ impl Render for KubeSparkApp {
         let colors = settings.appearance.theme.colors();
 
         div()
+            .relative()
             .flex()
             .flex_col()
             .size_full()
@@ -499,6 +2961,8 @@ impl Render for KubeSparkApp {
             .font_family(
                 "'SF Pro Display', 'Inter', 'Segoe UI', system-ui, -apple-system, sans-serif",
             )
+            .track_focus(&self.focus_handle)
+            .on_key_down(cx.listener(Self::on_key_down))
             .child(self.render_title_bar(cx, &colors))
             .child(
                 div()
@@ -509,6 +2973,8 @@ impl Render for KubeSparkApp {
                         let weak = cx.entity().downgrade();
                         let weak_ctx_menu = weak.clone();
                         let weak_switch = weak.clone();
+                        let weak_ns_menu = weak.clone();
+                        let weak_ns_select = weak.clone();
                         
                         Sidebar::new(
                             glass_style,
@@ -518,14 +2984,21 @@ impl Render for KubeSparkApp {
                             self.current_context.clone(),
                             self.available_contexts.clone(),
                             self.show_context_menu,
+                            self.custom_kinds.clone(),
+                            self.namespace_picker(),
                             move |view, _win, cx| {
                                 let _ = weak.update(cx, |this, cx| {
                                     if let ActiveView::Resources(kind) = view {
                                          this.select_resource_kind(kind, cx);
                                     } else {
+                                         this.stop_log_stream();
+                                         this.stop_terminal_session();
                                          this.active_view = view.clone();
                                          this.selected_resource = None;
                                          this.show_detail = false;
+                                         this.refresh_task = None;
+                                         this.refresh(cx);
+                                         this.restart_poller(cx);
                                          cx.notify();
                                     }
                                 });
@@ -543,29 +3016,72 @@ impl Render for KubeSparkApp {
                                     cx.notify();
                                 });
                             },
+                            move |_win, cx| {
+                                let _ = weak_ns_menu.update(cx, |this, cx| {
+                                    this.toggle_namespace_menu(cx);
+                                    cx.notify();
+                                });
+                            },
+                            move |namespace, additive, _win, cx| {
+                                let _ = weak_ns_select.update(cx, |this, cx| {
+                                    this.select_namespace(namespace, additive, cx);
+                                });
+                            },
                         )
                     }))
                     .child(div().flex_1().h_full().child(match active_view {
                         ActiveView::Dashboard => {
-                            DashboardView::new(glass_style, &colors).render().into_any_element()
+                            let (normal, warning) = self.events.counts(&self.selected_namespaces);
+                            let feed = EventFeed {
+                                groups: self.events.feed(self.event_filter, &self.selected_namespaces),
+                                normal,
+                                warning,
+                                filter: self.event_filter,
+                                namespaces: self.selected_namespaces.clone(),
+                                error: self.events.error.clone(),
+                                synced: self.events.synced,
+                            };
+                            let stats = self.cluster_stats();
+                            let usage = self.dashboard_usage(stats.as_ref());
+                            let weak = cx.entity().downgrade();
+                            let weak_window = weak.clone();
+                            DashboardView::new(glass_style, stats, usage, feed, &colors)
+                                .render(
+                                    move |window, _win, cx| {
+                                        let _ = weak_window.update(cx, |this, cx| {
+                                            this.set_history_window(window, cx);
+                                        });
+                                    },
+                                    move |action, _win, cx| {
+                                        let _ = weak.update(cx, |this, cx| {
+                                            this.handle_event_action(action, cx);
+                                        });
+                                    },
+                                )
+                                .into_any_element()
                         }
                         ActiveView::Resources(kind) => {
-                            let filtered = self
-                                .resources
+                            let filtered = self.visible_items(&kind);
+
+                            // Oldest of the merged lists, so the header never overstates freshness
+                            let last_refreshed = self
+                                .cache_keys(&kind)
                                 .iter()
-                                .filter(|r| r.kind == kind)
-                                .cloned()
-                                .collect();
+                                .map(|key| self.resource_cache.updated_at(key))
+                                .min()
+                                .flatten();
 
                             let weak = cx.entity().downgrade();
                             ResourceListView::new(
                                 Some(kind),
                                 filtered,
+                                last_refreshed,
+                                self.notice.clone(),
+                                self.metrics.as_ref(),
                                 glass_style,
                                 move |resource, _win, cx| {
                                     let _ = weak.update(cx, |this, cx| {
-                                        this.selected_resource = Some(resource);
-                                        this.show_detail = true;
+                                        this.select_resource(resource);
                                         cx.notify();
                                     });
                                 },
@@ -575,71 +3091,122 @@ impl Render for KubeSparkApp {
                         }
                         ActiveView::Logs(log_view) => {
                             let weak = cx.entity().downgrade();
+                            let weak_pause = weak.clone();
+                            let weak_options = weak.clone();
+                            let weak_action = weak.clone();
                             log_view
-                                .render(move |_event, _win, cx| {
+                                .render(
+                                    &self.log_scroll,
+                                    move |_event, _win, cx| {
+                                        let _ = weak.update(cx, |this, cx| {
+                                            this.close_logs(cx);
+                                        });
+                                    },
+                                    move |_event, _win, cx| {
+                                        let _ = weak_pause.update(cx, |this, cx| {
+                                            this.toggle_log_pause(cx);
+                                        });
+                                    },
+                                    move |options, _win, cx| {
+                                        let _ = weak_options.update(cx, |this, cx| {
+                                            this.set_log_options(options, cx);
+                                        });
+                                    },
+                                    move |action, win, cx| {
+                                        let _ = weak_action.update(cx, |this, cx| {
+                                            this.handle_log_action(action, win, cx);
+                                        });
+                                    },
+                                    &colors,
+                                )
+                                .into_any_element()
+                        }
+                        ActiveView::PortForwards => {
+                            let (entries, idle_favorites) = self.port_forward_entries();
+                            let weak = cx.entity().downgrade();
+                            PortForwardsView::new(
+                                entries,
+                                idle_favorites,
+                                glass_style,
+                                move |action, _win, cx| {
                                     let _ = weak.update(cx, |this, cx| {
-                                        this.active_view = ActiveView::Resources(ResourceKind::Pod);
-                                        cx.notify();
+                                        this.handle_port_forward_action(action, cx);
                                     });
-                                }, &colors)
+                                },
+                                &colors,
+                            )
+                            .into_any_element()
+                        }
+                        ActiveView::Terminal(terminal_view) => {
+                            let weak = cx.entity().downgrade();
+                            let weak_action = weak.clone();
+                            let weak_resize = weak.clone();
+                            let weak_scroll = weak.clone();
+                            terminal_view
+                                .render(
+                                    move |_event, _win, cx| {
+                                        let _ = weak.update(cx, |this, cx| {
+                                            this.close_terminal(cx);
+                                        });
+                                    },
+                                    move |action, win, cx| {
+                                        let _ = weak_action.update(cx, |this, cx| {
+                                            this.handle_terminal_action(action, win, cx);
+                                        });
+                                    },
+                                    move |rows, cols, _win, cx| {
+                                        let _ = weak_resize.update(cx, |this, cx| {
+                                            this.resize_terminal(rows, cols, cx);
+                                        });
+                                    },
+                                    move |lines, _win, cx| {
+                                        let _ = weak_scroll.update(cx, |this, cx| {
+                                            this.scroll_terminal(lines, cx);
+                                        });
+                                    },
+                                    &colors,
+                                )
                                 .into_any_element()
                         }
                     }))
                     .child(if show_detail {
                         let weak = cx.entity().downgrade();
-                        let selected = selected_resource.clone();
-                        let namespace = selected
+                        let replica_target = self
+                            .replica_target
                             .as_ref()
-                            .and_then(|r| r.namespace.clone())
-                            .unwrap_or_default();
-
-                        // Create async context for spawning tasks
-                        let async_cx = cx.to_async();
+                            .filter(|(target, _)| {
+                                selected_resource.as_ref().is_some_and(|r| target.matches(r))
+                            })
+                            .map(|(_, replicas)| *replicas);
 
                         div().w(detail_width).h_full().child(DetailView::new(
-                            selected_resource.clone(),
-                            glass_style,
-                            move |pod_name, _win, cx| {
-                                // Use captured async_cx for async work
-                                let ns = namespace.clone();
-                                let pod = pod_name.clone();
-                                let weak_for_spawn = weak.clone();
-
-                                let _ = weak.clone().update(cx, |this, cx| {
-                                    // Update view
-                                    let log_view =
-                                        LogView::new(pod.clone(), ns.clone(), glass_style);
-                                    this.active_view = ActiveView::Logs(log_view);
-                                    cx.notify();
-
-                                    let client = this.kube_client.clone();
-                                    let pod = pod.clone();
-                                    let ns = ns.clone();
-
-                                    let mut fetch_cx = async_cx.clone();
-                                    let (tx, rx) = oneshot::channel();
-                                    
-                                    // Background fetch (Send-safe)
-                                    tokio::spawn(async move {
-                                        let result = client.get_pod_logs(&ns, &pod, None).await;
-                                        let logs = match result {
-                                            Ok(l) => l.lines().map(|s| s.to_string()).collect(),
-                                            Err(e) => vec![format!("Error: {}", e)],
-                                        };
-                                        let _ = tx.send(logs);
-                                    });
-
-                                    // Local update (UI thread)
-                                    cx.spawn(move |_this: WeakEntity<KubeSparkApp>, _cx: &mut AsyncApp| async move {
-                                        if let Ok(logs) = rx.await {
-                                            let _ = weak_for_spawn.update(&mut fetch_cx, |this, cx| {
-                                                if let ActiveView::Logs(view) = &mut this.active_view {
-                                                     view.set_logs(logs);
-                                                     cx.notify();
-                                                }
-                                            });
-                                        }
-                                    }).detach();
+                            DetailProps {
+                                selected: selected_resource.clone(),
+                                glass_style,
+                                can_go_back: !self.nav_stack.is_empty(),
+                                tab: self.detail_tab,
+                                replica_target,
+                                events: selected_resource
+                                    .as_ref()
+                                    .map(|r| self.events.for_object(r))
+                                    .unwrap_or_default(),
+                                usage: selected_resource
+                                    .as_ref()
+                                    .and_then(|r| self.detail_usage(r)),
+                                yaml_editor: self.yaml_editor().cloned(),
+                                editor_settings: settings.editor.clone(),
+                                history: self.rollout_history().cloned(),
+                                metrics: selected_resource
+                                    .as_ref()
+                                    .filter(|r| {
+                                        settings.kubernetes.prometheus_enabled
+                                            && has_pod_metrics(&r.kind)
+                                    })
+                                    .map(|_| self.prometheus_metrics().cloned().unwrap_or_default()),
+                            },
+                            move |action, win, cx| {
+                                let _ = weak.update(cx, |this, cx| {
+                                    this.handle_detail_action(action, win, cx);
                                 });
                             },
                             &colors,
@@ -648,6 +3215,28 @@ impl Render for KubeSparkApp {
                         div()
                     }),
             )
+            .when_some(self.drain_dialog.as_ref(), |this, dialog| {
+                let weak = cx.entity().downgrade();
+                this.child(dialog.render(
+                    move |action, _win, cx| {
+                        let _ = weak.update(cx, |this, cx| {
+                            this.handle_drain_action(action, cx);
+                        });
+                    },
+                    &colors,
+                ))
+            })
+            .when_some(self.delete_dialog.as_ref(), |this, dialog| {
+                let weak = cx.entity().downgrade();
+                this.child(dialog.render(
+                    move |action, _win, cx| {
+                        let _ = weak.update(cx, |this, cx| {
+                            this.handle_delete_action(action, cx);
+                        });
+                    },
+                    &colors,
+                ))
+            })
             .into_any_element()
     }
 }
@@ -0,0 +1,197 @@
+use k8s_openapi::api::{
+    apps::v1::{DaemonSet, Deployment, ReplicaSet, StatefulSet},
+    batch::v1::{CronJob, Job},
+    core::v1::{
+        ConfigMap, Namespace, Node, PersistentVolume, PersistentVolumeClaim, Pod, Secret, Service,
+    },
+    networking::v1::Ingress,
+};
+use kube::api::ApiResource;
+use regex::Regex;
+use std::fmt;
+use std::sync::OnceLock;
+
+use super::ResourceKind;
+
+/// Field manager recorded for server-side applies from the app.
+pub const FIELD_MANAGER: &str = "kubespark";
+
+/// How an edited object is written back to the cluster.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SaveMode {
+    /// Server-side apply as the `kubespark` field manager
+    #[default]
+    Apply,
+    /// PUT of the whole object
+    Replace,
+}
+
+impl SaveMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Apply => "Apply",
+            Self::Replace => "Replace",
+        }
+    }
+}
+
+/// One `path: message` entry of a validation failure.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldError {
+    /// API field path, e.g. `spec.template.spec.containers[0].image`
+    pub path: String,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SaveError {
+    /// The object changed since it was loaded (stale `resourceVersion`), or
+    /// an apply touched fields owned by another field manager
+    Conflict(String),
+    /// Rejected by validation; `fields` is empty when the message names no paths
+    Invalid {
+        message: String,
+        fields: Vec<FieldError>,
+    },
+    Failed(String),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Conflict(message) | Self::Failed(message) => f.write_str(message),
+            Self::Invalid { message, .. } => f.write_str(message),
+        }
+    }
+}
+
+impl From<kube::Error> for SaveError {
+    fn from(error: kube::Error) -> Self {
+        match error {
+            kube::Error::Api(response) if response.code == 409 => Self::Conflict(response.message),
+            kube::Error::Api(response) if response.code == 422 || response.reason == "Invalid" => {
+                Self::Invalid {
+                    fields: field_errors(&response.message),
+                    message: response.message,
+                }
+            }
+            other => Self::Failed(other.to_string()),
+        }
+    }
+}
+
+/// Type information for the generic (`DynamicObject`) API of any kind.
+pub(super) fn api_resource(kind: &ResourceKind) -> ApiResource {
+    match kind {
+        ResourceKind::Namespace => ApiResource::erase::<Namespace>(&()),
+        ResourceKind::Pod => ApiResource::erase::<Pod>(&()),
+        ResourceKind::Deployment => ApiResource::erase::<Deployment>(&()),
+        ResourceKind::StatefulSet => ApiResource::erase::<StatefulSet>(&()),
+        ResourceKind::DaemonSet => ApiResource::erase::<DaemonSet>(&()),
+        ResourceKind::ReplicaSet => ApiResource::erase::<ReplicaSet>(&()),
+        ResourceKind::Service => ApiResource::erase::<Service>(&()),
+        ResourceKind::Job => ApiResource::erase::<Job>(&()),
+        ResourceKind::CronJob => ApiResource::erase::<CronJob>(&()),
+        ResourceKind::ConfigMap => ApiResource::erase::<ConfigMap>(&()),
+        ResourceKind::Secret => ApiResource::erase::<Secret>(&()),
+        ResourceKind::Ingress => ApiResource::erase::<Ingress>(&()),
+        ResourceKind::PersistentVolume => ApiResource::erase::<PersistentVolume>(&()),
+        ResourceKind::PersistentVolumeClaim => ApiResource::erase::<PersistentVolumeClaim>(&()),
+        ResourceKind::Node => ApiResource::erase::<Node>(&()),
+        ResourceKind::Custom(custom) => ApiResource {
+            group: custom.group.clone(),
+            version: custom.version.clone(),
+            api_version: if custom.group.is_empty() {
+                custom.version.clone()
+            } else {
+                format!("{}/{}", custom.group, custom.version)
+            },
+            kind: custom.kind.clone(),
+            plural: custom.plural.clone(),
+        },
+    }
+}
+
+/// Splits a message like `Deployment.apps "web" is invalid: [spec.replicas:
+/// Invalid value: -1: must be greater than or equal to 0, metadata.name: ...]`
+/// into its per-field entries.
+pub fn field_errors(message: &str) -> Vec<FieldError> {
+    static FIELD_PATH: OnceLock<Regex> = OnceLock::new();
+    // A dotted or indexed path at the start of an entry, followed by ": "
+    let re = FIELD_PATH.get_or_init(|| {
+        Regex::new(r"(?:^|, )([A-Za-z][\w\-/]*(?:\.[\w\-/]+|\[[^\]]*\])+): ").unwrap()
+    });
+
+    let Some((_, details)) = message.split_once("is invalid: ") else {
+        return Vec::new();
+    };
+    let details = details.trim();
+    let details = details
+        .strip_prefix('[')
+        .and_then(|d| d.strip_suffix(']'))
+        .unwrap_or(details);
+
+    let starts: Vec<(usize, usize, &str)> = re
+        .captures_iter(details)
+        .filter_map(|caps| {
+            let whole = caps.get(0)?;
+            Some((whole.start(), whole.end(), caps.get(1)?.as_str()))
+        })
+        .collect();
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(ix, (_, end, path))| {
+            let message_end = starts
+                .get(ix + 1)
+                .map(|(next, _, _)| *next)
+                .unwrap_or(details.len());
+            FieldError {
+                path: path.to_string(),
+                message: details[*end..message_end].trim().to_string(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_errors_splits_bracketed_list() {
+        let message = r#"Deployment.apps "web" is invalid: [spec.replicas: Invalid value: -1: must be greater than or equal to 0, spec.template.spec.containers[0].image: Required value]"#;
+        assert_eq!(
+            field_errors(message),
+            vec![
+                FieldError {
+                    path: "spec.replicas".to_string(),
+                    message: "Invalid value: -1: must be greater than or equal to 0".to_string(),
+                },
+                FieldError {
+                    path: "spec.template.spec.containers[0].image".to_string(),
+                    message: "Required value".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn field_errors_reads_single_entry_without_brackets() {
+        let message = r#"Service "api" is invalid: spec.ports[0].port: Invalid value: 0: must be between 1 and 65535, inclusive"#;
+        assert_eq!(
+            field_errors(message),
+            vec![FieldError {
+                path: "spec.ports[0].port".to_string(),
+                message: "Invalid value: 0: must be between 1 and 65535, inclusive".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn field_errors_empty_without_paths() {
+        assert!(field_errors("the server could not find the requested resource").is_empty());
+        assert!(field_errors(r#"ConfigMap "cfg" is invalid: name is too long"#).is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+use chrono::{DateTime, Local};
+use std::collections::{BTreeMap, HashMap};
+
+use super::watch::ResourceEvent;
+use super::{ResourceItem, ResourceKind};
+
+/// Identifies one cached list: a resource kind scoped to a namespace
+/// (`None` = all namespaces / cluster-scoped).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    pub kind: ResourceKind,
+    pub namespace: Option<String>,
+}
+
+impl CacheKey {
+    pub fn new(kind: ResourceKind, namespace: Option<String>) -> Self {
+        Self { kind, namespace }
+    }
+}
+
+/// Items are keyed by (namespace, name) so lists render in a stable order.
+type ItemMap = BTreeMap<(Option<String>, String), ResourceItem>;
+
+/// Per-kind, per-namespace store of resources, fed either by watch events
+/// or by wholesale replacement from a polling refresh.
+#[derive(Default)]
+pub struct ResourceCache {
+    entries: HashMap<CacheKey, ItemMap>,
+    updated_at: HashMap<CacheKey, DateTime<Local>>,
+}
+
+impl ResourceCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn items(&self, key: &CacheKey) -> Vec<ResourceItem> {
+        self.entries
+            .get(key)
+            .map(|items| items.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// When the list for `key` last received data from the cluster.
+    pub fn updated_at(&self, key: &CacheKey) -> Option<DateTime<Local>> {
+        self.updated_at.get(key).copied()
+    }
+
+    pub fn replace(&mut self, key: CacheKey, items: Vec<ResourceItem>) {
+        self.updated_at.insert(key.clone(), Local::now());
+        self.entries.insert(
+            key,
+            items
+                .into_iter()
+                .map(|item| (item_key(&item), item))
+                .collect(),
+        );
+    }
+
+    /// Applies a watch event. Returns true if the cached list changed.
+    pub fn apply(&mut self, key: &CacheKey, event: ResourceEvent) -> bool {
+        match event {
+            ResourceEvent::Restarted(items) => {
+                self.replace(key.clone(), items);
+                true
+            }
+            ResourceEvent::Applied(item) => {
+                self.updated_at.insert(key.clone(), Local::now());
+                self.entries
+                    .entry(key.clone())
+                    .or_default()
+                    .insert(item_key(&item), item);
+                true
+            }
+            ResourceEvent::Deleted(item) => {
+                self.updated_at.insert(key.clone(), Local::now());
+                self.entries
+                    .get_mut(key)
+                    .map(|items| items.remove(&item_key(&item)).is_some())
+                    .unwrap_or(false)
+            }
+            ResourceEvent::Error(_) => false,
+        }
+    }
+
+    /// Drops the list for `key`, e.g. once nothing keeps it up to date.
+    pub fn remove(&mut self, key: &CacheKey) {
+        self.entries.remove(key);
+        self.updated_at.remove(key);
+    }
+
+    /// Removes an object from every list holding it, ahead of its watch
+    /// event, and returns what was removed so it can be put back.
+    pub fn remove_item(&mut self, item: &ResourceItem) -> Vec<(CacheKey, ResourceItem)> {
+        let item_key = item_key(item);
+        self.entries
+            .iter_mut()
+            .filter(|(key, _)| key.kind == item.kind)
+            .filter_map(|(key, items)| {
+                items
+                    .remove(&item_key)
+                    .map(|removed| (key.clone(), removed))
+            })
+            .collect()
+    }
+
+    /// Undoes `remove_item`.
+    pub fn restore(&mut self, removed: Vec<(CacheKey, ResourceItem)>) {
+        for (key, item) in removed {
+            self.entries
+                .entry(key)
+                .or_default()
+                .insert(item_key(&item), item);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.updated_at.clear();
+    }
+}
+
+fn item_key(item: &ResourceItem) -> (Option<String>, String) {
+    (item.namespace.clone(), item.name.clone())
+}
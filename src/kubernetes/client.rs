@@ -2,32 +2,86 @@
 
 use anyhow::{anyhow, Context, Result};
 
-use k8s_openapi::api::{
-    apps::v1::{DaemonSet, Deployment, ReplicaSet, StatefulSet},
-    batch::v1::{CronJob, Job},
-    core::v1::{ConfigMap, Namespace, Node, Pod, Secret, Service},
-    networking::v1::Ingress,
-};
+use futures::channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use futures::{AsyncBufReadExt, SinkExt, StreamExt, TryStreamExt};
+use k8s_openapi::api::apps::v1::{ControllerRevision, ReplicaSet};
+use k8s_openapi::api::core::v1::{Namespace, Node, Pod, Service};
+use k8s_openapi::api::events::v1::Event;
+use k8s_openapi::apiextensions_apiserver::pkg::apis::apiextensions::v1::CustomResourceDefinition;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{OwnerReference, Status};
+use k8s_openapi::NamespaceResourceScope;
 use kube::{
-    api::{ListParams, LogParams},
+    api::{
+        AttachParams, DeleteParams, DynamicObject, EvictParams, ListParams, LogParams, Patch,
+        PatchParams, PostParams, PropagationPolicy, TerminalSize,
+    },
     config::{KubeConfigOptions, Kubeconfig},
-    Api, Client, Config,
+    discovery::{verbs, Discovery, Scope},
+    runtime::{watcher, WatchStreamExt},
+    Api, Client, Config, Resource, ResourceExt,
 };
-use log::{error, info};
+use log::{error, info, warn};
+use serde::de::DeserializeOwned;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::sync::RwLock;
+use tokio::task::{AbortHandle, JoinSet};
+
+use super::apply::{api_resource, SaveError, SaveMode, FIELD_MANAGER};
+use super::convert::*;
+use super::events::{event_item, ClusterEvent};
+use super::metrics::{
+    node_metrics, node_metrics_resource, pod_metrics, pod_metrics_resource, MetricsSnapshot,
+};
+use super::port_forward::{self, PortForwardStats};
+use super::prometheus::{parse_range_response, PrometheusEndpoint, QuerySeries};
+use super::watch::{run_watcher, ResourceEvent};
+use super::{
+    CustomResourceKind, DeleteOptions, DeletePropagation, DrainEvent, DrainOptions, DrainPod,
+    DrainPodState, ExecEvent, LogLine, LogOptions, PortForwardSpec, PrinterColumn, ResourceItem,
+    ResourceKind, ResourceRef, Revision, TerminalInput,
+};
+
+const REVISION_ANNOTATION: &str = "deployment.kubernetes.io/revision";
+const CHANGE_CAUSE_ANNOTATION: &str = "kubernetes.io/change-cause";
+
+/// Set on the API server's copies of static pods, which the kubelet owns.
+const MIRROR_POD_ANNOTATION: &str = "kubernetes.io/config.mirror";
+
+/// Wait between attempts at an eviction a disruption budget refused.
+const EVICTION_RETRY: Duration = Duration::from_secs(5);
+
+/// Wait between checks for an evicted pod being gone.
+const TERMINATION_POLL: Duration = Duration::from_secs(2);
+
+/// Points per line a Prometheus range query asks for.
+const PROMETHEUS_POINTS: i64 = 120;
 
-use super::{ResourceItem, ResourceKind};
+/// Finer steps than a typical scrape interval only repeat samples.
+const PROMETHEUS_MIN_STEP: i64 = 15;
+
+/// How long a Prometheus query may take before the Metrics tab gives up on it.
+const PROMETHEUS_TIMEOUT: Duration = Duration::from_secs(30);
 
 #[derive(Clone)]
 pub struct KubeClient {
     client: Arc<RwLock<Option<Client>>>,
+    /// For Prometheus endpoints given as a URL; shares connections between queries
+    http: reqwest::Client,
 }
 
 impl KubeClient {
     pub fn new() -> Self {
         Self {
             client: Arc::new(RwLock::new(None)),
+            // Only fails when the TLS backend can't initialize, as `reqwest::Client::new` would
+            http: reqwest::Client::builder()
+                .timeout(PROMETHEUS_TIMEOUT)
+                .build()
+                .expect("Failed to initialize HTTP client"),
         }
     }
 
@@ -103,658 +157,1278 @@ impl KubeClient {
         &self,
         kind: ResourceKind,
         namespace: Option<&str>,
+    ) -> Result<Vec<ResourceItem>> {
+        self.list_matching(kind, namespace, &ListParams::default())
+            .await
+    }
+
+    /// Fetches a single object by name; `None` if it doesn't exist.
+    pub async fn get_resource(
+        &self,
+        kind: ResourceKind,
+        namespace: Option<&str>,
+        name: &str,
+    ) -> Result<Option<ResourceItem>> {
+        let params = ListParams::default().fields(&format!("metadata.name={}", name));
+        let items = self.list_matching(kind, namespace, &params).await?;
+        Ok(items.into_iter().next())
+    }
+
+    async fn list_matching(
+        &self,
+        kind: ResourceKind,
+        namespace: Option<&str>,
+        params: &ListParams,
     ) -> Result<Vec<ResourceItem>> {
         let client = self.get_client().await?;
 
         match kind {
-            ResourceKind::Namespace => self.list_namespaces_as_items().await,
-            ResourceKind::Pod => self.list_pods(&client, namespace).await,
-            ResourceKind::Deployment => self.list_deployments(&client, namespace).await,
-            ResourceKind::StatefulSet => self.list_statefulsets(&client, namespace).await,
-            ResourceKind::DaemonSet => self.list_daemonsets(&client, namespace).await,
-            ResourceKind::ReplicaSet => self.list_replicasets(&client, namespace).await,
-            ResourceKind::Service => self.list_services(&client, namespace).await,
-            ResourceKind::Job => self.list_jobs(&client, namespace).await,
-            ResourceKind::CronJob => self.list_cronjobs(&client, namespace).await,
-            ResourceKind::ConfigMap => self.list_configmaps(&client, namespace).await,
-            ResourceKind::Secret => self.list_secrets(&client, namespace).await,
-            ResourceKind::Ingress => self.list_ingresses(&client, namespace).await,
-            ResourceKind::Node => self.list_nodes(&client).await,
-            _ => Ok(vec![]),
+            ResourceKind::Namespace => list_items(Api::all(client), params, namespace_item).await,
+            ResourceKind::Pod => list_items(scoped_api(&client, namespace), params, pod_item).await,
+            ResourceKind::Deployment => {
+                list_items(scoped_api(&client, namespace), params, deployment_item).await
+            }
+            ResourceKind::StatefulSet => {
+                list_items(scoped_api(&client, namespace), params, statefulset_item).await
+            }
+            ResourceKind::DaemonSet => {
+                list_items(scoped_api(&client, namespace), params, daemonset_item).await
+            }
+            ResourceKind::ReplicaSet => {
+                list_items(scoped_api(&client, namespace), params, replicaset_item).await
+            }
+            ResourceKind::Service => {
+                list_items(scoped_api(&client, namespace), params, service_item).await
+            }
+            ResourceKind::Job => list_items(scoped_api(&client, namespace), params, job_item).await,
+            ResourceKind::CronJob => {
+                list_items(scoped_api(&client, namespace), params, cronjob_item).await
+            }
+            ResourceKind::ConfigMap => {
+                list_items(scoped_api(&client, namespace), params, configmap_item).await
+            }
+            ResourceKind::Secret => {
+                list_items(scoped_api(&client, namespace), params, secret_item).await
+            }
+            ResourceKind::Ingress => {
+                list_items(scoped_api(&client, namespace), params, ingress_item).await
+            }
+            ResourceKind::PersistentVolume => {
+                list_items(Api::all(client), params, persistent_volume_item).await
+            }
+            ResourceKind::PersistentVolumeClaim => {
+                list_items(
+                    scoped_api(&client, namespace),
+                    params,
+                    persistent_volume_claim_item,
+                )
+                .await
+            }
+            ResourceKind::Node => list_items(Api::all(client), params, node_item).await,
+            ResourceKind::Custom(custom) => {
+                let api = dynamic_api(&client, &custom, namespace);
+                list_items(api, params, |obj| dynamic_item(&custom, obj)).await
+            }
         }
     }
 
-    async fn list_namespaces_as_items(&self) -> Result<Vec<ResourceItem>> {
+    /// Runs API discovery and returns every listable resource outside the
+    /// built-in Kubernetes groups (CRDs and third-party aggregated APIs).
+    pub async fn discover_custom_resources(&self) -> Result<Vec<CustomResourceKind>> {
         let client = self.get_client().await?;
-        let api: Api<Namespace> = Api::all(client);
-        let namespaces = api.list(&ListParams::default()).await?;
 
-        Ok(namespaces
-            .items
-            .iter()
-            .filter_map(|ns| {
-                let name = ns.metadata.name.clone()?;
-                let status = ns
-                    .status
-                    .as_ref()
-                    .and_then(|s| s.phase.clone())
-                    .unwrap_or_else(|| "Unknown".to_string());
-                let age = ns
-                    .metadata
-                    .creation_timestamp
-                    .as_ref()
-                    .map(|ts| format_age(&ts.0))
-                    .unwrap_or_else(|| "Unknown".to_string());
-
-                Some(ResourceItem {
-                    kind: ResourceKind::Namespace,
-                    name,
-                    namespace: None,
-                    status,
-                    age,
-                    restart_count: None,
-                    node_name: None,
-                    pod_ip: None,
-                    metadata: serde_json::to_value(ns).ok()?,
-                })
-            })
-            .collect())
+        // Printer columns only exist on CRDs; index them by (group, plural, version)
+        let crds: Api<CustomResourceDefinition> = Api::all(client.clone());
+        let crds = match crds.list(&ListParams::default()).await {
+            Ok(list) => list.items,
+            Err(e) => {
+                warn!("Failed to list CRDs, printer columns unavailable: {}", e);
+                Vec::new()
+            }
+        };
+        let crd_groups: HashSet<String> = crds.iter().map(|crd| crd.spec.group.clone()).collect();
+        let printer_columns: HashMap<(String, String, String), Vec<PrinterColumn>> =
+            crds.iter().flat_map(crd_printer_columns).collect();
+
+        let discovery = Discovery::new(client).run().await?;
+        let mut kinds = Vec::new();
+
+        for group in discovery.groups() {
+            let name = group.name();
+            // CRDs can live in *.k8s.io groups too (e.g. gateway.networking.k8s.io)
+            if !crd_groups.contains(name) && is_builtin_group(name) {
+                continue;
+            }
+
+            for (ar, caps) in group.recommended_resources() {
+                if !caps.supports_operation(verbs::LIST) {
+                    continue;
+                }
+
+                let key = (ar.group.clone(), ar.plural.clone(), ar.version.clone());
+                kinds.push(CustomResourceKind {
+                    printer_columns: printer_columns.get(&key).cloned().unwrap_or_default(),
+                    group: ar.group,
+                    version: ar.version,
+                    kind: ar.kind,
+                    plural: ar.plural,
+                    namespaced: matches!(caps.scope, Scope::Namespaced),
+                });
+            }
+        }
+
+        kinds.sort_by(|a, b| (&a.group, &a.kind).cmp(&(&b.group, &b.kind)));
+        info!("Discovered {} custom resource kinds", kinds.len());
+        Ok(kinds)
     }
 
-    async fn list_pods(
+    /// Watches `kind` and streams incremental changes into `tx` until the
+    /// receiver is dropped or the returned future is cancelled.
+    pub async fn watch_resources(
         &self,
-        client: &Client,
+        kind: ResourceKind,
         namespace: Option<&str>,
-    ) -> Result<Vec<ResourceItem>> {
-        let api: Api<Pod> = if let Some(ns) = namespace {
-            Api::namespaced(client.clone(), ns)
-        } else {
-            Api::all(client.clone())
-        };
+        tx: UnboundedSender<ResourceEvent>,
+    ) -> Result<()> {
+        let client = self.get_client().await?;
 
-        let pods = api.list(&ListParams::default()).await?;
+        match kind {
+            ResourceKind::Namespace => run_watcher(Api::all(client), namespace_item, tx).await,
+            ResourceKind::Pod => run_watcher(scoped_api(&client, namespace), pod_item, tx).await,
+            ResourceKind::Deployment => {
+                run_watcher(scoped_api(&client, namespace), deployment_item, tx).await
+            }
+            ResourceKind::StatefulSet => {
+                run_watcher(scoped_api(&client, namespace), statefulset_item, tx).await
+            }
+            ResourceKind::DaemonSet => {
+                run_watcher(scoped_api(&client, namespace), daemonset_item, tx).await
+            }
+            ResourceKind::ReplicaSet => {
+                run_watcher(scoped_api(&client, namespace), replicaset_item, tx).await
+            }
+            ResourceKind::Service => {
+                run_watcher(scoped_api(&client, namespace), service_item, tx).await
+            }
+            ResourceKind::Job => run_watcher(scoped_api(&client, namespace), job_item, tx).await,
+            ResourceKind::CronJob => {
+                run_watcher(scoped_api(&client, namespace), cronjob_item, tx).await
+            }
+            ResourceKind::ConfigMap => {
+                run_watcher(scoped_api(&client, namespace), configmap_item, tx).await
+            }
+            ResourceKind::Secret => {
+                run_watcher(scoped_api(&client, namespace), secret_item, tx).await
+            }
+            ResourceKind::Ingress => {
+                run_watcher(scoped_api(&client, namespace), ingress_item, tx).await
+            }
+            ResourceKind::PersistentVolume => {
+                run_watcher(Api::all(client), persistent_volume_item, tx).await
+            }
+            ResourceKind::PersistentVolumeClaim => {
+                run_watcher(
+                    scoped_api(&client, namespace),
+                    persistent_volume_claim_item,
+                    tx,
+                )
+                .await
+            }
+            ResourceKind::Node => run_watcher(Api::all(client), node_item, tx).await,
+            ResourceKind::Custom(custom) => {
+                let api = dynamic_api(&client, &custom, namespace);
+                run_watcher(api, |obj| dynamic_item(&custom, obj), tx).await
+            }
+        }
 
-        Ok(pods
-            .items
-            .iter()
-            .filter_map(|pod| {
-                let name = pod.metadata.name.clone()?;
-                let namespace = pod.metadata.namespace.clone();
-                let status = pod
-                    .status
-                    .as_ref()
-                    .and_then(|s| s.phase.clone())
-                    .unwrap_or_else(|| "Unknown".to_string());
-                let age = pod
-                    .metadata
-                    .creation_timestamp
-                    .as_ref()
-                    .map(|ts| format_age(&ts.0))
-                    .unwrap_or_else(|| "Unknown".to_string());
-
-                let restart_count = pod
-                    .status
-                    .as_ref()
-                    .and_then(|s| s.container_statuses.as_ref())
-                    .map(|statuses| statuses.iter().map(|s| s.restart_count).sum());
-
-                let node_name = pod.spec.as_ref().and_then(|s| s.node_name.clone());
-                let pod_ip = pod.status.as_ref().and_then(|s| s.pod_ip.clone());
-
-                Some(ResourceItem {
-                    kind: ResourceKind::Pod,
-                    name,
-                    namespace,
-                    status,
-                    age,
-                    restart_count,
-                    node_name,
-                    pod_ip,
-                    metadata: serde_json::to_value(pod).ok()?,
-                })
-            })
-            .collect())
+        Ok(())
     }
 
-    async fn list_deployments(
+    /// Watches `events.k8s.io/v1` Events in every namespace until the
+    /// receiver is dropped or the returned future is cancelled.
+    pub async fn watch_events(
         &self,
-        client: &Client,
-        namespace: Option<&str>,
-    ) -> Result<Vec<ResourceItem>> {
-        let api: Api<Deployment> = if let Some(ns) = namespace {
-            Api::namespaced(client.clone(), ns)
-        } else {
-            Api::all(client.clone())
+        tx: UnboundedSender<ResourceEvent<ClusterEvent>>,
+    ) -> Result<()> {
+        let client = self.get_client().await?;
+        run_watcher(Api::<Event>::all(client), event_item, tx).await;
+        Ok(())
+    }
+
+    /// Current usage of every pod and node from metrics-server. `None` when
+    /// the cluster doesn't serve `metrics.k8s.io`.
+    pub async fn metrics_snapshot(&self) -> Result<Option<MetricsSnapshot>> {
+        let client = self.get_client().await?;
+        let pods: Api<DynamicObject> = Api::all_with(client.clone(), &pod_metrics_resource());
+        let nodes: Api<DynamicObject> = Api::all_with(client, &node_metrics_resource());
+
+        let (pods, nodes) = futures::join!(
+            pods.list(&ListParams::default()),
+            nodes.list(&ListParams::default())
+        );
+        let (pods, nodes) = match (pods, nodes) {
+            (Ok(pods), Ok(nodes)) => (pods, nodes),
+            (Err(e), _) | (_, Err(e)) if is_metrics_unavailable(&e) => return Ok(None),
+            (Err(e), _) | (_, Err(e)) => {
+                return Err(e).context("Failed to read metrics from metrics.k8s.io")
+            }
         };
 
-        let deployments = api.list(&ListParams::default()).await?;
+        Ok(Some(MetricsSnapshot::new(
+            pods.items.iter().filter_map(pod_metrics).collect(),
+            nodes.items.iter().filter_map(node_metrics).collect(),
+        )))
+    }
 
-        Ok(deployments
-            .items
-            .iter()
-            .filter_map(|deploy| {
-                let name = deploy.metadata.name.clone()?;
-                let namespace = deploy.metadata.namespace.clone();
-                let status = deploy
-                    .status
-                    .as_ref()
-                    .map(|s| {
-                        format!(
-                            "{}/{}",
-                            s.ready_replicas.unwrap_or(0),
-                            s.replicas.unwrap_or(0)
-                        )
-                    })
-                    .unwrap_or_else(|| "Unknown".to_string());
-                let age = deploy
-                    .metadata
-                    .creation_timestamp
-                    .as_ref()
-                    .map(|ts| format_age(&ts.0))
-                    .unwrap_or_else(|| "Unknown".to_string());
-
-                Some(ResourceItem {
-                    kind: ResourceKind::Deployment,
-                    name,
-                    namespace,
-                    status,
-                    age,
-                    restart_count: None,
-                    node_name: None,
-                    pod_ip: None,
-                    metadata: serde_json::to_value(deploy).ok()?,
-                })
-            })
-            .collect())
+    /// Names of the pods in `namespace` matching `selector`.
+    pub async fn pod_names(&self, namespace: &str, selector: &str) -> Result<Vec<String>> {
+        let client = self.get_client().await?;
+        let api: Api<Pod> = Api::namespaced(client, namespace);
+        let pods = api
+            .list(&ListParams::default().labels(selector))
+            .await
+            .with_context(|| format!("Failed to list pods matching {}", selector))?;
+        Ok(pods.items.iter().map(|pod| pod.name_any()).collect())
     }
 
-    async fn list_statefulsets(
+    /// Runs a PromQL range query over the last `seconds`, in about
+    /// `PROMETHEUS_POINTS` steps.
+    pub async fn prometheus_range(
         &self,
-        client: &Client,
-        namespace: Option<&str>,
-    ) -> Result<Vec<ResourceItem>> {
-        let api: Api<StatefulSet> = if let Some(ns) = namespace {
-            Api::namespaced(client.clone(), ns)
-        } else {
-            Api::all(client.clone())
+        endpoint: &PrometheusEndpoint,
+        query: &str,
+        seconds: i64,
+    ) -> Result<Vec<QuerySeries>> {
+        let end = chrono::Utc::now().timestamp();
+        let step = (seconds / PROMETHEUS_POINTS).max(PROMETHEUS_MIN_STEP);
+        let params = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("query", query)
+            .append_pair("start", &(end - seconds).to_string())
+            .append_pair("end", &end.to_string())
+            .append_pair("step", &step.to_string())
+            .finish();
+
+        let body = match endpoint {
+            PrometheusEndpoint::Url { url } => {
+                let url = format!(
+                    "{}/api/v1/query_range?{}",
+                    url.trim_end_matches('/'),
+                    params
+                );
+                // Prometheus answers bad queries with 400 and a JSON error body
+                self.http
+                    .get(&url)
+                    .send()
+                    .await
+                    .with_context(|| format!("Failed to reach Prometheus at {}", url))?
+                    .text()
+                    .await
+                    .context("Failed to read Prometheus response")?
+            }
+            PrometheusEndpoint::Service { namespace, service } => {
+                let client = self.get_client().await?;
+                let path = format!(
+                    "/api/v1/namespaces/{}/services/{}/proxy/api/v1/query_range?{}",
+                    namespace, service, params
+                );
+                let request = k8s_openapi::http::Request::get(path)
+                    .body(Vec::new())
+                    .context("Failed to build Prometheus request")?;
+                tokio::time::timeout(PROMETHEUS_TIMEOUT, client.request_text(request))
+                    .await
+                    .map_err(|_| anyhow!("Prometheus did not answer in time"))
+                    .and_then(|response| response.map_err(Into::into))
+                    .with_context(|| {
+                        format!(
+                            "Failed to query Prometheus through service {}/{}",
+                            namespace, service
+                        )
+                    })?
+            }
         };
+        parse_range_response(&body)
+    }
 
-        let statefulsets = api.list(&ListParams::default()).await?;
+    pub async fn get_pod_logs(
+        &self,
+        namespace: &str,
+        pod_name: &str,
+        options: &LogOptions,
+    ) -> Result<String> {
+        let client = self.get_client().await?;
+        let api: Api<Pod> = Api::namespaced(client, namespace);
 
-        Ok(statefulsets
-            .items
-            .iter()
-            .filter_map(|ss| {
-                let name = ss.metadata.name.clone()?;
-                let namespace = ss.metadata.namespace.clone();
-                let status = ss
-                    .status
-                    .as_ref()
-                    .map(|s| format!("{}/{}", s.ready_replicas.unwrap_or(0), s.replicas))
-                    .unwrap_or_else(|| "Unknown".to_string());
-                let age = ss
-                    .metadata
-                    .creation_timestamp
-                    .as_ref()
-                    .map(|ts| format_age(&ts.0))
-                    .unwrap_or_else(|| "Unknown".to_string());
-
-                Some(ResourceItem {
-                    kind: ResourceKind::StatefulSet,
-                    name,
-                    namespace,
-                    status,
-                    age,
-                    restart_count: None,
-                    node_name: None,
-                    pod_ip: None,
-                    metadata: serde_json::to_value(ss).ok()?,
-                })
-            })
-            .collect())
+        let logs = api.logs(pod_name, &log_params(options, false)).await?;
+        Ok(logs)
     }
 
-    async fn list_daemonsets(
+    /// Reads the current log of every started container of the pods matching
+    /// `selector`, one container after another.
+    pub async fn get_selector_logs(
         &self,
-        client: &Client,
-        namespace: Option<&str>,
-    ) -> Result<Vec<ResourceItem>> {
-        let api: Api<DaemonSet> = if let Some(ns) = namespace {
-            Api::namespaced(client.clone(), ns)
-        } else {
-            Api::all(client.clone())
-        };
+        namespace: &str,
+        selector: &str,
+        options: &LogOptions,
+    ) -> Result<Vec<LogLine>> {
+        let client = self.get_client().await?;
+        let api: Api<Pod> = Api::namespaced(client, namespace);
 
-        let daemonsets = api.list(&ListParams::default()).await?;
+        let pods = api.list(&ListParams::default().labels(selector)).await?;
+        let mut lines = Vec::new();
+        for pod in &pods.items {
+            let pod_name = pod.name_any();
+            for (container, _) in started_containers(pod) {
+                let params = log_params(
+                    &LogOptions {
+                        container: Some(container.clone()),
+                        previous: false,
+                        ..options.clone()
+                    },
+                    false,
+                );
+                let logs = api
+                    .logs(&pod_name, &params)
+                    .await
+                    .with_context(|| format!("Failed to read {}/{}", pod_name, container))?;
+                lines.extend(logs.lines().map(|text| LogLine {
+                    pod: pod_name.clone(),
+                    container: Some(container.clone()),
+                    text: text.to_string(),
+                }));
+            }
+        }
+        Ok(lines)
+    }
 
-        Ok(daemonsets
-            .items
-            .iter()
-            .filter_map(|ds| {
-                let name = ds.metadata.name.clone()?;
-                let namespace = ds.metadata.namespace.clone();
-                let status = ds
-                    .status
-                    .as_ref()
-                    .map(|s| format!("{}/{}", s.number_ready, s.desired_number_scheduled))
-                    .unwrap_or_else(|| "Unknown".to_string());
-                let age = ds
-                    .metadata
-                    .creation_timestamp
-                    .as_ref()
-                    .map(|ts| format_age(&ts.0))
-                    .unwrap_or_else(|| "Unknown".to_string());
-
-                Some(ResourceItem {
-                    kind: ResourceKind::DaemonSet,
-                    name,
-                    namespace,
-                    status,
-                    age,
-                    restart_count: None,
-                    node_name: None,
-                    pod_ip: None,
-                    metadata: serde_json::to_value(ds).ok()?,
-                })
-            })
-            .collect())
+    /// Follows the pod log, sending each line to `tx` until the stream ends or
+    /// the receiver is dropped.
+    pub async fn stream_pod_logs(
+        &self,
+        namespace: &str,
+        pod_name: &str,
+        options: &LogOptions,
+        tx: UnboundedSender<LogLine>,
+    ) -> Result<()> {
+        let client = self.get_client().await?;
+        let api: Api<Pod> = Api::namespaced(client, namespace);
+
+        // A terminated container's log can't grow, so there is nothing to follow
+        let log_params = log_params(options, !options.previous);
+
+        let mut lines = api.log_stream(pod_name, &log_params).await?.lines();
+        while let Some(text) = lines.try_next().await? {
+            let line = LogLine {
+                pod: pod_name.to_string(),
+                container: options.container.clone(),
+                text,
+            };
+            if tx.unbounded_send(line).is_err() {
+                break;
+            }
+        }
+        Ok(())
     }
 
-    async fn list_replicasets(
+    /// Follows every container of the pods matching `selector`, picking up pods
+    /// as they start and dropping them as they go away.
+    pub async fn stream_selector_logs(
         &self,
-        client: &Client,
-        namespace: Option<&str>,
-    ) -> Result<Vec<ResourceItem>> {
-        let api: Api<ReplicaSet> = if let Some(ns) = namespace {
-            Api::namespaced(client.clone(), ns)
-        } else {
-            Api::all(client.clone())
-        };
+        namespace: &str,
+        selector: &str,
+        options: &LogOptions,
+        tx: UnboundedSender<LogLine>,
+    ) -> Result<()> {
+        let client = self.get_client().await?;
+        let api: Api<Pod> = Api::namespaced(client, namespace);
 
-        let replicasets = api.list(&ListParams::default()).await?;
+        // Dropping the set (when this future is cancelled) aborts every follower
+        let mut followers = JoinSet::new();
+        // Restart count is kept so a restarted container is followed again
+        let mut streams: HashMap<(String, String), (AbortHandle, i32)> = HashMap::new();
+
+        let config = watcher::Config::default().labels(selector);
+        let mut events = watcher(api.clone(), config).default_backoff().boxed();
+
+        while let Some(event) = events.next().await {
+            if tx.is_closed() {
+                break;
+            }
+
+            let pod = match event {
+                Ok(watcher::Event::Apply(pod)) | Ok(watcher::Event::InitApply(pod)) => pod,
+                Ok(watcher::Event::Delete(pod)) => {
+                    let name = pod.name_any();
+                    streams.retain(|(pod_name, _), (handle, _)| {
+                        let keep = *pod_name != name;
+                        if !keep {
+                            handle.abort();
+                        }
+                        keep
+                    });
+                    continue;
+                }
+                Ok(_) => continue,
+                Err(e) => {
+                    warn!("Pod watch error: {}", e);
+                    continue;
+                }
+            };
+
+            let pod_name = pod.name_any();
+            for (container, restart_count) in started_containers(&pod) {
+                let key = (pod_name.clone(), container);
+                if let Some((handle, restarts)) = streams.get(&key) {
+                    if !handle.is_finished() || *restarts == restart_count {
+                        continue;
+                    }
+                }
+
+                let params = log_params(
+                    &LogOptions {
+                        container: Some(key.1.clone()),
+                        previous: false,
+                        ..options.clone()
+                    },
+                    true,
+                );
+                let handle = followers.spawn(follow_container(
+                    api.clone(),
+                    key.0.clone(),
+                    key.1.clone(),
+                    params,
+                    tx.clone(),
+                ));
+                streams.insert(key, (handle, restart_count));
+            }
+
+            // Reap followers that already finished
+            while followers.try_join_next().is_some() {}
+        }
 
-        Ok(replicasets
-            .items
-            .iter()
-            .filter_map(|rs| {
-                let name = rs.metadata.name.clone()?;
-                let namespace = rs.metadata.namespace.clone();
-                let status = rs
-                    .status
-                    .as_ref()
-                    .map(|s| format!("{}/{}", s.ready_replicas.unwrap_or(0), s.replicas))
-                    .unwrap_or_else(|| "Unknown".to_string());
-                let age = rs
-                    .metadata
-                    .creation_timestamp
-                    .as_ref()
-                    .map(|ts| format_age(&ts.0))
-                    .unwrap_or_else(|| "Unknown".to_string());
-
-                Some(ResourceItem {
-                    kind: ResourceKind::ReplicaSet,
-                    name,
-                    namespace,
-                    status,
-                    age,
-                    restart_count: None,
-                    node_name: None,
-                    pod_ip: None,
-                    metadata: serde_json::to_value(rs).ok()?,
-                })
-            })
-            .collect())
+        Ok(())
     }
 
-    async fn list_services(
+    /// Runs an interactive TTY session in the pod, trying each command in turn
+    /// until one exists in the image. Keystrokes and resizes arrive on `input`;
+    /// output and the exit status go to `tx`. Dropping `input`'s sender ends the
+    /// session.
+    pub async fn exec_shell(
         &self,
-        client: &Client,
-        namespace: Option<&str>,
-    ) -> Result<Vec<ResourceItem>> {
-        let api: Api<Service> = if let Some(ns) = namespace {
-            Api::namespaced(client.clone(), ns)
-        } else {
-            Api::all(client.clone())
-        };
+        namespace: &str,
+        pod_name: &str,
+        container: Option<String>,
+        commands: Vec<Vec<String>>,
+        mut input: UnboundedReceiver<TerminalInput>,
+        tx: UnboundedSender<ExecEvent>,
+    ) -> Result<()> {
+        let client = self.get_client().await?;
+        let api: Api<Pod> = Api::namespaced(client, namespace);
 
-        let services = api.list(&ListParams::default()).await?;
+        let mut params = AttachParams::interactive_tty();
+        if let Some(container) = container {
+            params = params.container(container);
+        }
 
-        Ok(services
-            .items
-            .iter()
-            .filter_map(|svc| {
-                let name = svc.metadata.name.clone()?;
-                let namespace = svc.metadata.namespace.clone();
-                let status = svc
-                    .spec
-                    .as_ref()
-                    .and_then(|s| s.type_.clone())
-                    .unwrap_or_else(|| "Unknown".to_string());
-                let age = svc
-                    .metadata
-                    .creation_timestamp
-                    .as_ref()
-                    .map(|ts| format_age(&ts.0))
-                    .unwrap_or_else(|| "Unknown".to_string());
-
-                Some(ResourceItem {
-                    kind: ResourceKind::Service,
-                    name,
-                    namespace,
-                    status,
-                    age,
-                    restart_count: None,
-                    node_name: None,
-                    pod_ip: None,
-                    metadata: serde_json::to_value(svc).ok()?,
-                })
-            })
-            .collect())
+        // Last size reported by the view, replayed to a fallback shell
+        let mut size: Option<TerminalSize> = None;
+
+        for (attempt, command) in commands.iter().enumerate() {
+            let is_last = attempt + 1 == commands.len();
+            let mut process = api.exec(pod_name, command.clone(), &params).await?;
+            let mut stdin = process.stdin().context("Exec session has no stdin")?;
+            let mut stdout = process.stdout().context("Exec session has no stdout")?;
+            let mut resize = process.terminal_size();
+            let status = process.take_status();
+
+            if let (Some(resize), Some(size)) = (resize.as_mut(), size.clone()) {
+                let _ = resize.send(size).await;
+            }
+            let _ = tx.unbounded_send(ExecEvent::Started(command.join(" ")));
+
+            let mut buf = vec![0u8; 8192];
+            loop {
+                tokio::select! {
+                    read = stdout.read(&mut buf) => match read {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => {
+                            if tx.unbounded_send(ExecEvent::Output(buf[..n].to_vec())).is_err() {
+                                process.abort();
+                                return Ok(());
+                            }
+                        }
+                    },
+                    message = input.next() => match message {
+                        Some(TerminalInput::Data(bytes)) => {
+                            if stdin.write_all(&bytes).await.is_err() {
+                                break;
+                            }
+                        }
+                        Some(TerminalInput::Resize { cols, rows }) => {
+                            let new_size = TerminalSize {
+                                width: cols,
+                                height: rows,
+                            };
+                            if let Some(resize) = resize.as_mut() {
+                                let _ = resize.send(new_size.clone()).await;
+                            }
+                            size = Some(new_size);
+                        }
+                        None => {
+                            process.abort();
+                            return Ok(());
+                        }
+                    },
+                }
+            }
+
+            let status = match status {
+                Some(status) => status.await,
+                None => None,
+            };
+            match status.filter(|s| s.status.as_deref() == Some("Failure")) {
+                Some(status) if !is_last && is_missing_command(&status) => {
+                    info!(
+                        "{} not available in {}, trying next shell",
+                        command.join(" "),
+                        pod_name
+                    );
+                }
+                Some(status) => {
+                    let message = status
+                        .message
+                        .unwrap_or_else(|| "Session failed".to_string());
+                    let _ = tx.unbounded_send(ExecEvent::Exited(Some(message)));
+                    return Ok(());
+                }
+                None => {
+                    let _ = tx.unbounded_send(ExecEvent::Exited(None));
+                    return Ok(());
+                }
+            }
+        }
+
+        Ok(())
     }
 
-    async fn list_jobs(
+    /// Serves `spec` on a localhost listener until the task is aborted. A
+    /// service is resolved to one ready pod when the forward starts.
+    pub async fn port_forward(
         &self,
-        client: &Client,
-        namespace: Option<&str>,
-    ) -> Result<Vec<ResourceItem>> {
-        let api: Api<Job> = if let Some(ns) = namespace {
-            Api::namespaced(client.clone(), ns)
-        } else {
-            Api::all(client.clone())
+        spec: &PortForwardSpec,
+        stats: Arc<PortForwardStats>,
+    ) -> Result<()> {
+        let client = self.get_client().await?;
+        let pods: Api<Pod> = Api::namespaced(client.clone(), &spec.namespace);
+
+        let (pod, port) = match spec.kind {
+            ResourceKind::Service => {
+                let services: Api<Service> = Api::namespaced(client, &spec.namespace);
+                let service = services.get(&spec.name).await?;
+                port_forward::resolve_service(&pods, &service, spec.remote_port).await?
+            }
+            _ => (spec.name.clone(), spec.remote_port),
         };
 
-        let jobs = api.list(&ListParams::default()).await?;
+        port_forward::serve(pods, pod, port, spec.local_port, stats).await
+    }
 
-        Ok(jobs
-            .items
-            .iter()
-            .filter_map(|job| {
-                let name = job.metadata.name.clone()?;
-                let namespace = job.metadata.namespace.clone();
-                let status = job
-                    .status
-                    .as_ref()
-                    .map(|s| format!("{}/{}", s.succeeded.unwrap_or(0), s.active.unwrap_or(0)))
-                    .unwrap_or_else(|| "Unknown".to_string());
-                let age = job
-                    .metadata
-                    .creation_timestamp
-                    .as_ref()
-                    .map(|ts| format_age(&ts.0))
-                    .unwrap_or_else(|| "Unknown".to_string());
-
-                Some(ResourceItem {
-                    kind: ResourceKind::Job,
-                    name,
-                    namespace,
-                    status,
-                    age,
-                    restart_count: None,
-                    node_name: None,
-                    pod_ip: None,
-                    metadata: serde_json::to_value(job).ok()?,
-                })
-            })
-            .collect())
+    /// Current state of one object, as the full JSON the editor starts from.
+    pub async fn get_object(
+        &self,
+        kind: &ResourceKind,
+        namespace: Option<&str>,
+        name: &str,
+    ) -> Result<serde_json::Value> {
+        let client = self.get_client().await?;
+        let object = object_api(&client, kind, namespace).get(name).await?;
+        Ok(serde_json::to_value(object)?)
     }
 
-    async fn list_cronjobs(
+    /// Writes an edited manifest back. A `resourceVersion` in `yaml` makes a
+    /// stale write fail with `SaveError::Conflict`; `force` drops that check
+    /// and, for apply, takes ownership of conflicting fields. With `dry_run`
+    /// the server runs admission (`dryRun=All`) and returns the result
+    /// without persisting it.
+    pub async fn save_object(
         &self,
-        client: &Client,
+        kind: &ResourceKind,
         namespace: Option<&str>,
-    ) -> Result<Vec<ResourceItem>> {
-        let api: Api<CronJob> = if let Some(ns) = namespace {
-            Api::namespaced(client.clone(), ns)
-        } else {
-            Api::all(client.clone())
+        name: &str,
+        yaml: &str,
+        mode: SaveMode,
+        force: bool,
+        dry_run: bool,
+    ) -> std::result::Result<serde_json::Value, SaveError> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| SaveError::Failed(e.to_string()))?;
+        let mut object: DynamicObject = serde_yaml::from_str(yaml)
+            .map_err(|e| SaveError::Failed(format!("Invalid YAML: {}", e)))?;
+        if object.metadata.name.as_deref() != Some(name) {
+            return Err(SaveError::Failed(format!(
+                "metadata.name must stay \"{}\"; renaming isn't supported",
+                name
+            )));
+        }
+        // Server-owned; applies that include it are rejected
+        object.metadata.managed_fields = None;
+
+        let api = object_api(&client, kind, namespace);
+        let saved = match mode {
+            SaveMode::Apply => {
+                let mut params = PatchParams::apply(FIELD_MANAGER);
+                params.dry_run = dry_run;
+                if force {
+                    params = params.force();
+                    object.metadata.resource_version = None;
+                }
+                api.patch(name, &params, &Patch::Apply(&object)).await?
+            }
+            SaveMode::Replace => {
+                if force {
+                    object.metadata.resource_version = api.get(name).await?.resource_version();
+                }
+                let params = PostParams {
+                    dry_run,
+                    ..PostParams::default()
+                };
+                api.replace(name, &params, &object).await?
+            }
         };
 
-        let cronjobs = api.list(&ListParams::default()).await?;
-
-        Ok(cronjobs
-            .items
-            .iter()
-            .filter_map(|cj| {
-                let name = cj.metadata.name.clone()?;
-                let namespace = cj.metadata.namespace.clone();
-                let status = cj
-                    .spec
-                    .as_ref()
-                    .map(|s| s.schedule.clone())
-                    .unwrap_or_else(|| "Unknown".to_string());
-                let age = cj
-                    .metadata
-                    .creation_timestamp
-                    .as_ref()
-                    .map(|ts| format_age(&ts.0))
-                    .unwrap_or_else(|| "Unknown".to_string());
-
-                Some(ResourceItem {
-                    kind: ResourceKind::CronJob,
-                    name,
-                    namespace,
-                    status,
-                    age,
-                    restart_count: None,
-                    node_name: None,
-                    pod_ip: None,
-                    metadata: serde_json::to_value(cj).ok()?,
-                })
-            })
-            .collect())
+        serde_json::to_value(saved).map_err(|e| SaveError::Failed(e.to_string()))
     }
 
-    async fn list_configmaps(
+    pub async fn delete_resource(
         &self,
-        client: &Client,
+        kind: &ResourceKind,
         namespace: Option<&str>,
-    ) -> Result<Vec<ResourceItem>> {
-        let api: Api<ConfigMap> = if let Some(ns) = namespace {
-            Api::namespaced(client.clone(), ns)
-        } else {
-            Api::all(client.clone())
+        name: &str,
+        options: &DeleteOptions,
+    ) -> Result<()> {
+        let client = self.get_client().await?;
+        let params = DeleteParams {
+            grace_period_seconds: if options.force {
+                Some(0)
+            } else {
+                options.grace_period
+            },
+            propagation_policy: Some(match options.propagation {
+                DeletePropagation::Background => PropagationPolicy::Background,
+                DeletePropagation::Foreground => PropagationPolicy::Foreground,
+                DeletePropagation::Orphan => PropagationPolicy::Orphan,
+            }),
+            ..DeleteParams::default()
         };
 
-        let configmaps = api.list(&ListParams::default()).await?;
+        object_api(&client, kind, namespace)
+            .delete(name, &params)
+            .await
+            .with_context(|| format!("Failed to delete {} {}", kind.kind_name(), name))?;
+        Ok(())
+    }
 
-        Ok(configmaps
-            .items
-            .iter()
-            .filter_map(|cm| {
-                let name = cm.metadata.name.clone()?;
-                let namespace = cm.metadata.namespace.clone();
-                let status = cm
-                    .data
-                    .as_ref()
-                    .map(|d| format!("{} keys", d.len()))
-                    .unwrap_or_else(|| "0 keys".to_string());
-                let age = cm
-                    .metadata
-                    .creation_timestamp
-                    .as_ref()
-                    .map(|ts| format_age(&ts.0))
-                    .unwrap_or_else(|| "Unknown".to_string());
-
-                Some(ResourceItem {
-                    kind: ResourceKind::ConfigMap,
-                    name,
-                    namespace,
-                    status,
-                    age,
-                    restart_count: None,
-                    node_name: None,
-                    pod_ip: None,
-                    metadata: serde_json::to_value(cm).ok()?,
-                })
-            })
-            .collect())
+    /// Objects garbage collected along with `item`, found by following
+    /// `ownerReferences` down through the kinds each controller creates.
+    pub async fn find_dependents(&self, item: &ResourceItem) -> Result<Vec<ResourceRef>> {
+        let client = self.get_client().await?;
+        let namespace = item.namespace.as_deref();
+        let mut listed: HashMap<ResourceKind, Vec<DynamicObject>> = HashMap::new();
+        let mut dependents = Vec::new();
+        let mut owners = vec![(
+            item.kind.clone(),
+            item.metadata
+                .pointer("/metadata/uid")
+                .and_then(|uid| uid.as_str())
+                .map(str::to_string),
+        )];
+
+        while let Some((kind, uid)) = owners.pop() {
+            let Some(uid) = uid else {
+                continue;
+            };
+            for child_kind in owned_kinds(&kind) {
+                if !listed.contains_key(&child_kind) {
+                    let api = object_api(&client, &child_kind, namespace);
+                    let items = api.list(&ListParams::default()).await?.items;
+                    listed.insert(child_kind.clone(), items);
+                }
+
+                for child in &listed[&child_kind] {
+                    if child
+                        .owner_references()
+                        .iter()
+                        .any(|owner| owner.uid == uid)
+                    {
+                        dependents.push(ResourceRef {
+                            kind: child_kind.clone(),
+                            namespace: child.namespace(),
+                            name: child.name_any(),
+                        });
+                        owners.push((child_kind.clone(), child.uid()));
+                    }
+                }
+            }
+        }
+
+        Ok(dependents)
     }
 
-    async fn list_secrets(
+    /// Sets the replica count through the `scale` subresource.
+    pub async fn scale(
         &self,
-        client: &Client,
-        namespace: Option<&str>,
-    ) -> Result<Vec<ResourceItem>> {
-        let api: Api<Secret> = if let Some(ns) = namespace {
-            Api::namespaced(client.clone(), ns)
-        } else {
-            Api::all(client.clone())
-        };
+        kind: &ResourceKind,
+        namespace: &str,
+        name: &str,
+        replicas: i32,
+    ) -> Result<()> {
+        let client = self.get_client().await?;
+        let patch = serde_json::json!({ "spec": { "replicas": replicas } });
+        object_api(&client, kind, Some(namespace))
+            .patch_scale(name, &PatchParams::default(), &Patch::Merge(&patch))
+            .await
+            .with_context(|| format!("Failed to scale {} {}", kind.kind_name(), name))?;
+        Ok(())
+    }
+
+    /// Same as `kubectl rollout restart`: stamping the pod template makes the
+    /// controller replace every pod.
+    pub async fn restart_rollout(
+        &self,
+        kind: &ResourceKind,
+        namespace: &str,
+        name: &str,
+    ) -> Result<()> {
+        let client = self.get_client().await?;
+        let patch = serde_json::json!({
+            "spec": { "template": { "metadata": { "annotations": {
+                "kubectl.kubernetes.io/restartedAt": chrono::Utc::now().to_rfc3339(),
+            } } } }
+        });
+        object_api(&client, kind, Some(namespace))
+            .patch(name, &PatchParams::default(), &Patch::Merge(&patch))
+            .await
+            .with_context(|| format!("Failed to restart {} {}", kind.kind_name(), name))?;
+        Ok(())
+    }
 
-        let secrets = api.list(&ListParams::default()).await?;
+    /// Pauses or resumes a Deployment's rollout.
+    pub async fn set_rollout_paused(
+        &self,
+        namespace: &str,
+        name: &str,
+        paused: bool,
+    ) -> Result<()> {
+        let client = self.get_client().await?;
+        let patch = serde_json::json!({ "spec": { "paused": paused } });
+        object_api(&client, &ResourceKind::Deployment, Some(namespace))
+            .patch(name, &PatchParams::default(), &Patch::Merge(&patch))
+            .await
+            .with_context(|| format!("Failed to update deployment {}", name))?;
+        Ok(())
+    }
 
-        Ok(secrets
-            .items
-            .iter()
-            .filter_map(|secret| {
-                let name = secret.metadata.name.clone()?;
-                let namespace = secret.metadata.namespace.clone();
-                let status = secret
-                    .type_
-                    .as_ref()
-                    .map(|t| t.clone())
-                    .unwrap_or_else(|| "Opaque".to_string());
-                let age = secret
+    /// Past pod templates of a Deployment (kept in its ReplicaSets) or of a
+    /// StatefulSet or DaemonSet (kept in its ControllerRevisions), newest first.
+    pub async fn rollout_history(&self, item: &ResourceItem) -> Result<Vec<Revision>> {
+        let client = self.get_client().await?;
+        let namespace = item
+            .namespace
+            .as_deref()
+            .ok_or_else(|| anyhow!("{} has no namespace", item.name))?;
+        let uid = item
+            .metadata
+            .pointer("/metadata/uid")
+            .and_then(|uid| uid.as_str())
+            .unwrap_or_default();
+        let owned = |owners: &[OwnerReference]| owners.iter().any(|owner| owner.uid == uid);
+
+        let mut revisions: Vec<Revision> = match item.kind {
+            ResourceKind::Deployment => {
+                let current = item
                     .metadata
-                    .creation_timestamp
-                    .as_ref()
-                    .map(|ts| format_age(&ts.0))
-                    .unwrap_or_else(|| "Unknown".to_string());
-
-                Some(ResourceItem {
-                    kind: ResourceKind::Secret,
-                    name,
-                    namespace,
-                    status,
-                    age,
-                    restart_count: None,
-                    node_name: None,
-                    pod_ip: None,
-                    metadata: serde_json::to_value(secret).ok()?,
-                })
-            })
-            .collect())
+                    .pointer("/metadata/annotations")
+                    .and_then(|a| a.get(REVISION_ANNOTATION))
+                    .and_then(|r| r.as_str())
+                    .and_then(|r| r.parse::<i64>().ok());
+                let api: Api<ReplicaSet> = Api::namespaced(client, namespace);
+                api.list(&ListParams::default())
+                    .await?
+                    .items
+                    .into_iter()
+                    .filter(|rs| owned(rs.owner_references()))
+                    .filter_map(|rs| {
+                        let annotations = rs.annotations();
+                        let revision = annotations.get(REVISION_ANNOTATION)?.parse().ok()?;
+                        let mut template =
+                            serde_json::to_value(&rs.spec.as_ref()?.template).ok()?;
+                        // Added by the controller to tell its ReplicaSets apart
+                        if let Some(labels) = template
+                            .pointer_mut("/metadata/labels")
+                            .and_then(|labels| labels.as_object_mut())
+                        {
+                            labels.remove("pod-template-hash");
+                        }
+                        Some(Revision {
+                            revision,
+                            name: rs.name_any(),
+                            change_cause: annotations.get(CHANGE_CAUSE_ANNOTATION).cloned(),
+                            images: template_images(&template),
+                            age: rs
+                                .creation_timestamp()
+                                .map(|ts| format_age(&ts.0))
+                                .unwrap_or_default(),
+                            template,
+                            current: current == Some(revision),
+                        })
+                    })
+                    .collect()
+            }
+            ResourceKind::StatefulSet | ResourceKind::DaemonSet => {
+                // Only StatefulSets report which revision they are rolling to;
+                // for DaemonSets the newest one is current
+                let update_revision = item
+                    .metadata
+                    .pointer("/status/updateRevision")
+                    .and_then(|r| r.as_str());
+                let api: Api<ControllerRevision> = Api::namespaced(client, namespace);
+                api.list(&ListParams::default())
+                    .await?
+                    .items
+                    .into_iter()
+                    .filter(|cr| owned(cr.owner_references()))
+                    .filter_map(|cr| {
+                        // `data` is a patch: {"spec": {"template": {..., "$patch": "replace"}}}
+                        let mut template = cr.data.as_ref()?.0.pointer("/spec/template")?.clone();
+                        if let Some(template) = template.as_object_mut() {
+                            template.remove("$patch");
+                        }
+                        Some(Revision {
+                            revision: cr.revision,
+                            name: cr.name_any(),
+                            change_cause: cr.annotations().get(CHANGE_CAUSE_ANNOTATION).cloned(),
+                            images: template_images(&template),
+                            age: cr
+                                .creation_timestamp()
+                                .map(|ts| format_age(&ts.0))
+                                .unwrap_or_default(),
+                            template,
+                            current: update_revision == Some(cr.name_any().as_str()),
+                        })
+                    })
+                    .collect()
+            }
+            _ => return Err(anyhow!("{} has no rollout history", item.kind.kind_name())),
+        };
+
+        revisions.sort_by(|a, b| b.revision.cmp(&a.revision));
+        if !revisions.iter().any(|r| r.current) {
+            if let Some(newest) = revisions.first_mut() {
+                newest.current = true;
+            }
+        }
+        Ok(revisions)
     }
 
-    async fn list_ingresses(
+    /// Puts `revision`'s pod template back; the controller rolls it out as a
+    /// new revision, as `kubectl rollout undo` does.
+    pub async fn rollback(&self, item: &ResourceItem, revision: &Revision) -> Result<()> {
+        let client = self.get_client().await?;
+        let api = object_api(&client, &item.kind, item.namespace.as_deref());
+        let mut object = api.get(&item.name).await?;
+        object.data["spec"]["template"] = revision.template.clone();
+        api.replace(&item.name, &PostParams::default(), &object)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to roll {} {} back to revision {}",
+                    item.kind.kind_name(),
+                    item.name,
+                    revision.revision
+                )
+            })?;
+        Ok(())
+    }
+
+    /// Marks a node unschedulable or schedulable again, like `kubectl
+    /// cordon` and `kubectl uncordon`.
+    pub async fn set_unschedulable(&self, name: &str, unschedulable: bool) -> Result<()> {
+        let client = self.get_client().await?;
+        let api: Api<Node> = Api::all(client);
+        let patch = serde_json::json!({ "spec": { "unschedulable": unschedulable } });
+        api.patch(name, &PatchParams::default(), &Patch::Merge(&patch))
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to {} node {}",
+                    if unschedulable { "cordon" } else { "uncordon" },
+                    name
+                )
+            })?;
+        Ok(())
+    }
+
+    /// Cordons a node and evicts its pods through the Eviction API, so
+    /// PodDisruptionBudgets are honoured. Refused evictions are retried until
+    /// `options.timeout_secs` runs out. DaemonSet and static pods stay.
+    pub async fn drain_node(
         &self,
-        client: &Client,
-        namespace: Option<&str>,
-    ) -> Result<Vec<ResourceItem>> {
-        let api: Api<Ingress> = if let Some(ns) = namespace {
-            Api::namespaced(client.clone(), ns)
-        } else {
-            Api::all(client.clone())
-        };
+        name: &str,
+        options: &DrainOptions,
+        tx: UnboundedSender<DrainEvent>,
+    ) -> Result<()> {
+        self.set_unschedulable(name, true).await?;
 
-        let ingresses = api.list(&ListParams::default()).await?;
+        let client = self.get_client().await?;
+        let params = ListParams::default().fields(&format!("spec.nodeName={}", name));
+        let pods = Api::<Pod>::all(client.clone())
+            .list(&params)
+            .await
+            .with_context(|| format!("Failed to list pods on node {}", name))?;
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(options.timeout_secs);
+        let mut evictions = JoinSet::new();
+        let mut listed = Vec::new();
+        for pod in pods.items {
+            let namespace = pod.namespace().unwrap_or_default();
+            let pod_name = pod.name_any();
+            let state = drain_state(&pod, options);
+            if state == DrainPodState::Pending {
+                evictions.spawn(evict_pod(
+                    Api::namespaced(client.clone(), &namespace),
+                    namespace.clone(),
+                    pod_name.clone(),
+                    pod.uid(),
+                    options.grace_period,
+                    deadline,
+                    tx.clone(),
+                ));
+            }
+            listed.push(DrainPod {
+                namespace,
+                name: pod_name,
+                state,
+            });
+        }
 
-        Ok(ingresses
-            .items
+        let left_behind = listed
             .iter()
-            .filter_map(|ing| {
-                let name = ing.metadata.name.clone()?;
-                let namespace = ing.metadata.namespace.clone();
-                let status = ing
-                    .spec
-                    .as_ref()
-                    .and_then(|s| s.ingress_class_name.clone())
-                    .unwrap_or_else(|| "default".to_string());
-                let age = ing
-                    .metadata
-                    .creation_timestamp
-                    .as_ref()
-                    .map(|ts| format_age(&ts.0))
-                    .unwrap_or_else(|| "Unknown".to_string());
-
-                Some(ResourceItem {
-                    kind: ResourceKind::Ingress,
-                    name,
-                    namespace,
-                    status,
-                    age,
-                    restart_count: None,
-                    node_name: None,
-                    pod_ip: None,
-                    metadata: serde_json::to_value(ing).ok()?,
-                })
-            })
-            .collect())
+            .filter(|p| matches!(p.state, DrainPodState::Failed(_)))
+            .count();
+        let _ = tx.unbounded_send(DrainEvent::Pods(listed));
+
+        let mut failed = left_behind;
+        while let Some(evicted) = evictions.join_next().await {
+            if !evicted.unwrap_or(false) {
+                failed += 1;
+            }
+        }
+
+        if failed > 0 {
+            return Err(anyhow!(
+                "{} pod{} left on node {}",
+                failed,
+                if failed == 1 { " was" } else { "s were" },
+                name
+            ));
+        }
+        Ok(())
     }
+}
 
-    async fn list_nodes(&self, client: &Client) -> Result<Vec<ResourceItem>> {
-        let api: Api<Node> = Api::all(client.clone());
-        let nodes = api.list(&ListParams::default()).await?;
+/// The API server answers for an APIService whose backend (metrics-server)
+/// isn't installed or isn't running.
+fn is_metrics_unavailable(error: &kube::Error) -> bool {
+    matches!(error, kube::Error::Api(response) if matches!(response.code, 404 | 503))
+}
 
-        Ok(nodes
-            .items
-            .iter()
-            .filter_map(|node| {
-                let name = node.metadata.name.clone()?;
-                let status = node
-                    .status
-                    .as_ref()
-                    .and_then(|s| s.conditions.as_ref())
-                    .and_then(|conds| {
-                        conds
-                            .iter()
-                            .find(|c| c.type_ == "Ready")
-                            .map(|c| c.status.clone())
-                    })
-                    .unwrap_or_else(|| "Unknown".to_string());
-                let age = node
-                    .metadata
-                    .creation_timestamp
-                    .as_ref()
-                    .map(|ts| format_age(&ts.0))
-                    .unwrap_or_else(|| "Unknown".to_string());
-
-                Some(ResourceItem {
-                    kind: ResourceKind::Node,
-                    name,
-                    namespace: None,
-                    status,
-                    age,
-                    restart_count: None,
-                    node_name: None,
-                    pod_ip: None,
-                    metadata: serde_json::to_value(node).ok()?,
-                })
-            })
-            .collect())
+/// Whether draining may evict `pod`, following `kubectl drain`'s rules:
+/// `Pending` if so, otherwise why it stays.
+fn drain_state(pod: &Pod, options: &DrainOptions) -> DrainPodState {
+    if pod.annotations().contains_key(MIRROR_POD_ANNOTATION) {
+        return DrainPodState::Skipped("Static pod".to_string());
+    }
+    let controller = pod
+        .owner_references()
+        .iter()
+        .find(|owner| owner.controller == Some(true));
+    if controller.is_some_and(|owner| owner.kind == "DaemonSet") {
+        return DrainPodState::Skipped("DaemonSet pod".to_string());
     }
 
-    pub async fn get_pod_logs(
-        &self,
-        namespace: &str,
-        pod_name: &str,
-        container: Option<&str>,
-    ) -> Result<String> {
-        let client = self.get_client().await?;
-        let api: Api<Pod> = Api::namespaced(client, namespace);
+    let finished = matches!(
+        pod.status.as_ref().and_then(|s| s.phase.as_deref()),
+        Some("Succeeded") | Some("Failed")
+    );
+    if controller.is_none() && !finished && !options.force {
+        return DrainPodState::Failed(
+            "Not managed by a controller; enable Force to evict it".to_string(),
+        );
+    }
+    let uses_empty_dir = pod
+        .spec
+        .as_ref()
+        .and_then(|s| s.volumes.as_ref())
+        .is_some_and(|volumes| volumes.iter().any(|v| v.empty_dir.is_some()));
+    if uses_empty_dir && !finished && !options.delete_emptydir_data {
+        return DrainPodState::Failed(
+            "Uses emptyDir storage; enable Delete emptyDir data to evict it".to_string(),
+        );
+    }
+
+    DrainPodState::Pending
+}
 
-        let mut log_params = LogParams::default();
-        if let Some(container_name) = container {
-            log_params.container = Some(container_name.to_string());
+/// Evicts one pod and waits for it to be gone, reporting each step.
+/// Returns whether the pod left the node.
+async fn evict_pod(
+    api: Api<Pod>,
+    namespace: String,
+    name: String,
+    uid: Option<String>,
+    grace_period: Option<u32>,
+    deadline: tokio::time::Instant,
+    tx: UnboundedSender<DrainEvent>,
+) -> bool {
+    let report = |state: DrainPodState| {
+        let _ = tx.unbounded_send(DrainEvent::Update {
+            namespace: namespace.clone(),
+            name: name.clone(),
+            state,
+        });
+    };
+
+    let params = EvictParams {
+        delete_options: grace_period.map(|secs| DeleteParams {
+            grace_period_seconds: Some(secs),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    loop {
+        match api.evict(&name, &params).await {
+            Ok(_) => break,
+            // Already gone
+            Err(kube::Error::Api(response)) if response.code == 404 => {
+                report(DrainPodState::Evicted);
+                return true;
+            }
+            // Refused because it would violate a PodDisruptionBudget
+            Err(kube::Error::Api(response)) if response.code == 429 => {
+                if tokio::time::Instant::now() >= deadline {
+                    report(DrainPodState::Failed(format!(
+                        "Timed out: {}",
+                        response.message
+                    )));
+                    return false;
+                }
+                report(DrainPodState::Blocked(response.message));
+                tokio::time::sleep(EVICTION_RETRY).await;
+            }
+            Err(e) => {
+                report(DrainPodState::Failed(e.to_string()));
+                return false;
+            }
         }
+    }
 
-        let logs = api.logs(pod_name, &log_params).await?;
-        Ok(logs)
+    report(DrainPodState::Terminating);
+    loop {
+        match api.get_opt(&name).await {
+            // A pod with the same name but another uid is a replacement
+            Ok(Some(pod)) if pod.uid() == uid => {}
+            Ok(_) => break,
+            Err(e) => {
+                report(DrainPodState::Failed(e.to_string()));
+                return false;
+            }
+        }
+        if tokio::time::Instant::now() >= deadline {
+            report(DrainPodState::Failed(
+                "Timed out waiting for the pod to terminate".to_string(),
+            ));
+            return false;
+        }
+        tokio::time::sleep(TERMINATION_POLL).await;
     }
+
+    report(DrainPodState::Evicted);
+    true
+}
+
+/// Container images of a pod template, in spec order.
+fn template_images(template: &serde_json::Value) -> Vec<String> {
+    template
+        .pointer("/spec/containers")
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|c| c.get("image")?.as_str().map(str::to_string))
+        .collect()
+}
+
+/// Kinds whose objects `kind`'s controller creates and owns.
+fn owned_kinds(kind: &ResourceKind) -> Vec<ResourceKind> {
+    match kind {
+        ResourceKind::Deployment => vec![ResourceKind::ReplicaSet],
+        ResourceKind::CronJob => vec![ResourceKind::Job],
+        ResourceKind::ReplicaSet
+        | ResourceKind::StatefulSet
+        | ResourceKind::DaemonSet
+        | ResourceKind::Job => vec![ResourceKind::Pod],
+        _ => Vec::new(),
+    }
+}
+
+/// The exec failed because the command isn't in the image, as opposed to the
+/// shell itself exiting with an error.
+fn is_missing_command(status: &Status) -> bool {
+    let message = status.message.as_deref().unwrap_or_default();
+    message.contains("executable file not found") || message.contains("no such file or directory")
 }
 
-#[allow(dead_code)]
-fn format_age(timestamp: &chrono::DateTime<chrono::Utc>) -> String {
-    let now = chrono::Utc::now();
-    let duration = now.signed_duration_since(*timestamp);
+/// Containers (init and regular) that have started and so have a log to read.
+fn started_containers(pod: &Pod) -> Vec<(String, i32)> {
+    let Some(status) = pod.status.as_ref() else {
+        return Vec::new();
+    };
+
+    status
+        .init_container_statuses
+        .iter()
+        .flatten()
+        .chain(status.container_statuses.iter().flatten())
+        .filter(|s| {
+            s.state
+                .as_ref()
+                .is_some_and(|state| state.running.is_some() || state.terminated.is_some())
+        })
+        .map(|s| (s.name.clone(), s.restart_count))
+        .collect()
+}
+
+async fn follow_container(
+    api: Api<Pod>,
+    pod: String,
+    container: String,
+    params: LogParams,
+    tx: UnboundedSender<LogLine>,
+) {
+    let mut lines = match api.log_stream(&pod, &params).await {
+        Ok(stream) => stream.lines(),
+        Err(e) => {
+            warn!("Failed to follow {}/{}: {}", pod, container, e);
+            return;
+        }
+    };
 
-    if duration.num_days() > 0 {
-        format!("{}d", duration.num_days())
-    } else if duration.num_hours() > 0 {
-        format!("{}h", duration.num_hours())
-    } else if duration.num_minutes() > 0 {
-        format!("{}m", duration.num_minutes())
-    } else {
-        format!("{}s", duration.num_seconds())
+    while let Ok(Some(text)) = lines.try_next().await {
+        let line = LogLine {
+            pod: pod.clone(),
+            container: Some(container.clone()),
+            text,
+        };
+        if tx.unbounded_send(line).is_err() {
+            return;
+        }
     }
 }
+
+fn log_params(options: &LogOptions, follow: bool) -> LogParams {
+    LogParams {
+        follow,
+        container: options.container.clone(),
+        previous: options.previous,
+        tail_lines: options.tail_lines,
+        since_seconds: options.since_seconds,
+        timestamps: options.timestamps,
+        ..LogParams::default()
+    }
+}
+
+/// Namespaced API when a namespace is selected, cluster-wide otherwise.
+fn scoped_api<K>(client: &Client, namespace: Option<&str>) -> Api<K>
+where
+    K: Resource<Scope = NamespaceResourceScope>,
+    <K as Resource>::DynamicType: Default,
+{
+    match namespace {
+        Some(ns) => Api::namespaced(client.clone(), ns),
+        None => Api::all(client.clone()),
+    }
+}
+
+fn dynamic_api(
+    client: &Client,
+    custom: &CustomResourceKind,
+    namespace: Option<&str>,
+) -> Api<DynamicObject> {
+    object_api(client, &ResourceKind::Custom(custom.clone()), namespace)
+}
+
+/// Generic API for one object of any kind; `namespace` is ignored for
+/// cluster-scoped kinds.
+fn object_api(client: &Client, kind: &ResourceKind, namespace: Option<&str>) -> Api<DynamicObject> {
+    let ar = api_resource(kind);
+    match namespace {
+        Some(ns) if kind.is_namespaced() => Api::namespaced_with(client.clone(), ns, &ar),
+        _ => Api::all_with(client.clone(), &ar),
+    }
+}
+
+/// Groups served by Kubernetes itself rather than by CRDs or extension API servers.
+fn is_builtin_group(group: &str) -> bool {
+    group.is_empty()
+        || group.ends_with(".k8s.io")
+        || matches!(
+            group,
+            "apps" | "batch" | "autoscaling" | "policy" | "extensions"
+        )
+}
+
+/// Printer columns per served CRD version, skipping wide-only (priority > 0)
+/// columns and Age, which the table already shows.
+fn crd_printer_columns(
+    crd: &CustomResourceDefinition,
+) -> Vec<((String, String, String), Vec<PrinterColumn>)> {
+    crd.spec
+        .versions
+        .iter()
+        .map(|version| {
+            let columns = version
+                .additional_printer_columns
+                .iter()
+                .flatten()
+                .filter(|col| col.priority.unwrap_or(0) == 0)
+                .filter(|col| col.json_path != ".metadata.creationTimestamp")
+                .map(|col| PrinterColumn {
+                    name: col.name.clone(),
+                    json_path: col.json_path.clone(),
+                    type_: col.type_.clone(),
+                })
+                .collect();
+            (
+                (
+                    crd.spec.group.clone(),
+                    crd.spec.names.plural.clone(),
+                    version.name.clone(),
+                ),
+                columns,
+            )
+        })
+        .collect()
+}
+
+async fn list_items<K>(
+    api: Api<K>,
+    params: &ListParams,
+    convert: impl Fn(&K) -> Option<ResourceItem>,
+) -> Result<Vec<ResourceItem>>
+where
+    K: Resource + Clone + DeserializeOwned + Debug,
+{
+    let list = api.list(params).await?;
+    Ok(list.items.iter().filter_map(|obj| convert(obj)).collect())
+}
@@ -0,0 +1,585 @@
+use k8s_openapi::api::{
+    apps::v1::{DaemonSet, Deployment, ReplicaSet, StatefulSet},
+    batch::v1::{CronJob, Job},
+    core::v1::{
+        ConfigMap, Container, ContainerStatus, Namespace, Node, PersistentVolume,
+        PersistentVolumeClaim, Pod, Secret, Service,
+    },
+    networking::v1::Ingress,
+};
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use kube::api::DynamicObject;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use super::{ContainerInfo, CustomResourceKind, ResourceItem, ResourceKind};
+
+/// Builds a `ResourceItem` with the fields every kind shares.
+fn base_item<T: Serialize>(
+    kind: ResourceKind,
+    meta: &ObjectMeta,
+    status: String,
+    object: &T,
+) -> Option<ResourceItem> {
+    let name = meta.name.clone()?;
+    let age = meta
+        .creation_timestamp
+        .as_ref()
+        .map(|ts| format_age(&ts.0))
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    Some(ResourceItem {
+        kind,
+        name,
+        namespace: meta.namespace.clone(),
+        status,
+        age,
+        restart_count: None,
+        node_name: None,
+        pod_ip: None,
+        columns: Vec::new(),
+        metadata: serde_json::to_value(object).ok()?,
+    })
+}
+
+pub fn namespace_item(ns: &Namespace) -> Option<ResourceItem> {
+    let status = ns
+        .status
+        .as_ref()
+        .and_then(|s| s.phase.clone())
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    let mut item = base_item(ResourceKind::Namespace, &ns.metadata, status, ns)?;
+    item.namespace = None;
+    Some(item)
+}
+
+pub fn pod_item(pod: &Pod) -> Option<ResourceItem> {
+    let status = pod
+        .status
+        .as_ref()
+        .and_then(|s| s.phase.clone())
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    let mut item = base_item(ResourceKind::Pod, &pod.metadata, status, pod)?;
+    item.restart_count = pod
+        .status
+        .as_ref()
+        .and_then(|s| s.container_statuses.as_ref())
+        .map(|statuses| statuses.iter().map(|s| s.restart_count).sum());
+    item.node_name = pod.spec.as_ref().and_then(|s| s.node_name.clone());
+    item.pod_ip = pod.status.as_ref().and_then(|s| s.pod_ip.clone());
+    Some(item)
+}
+
+pub fn pod_containers(pod: &Pod) -> Vec<ContainerInfo> {
+    let Some(spec) = pod.spec.as_ref() else {
+        return Vec::new();
+    };
+    let status = pod.status.as_ref();
+    let init_statuses = status.and_then(|s| s.init_container_statuses.as_deref());
+    let statuses = status.and_then(|s| s.container_statuses.as_deref());
+
+    let info = |container: &Container, statuses: Option<&[ContainerStatus]>, init: bool| {
+        let status = statuses.and_then(|s| s.iter().find(|s| s.name == container.name));
+        ContainerInfo {
+            name: container.name.clone(),
+            image: container.image.clone().unwrap_or_default(),
+            ready: status.is_some_and(|s| s.ready),
+            restart_count: status.map(|s| s.restart_count).unwrap_or(0),
+            init,
+        }
+    };
+
+    spec.init_containers
+        .iter()
+        .flatten()
+        .map(|c| info(c, init_statuses, true))
+        .chain(spec.containers.iter().map(|c| info(c, statuses, false)))
+        .collect()
+}
+
+pub fn deployment_item(deploy: &Deployment) -> Option<ResourceItem> {
+    let status = deploy
+        .status
+        .as_ref()
+        .map(|s| {
+            format!(
+                "{}/{}",
+                s.ready_replicas.unwrap_or(0),
+                s.replicas.unwrap_or(0)
+            )
+        })
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    base_item(ResourceKind::Deployment, &deploy.metadata, status, deploy)
+}
+
+pub fn statefulset_item(ss: &StatefulSet) -> Option<ResourceItem> {
+    let status = ss
+        .status
+        .as_ref()
+        .map(|s| format!("{}/{}", s.ready_replicas.unwrap_or(0), s.replicas))
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    base_item(ResourceKind::StatefulSet, &ss.metadata, status, ss)
+}
+
+pub fn daemonset_item(ds: &DaemonSet) -> Option<ResourceItem> {
+    let status = ds
+        .status
+        .as_ref()
+        .map(|s| format!("{}/{}", s.number_ready, s.desired_number_scheduled))
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    base_item(ResourceKind::DaemonSet, &ds.metadata, status, ds)
+}
+
+pub fn replicaset_item(rs: &ReplicaSet) -> Option<ResourceItem> {
+    let status = rs
+        .status
+        .as_ref()
+        .map(|s| format!("{}/{}", s.ready_replicas.unwrap_or(0), s.replicas))
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    base_item(ResourceKind::ReplicaSet, &rs.metadata, status, rs)
+}
+
+pub fn service_item(svc: &Service) -> Option<ResourceItem> {
+    let status = svc
+        .spec
+        .as_ref()
+        .and_then(|s| s.type_.clone())
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    base_item(ResourceKind::Service, &svc.metadata, status, svc)
+}
+
+pub fn job_item(job: &Job) -> Option<ResourceItem> {
+    let status = job
+        .status
+        .as_ref()
+        .map(|s| format!("{}/{}", s.succeeded.unwrap_or(0), s.active.unwrap_or(0)))
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    base_item(ResourceKind::Job, &job.metadata, status, job)
+}
+
+pub fn cronjob_item(cj: &CronJob) -> Option<ResourceItem> {
+    let status = cj
+        .spec
+        .as_ref()
+        .map(|s| s.schedule.clone())
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    base_item(ResourceKind::CronJob, &cj.metadata, status, cj)
+}
+
+pub fn configmap_item(cm: &ConfigMap) -> Option<ResourceItem> {
+    let status = cm
+        .data
+        .as_ref()
+        .map(|d| format!("{} keys", d.len()))
+        .unwrap_or_else(|| "0 keys".to_string());
+
+    base_item(ResourceKind::ConfigMap, &cm.metadata, status, cm)
+}
+
+pub fn secret_item(secret: &Secret) -> Option<ResourceItem> {
+    let status = secret.type_.clone().unwrap_or_else(|| "Opaque".to_string());
+
+    base_item(ResourceKind::Secret, &secret.metadata, status, secret)
+}
+
+pub fn ingress_item(ing: &Ingress) -> Option<ResourceItem> {
+    let status = ing
+        .spec
+        .as_ref()
+        .and_then(|s| s.ingress_class_name.clone())
+        .unwrap_or_else(|| "default".to_string());
+
+    base_item(ResourceKind::Ingress, &ing.metadata, status, ing)
+}
+
+pub fn node_item(node: &Node) -> Option<ResourceItem> {
+    let ready = node
+        .status
+        .as_ref()
+        .and_then(|s| s.conditions.as_ref())
+        .and_then(|conds| conds.iter().find(|c| c.type_ == "Ready"))
+        .map(|c| c.status.as_str());
+    let mut status = match ready {
+        Some("True") => "Ready",
+        Some("False") => "NotReady",
+        _ => "Unknown",
+    }
+    .to_string();
+    // Same suffix `kubectl get nodes` shows for cordoned nodes
+    if node
+        .spec
+        .as_ref()
+        .and_then(|s| s.unschedulable)
+        .unwrap_or(false)
+    {
+        status.push_str(",SchedulingDisabled");
+    }
+
+    let mut item = base_item(ResourceKind::Node, &node.metadata, status, node)?;
+    item.namespace = None;
+    Some(item)
+}
+
+pub fn persistent_volume_item(pv: &PersistentVolume) -> Option<ResourceItem> {
+    let status = pv
+        .status
+        .as_ref()
+        .and_then(|s| s.phase.clone())
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    let spec = pv.spec.as_ref();
+    let claim = spec
+        .and_then(|s| s.claim_ref.as_ref())
+        .and_then(|c| {
+            let name = c.name.as_ref()?;
+            Some(match &c.namespace {
+                Some(ns) => format!("{}/{}", ns, name),
+                None => name.clone(),
+            })
+        })
+        .unwrap_or_else(|| "-".to_string());
+
+    let mut item = base_item(ResourceKind::PersistentVolume, &pv.metadata, status, pv)?;
+    item.namespace = None;
+    item.columns = vec![
+        format_storage(spec.and_then(|s| s.capacity.as_ref())),
+        format_access_modes(spec.and_then(|s| s.access_modes.as_ref())),
+        spec.and_then(|s| s.storage_class_name.clone())
+            .unwrap_or_else(|| "-".to_string()),
+        claim,
+    ];
+    Some(item)
+}
+
+pub fn persistent_volume_claim_item(pvc: &PersistentVolumeClaim) -> Option<ResourceItem> {
+    let status = pvc
+        .status
+        .as_ref()
+        .and_then(|s| s.phase.clone())
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    let spec = pvc.spec.as_ref();
+    // Bound claims report actual capacity; pending ones only have the request
+    let capacity = pvc
+        .status
+        .as_ref()
+        .and_then(|s| s.capacity.as_ref())
+        .or_else(|| {
+            spec.and_then(|s| s.resources.as_ref())
+                .and_then(|r| r.requests.as_ref())
+        });
+    let access_modes = pvc
+        .status
+        .as_ref()
+        .and_then(|s| s.access_modes.as_ref())
+        .or_else(|| spec.and_then(|s| s.access_modes.as_ref()));
+
+    let mut item = base_item(
+        ResourceKind::PersistentVolumeClaim,
+        &pvc.metadata,
+        status,
+        pvc,
+    )?;
+    item.columns = vec![
+        format_storage(capacity),
+        format_access_modes(access_modes),
+        spec.and_then(|s| s.storage_class_name.clone())
+            .unwrap_or_else(|| "-".to_string()),
+        spec.and_then(|s| s.volume_name.clone())
+            .unwrap_or_else(|| "-".to_string()),
+    ];
+    Some(item)
+}
+
+pub fn dynamic_item(custom: &CustomResourceKind, obj: &DynamicObject) -> Option<ResourceItem> {
+    let value = serde_json::to_value(obj).ok()?;
+    let status = dynamic_status(&value);
+    let columns = custom
+        .printer_columns
+        .iter()
+        .map(|col| format_printer_value(&value, col.json_path.as_str(), col.type_.as_str()))
+        .collect();
+
+    let mut item = base_item(
+        ResourceKind::Custom(custom.clone()),
+        &obj.metadata,
+        status,
+        obj,
+    )?;
+    item.columns = columns;
+    Some(item)
+}
+
+/// Best-effort status for arbitrary objects: `status.phase`, then the Ready condition.
+fn dynamic_status(value: &Value) -> String {
+    let status = value.get("status");
+
+    if let Some(phase) = status.and_then(|s| s.get("phase")).and_then(|p| p.as_str()) {
+        return phase.to_string();
+    }
+
+    let ready = status
+        .and_then(|s| s.get("conditions"))
+        .and_then(|c| c.as_array())
+        .and_then(|conds| {
+            conds
+                .iter()
+                .find(|c| c.get("type").and_then(|t| t.as_str()) == Some("Ready"))
+        })
+        .and_then(|c| c.get("status"))
+        .and_then(|s| s.as_str());
+
+    match ready {
+        Some("True") => "Ready".to_string(),
+        Some("False") => "NotReady".to_string(),
+        Some(_) => "Unknown".to_string(),
+        None => "-".to_string(),
+    }
+}
+
+fn format_printer_value(value: &Value, json_path: &str, type_: &str) -> String {
+    let values = eval_json_path(value, json_path);
+    if values.is_empty() {
+        return "-".to_string();
+    }
+
+    values
+        .iter()
+        .map(|v| match v {
+            Value::String(s) if type_ == "date" => chrono::DateTime::parse_from_rfc3339(s)
+                .map(|ts| format_age(&ts.with_timezone(&chrono::Utc)))
+                .unwrap_or_else(|_| s.clone()),
+            Value::String(s) => s.clone(),
+            Value::Null => "-".to_string(),
+            other => other.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Evaluates the subset of JSONPath used by `additionalPrinterColumns`:
+/// `.a.b`, `[0]`, `[*]` and `[?(@.type=="Ready")]` filters.
+fn eval_json_path<'a>(value: &'a Value, path: &str) -> Vec<&'a Value> {
+    let mut current = vec![value];
+    let mut rest = path.trim().trim_start_matches('{').trim_end_matches('}');
+    rest = rest.trim_start_matches('$');
+
+    while !rest.is_empty() {
+        if let Some(r) = rest.strip_prefix('.') {
+            let end = r.find(&['.', '['][..]).unwrap_or(r.len());
+            let field = &r[..end];
+            current = current.into_iter().filter_map(|v| v.get(field)).collect();
+            rest = &r[end..];
+        } else if let Some(r) = rest.strip_prefix('[') {
+            let end = if r.starts_with("?(") {
+                r.find(")]").map(|i| i + 1)
+            } else {
+                r.find(']')
+            };
+            let Some(end) = end else {
+                return Vec::new();
+            };
+            current = apply_selector(current, &r[..end]);
+            rest = &r[end + 1..];
+        } else {
+            return Vec::new();
+        }
+    }
+
+    current
+}
+
+fn apply_selector<'a>(values: Vec<&'a Value>, selector: &str) -> Vec<&'a Value> {
+    let elements = values
+        .into_iter()
+        .filter_map(|v| v.as_array())
+        .flat_map(|arr| arr.iter());
+
+    if selector == "*" {
+        return elements.collect();
+    }
+
+    if let Ok(index) = selector.parse::<usize>() {
+        return elements.skip(index).take(1).collect();
+    }
+
+    // Filter expression: ?(@.path==value) or ?(@.path!=value)
+    let Some(expr) = selector
+        .strip_prefix("?(")
+        .and_then(|e| e.strip_suffix(')'))
+    else {
+        return Vec::new();
+    };
+    let (lhs, rhs, negate) = match expr.split_once("!=") {
+        Some((l, r)) => (l, r, true),
+        None => match expr.split_once("==") {
+            Some((l, r)) => (l, r, false),
+            None => return Vec::new(),
+        },
+    };
+    let lhs = lhs.trim().trim_start_matches('@');
+    let expected = rhs.trim().trim_matches(|c| c == '"' || c == '\'');
+
+    elements
+        .filter(|el| {
+            let actual = eval_json_path(el, lhs)
+                .first()
+                .map(|v| match v {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+                .unwrap_or_default();
+            (actual == expected) != negate
+        })
+        .collect()
+}
+
+fn format_storage(resources: Option<&BTreeMap<String, Quantity>>) -> String {
+    resources
+        .and_then(|r| r.get("storage"))
+        .map(|q| q.0.clone())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Abbreviates access modes the way kubectl does (RWO, ROX, RWX, RWOP).
+fn format_access_modes(modes: Option<&Vec<String>>) -> String {
+    let modes: Vec<&str> = modes
+        .into_iter()
+        .flatten()
+        .map(|m| match m.as_str() {
+            "ReadWriteOnce" => "RWO",
+            "ReadOnlyMany" => "ROX",
+            "ReadWriteMany" => "RWX",
+            "ReadWriteOncePod" => "RWOP",
+            other => other,
+        })
+        .collect();
+
+    if modes.is_empty() {
+        "-".to_string()
+    } else {
+        modes.join(",")
+    }
+}
+
+pub fn format_age(timestamp: &chrono::DateTime<chrono::Utc>) -> String {
+    let now = chrono::Utc::now();
+    let duration = now.signed_duration_since(*timestamp);
+
+    if duration.num_days() > 0 {
+        format!("{}d", duration.num_days())
+    } else if duration.num_hours() > 0 {
+        format!("{}h", duration.num_hours())
+    } else if duration.num_minutes() > 0 {
+        format!("{}m", duration.num_minutes())
+    } else {
+        format!("{}s", duration.num_seconds())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn conditions() -> Value {
+        json!({
+            "spec": { "replicas": 3 },
+            "status": {
+                "conditions": [
+                    { "type": "Ready", "status": "True" },
+                    { "type": "Synced", "status": "False" }
+                ]
+            }
+        })
+    }
+
+    #[test]
+    fn eval_json_path_follows_fields() {
+        let value = conditions();
+        assert_eq!(eval_json_path(&value, ".spec.replicas"), vec![&json!(3)]);
+        assert_eq!(eval_json_path(&value, "{$.spec.replicas}"), vec![&json!(3)]);
+        assert!(eval_json_path(&value, ".spec.missing").is_empty());
+    }
+
+    #[test]
+    fn eval_json_path_indexes_and_wildcards() {
+        let value = conditions();
+        assert_eq!(
+            eval_json_path(&value, ".status.conditions[1].type"),
+            vec![&json!("Synced")]
+        );
+        assert_eq!(
+            eval_json_path(&value, ".status.conditions[*].status"),
+            vec![&json!("True"), &json!("False")]
+        );
+        assert!(eval_json_path(&value, ".status.conditions[5].type").is_empty());
+    }
+
+    #[test]
+    fn eval_json_path_filters() {
+        let value = conditions();
+        assert_eq!(
+            eval_json_path(&value, ".status.conditions[?(@.type==\"Ready\")].status"),
+            vec![&json!("True")]
+        );
+        assert_eq!(
+            eval_json_path(&value, ".status.conditions[?(@.type!='Ready')].type"),
+            vec![&json!("Synced")]
+        );
+    }
+
+    #[test]
+    fn pod_containers_lists_init_containers_first() {
+        let pod: Pod = serde_json::from_value(json!({
+            "metadata": { "name": "web" },
+            "spec": {
+                "initContainers": [{ "name": "migrate", "image": "app:1" }],
+                "containers": [
+                    { "name": "app", "image": "app:1" },
+                    { "name": "proxy", "image": "envoy" }
+                ]
+            },
+            "status": {
+                "initContainerStatuses": [{
+                    "name": "migrate", "image": "app:1", "imageID": "",
+                    "ready": false, "restartCount": 0
+                }],
+                "containerStatuses": [{
+                    "name": "app", "image": "app:1", "imageID": "",
+                    "ready": true, "restartCount": 2
+                }]
+            }
+        }))
+        .unwrap();
+
+        let containers = pod_containers(&pod);
+        let names: Vec<_> = containers.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["migrate", "app", "proxy"]);
+        assert!(containers[0].init);
+        assert!(!containers[1].init);
+        assert!(containers[1].ready);
+        assert_eq!(containers[1].restart_count, 2);
+        // No status yet
+        assert!(!containers[2].ready);
+        assert_eq!(containers[2].restart_count, 0);
+    }
+
+    #[test]
+    fn eval_json_path_rejects_malformed_paths() {
+        let value = conditions();
+        assert!(eval_json_path(&value, "spec").is_empty());
+        assert!(eval_json_path(&value, ".status.conditions[0").is_empty());
+    }
+}
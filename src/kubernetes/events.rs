@@ -0,0 +1,228 @@
+use chrono::{DateTime, Utc};
+use k8s_openapi::api::events::v1::Event;
+use kube::ResourceExt;
+use std::collections::HashMap;
+
+use super::convert::format_age;
+use super::watch::ResourceEvent;
+use super::ResourceItem;
+
+/// The object an event is about, as the event names it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EventObject {
+    pub kind: String,
+    pub api_version: String,
+    pub namespace: Option<String>,
+    pub name: String,
+}
+
+impl EventObject {
+    pub fn matches(&self, item: &ResourceItem) -> bool {
+        self.kind == item.kind.kind_name()
+            && self.name == item.name
+            && (self.namespace.is_none() || self.namespace == item.namespace)
+    }
+}
+
+/// An `events.k8s.io/v1` Event, reduced to what the feeds show.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClusterEvent {
+    pub uid: String,
+    pub namespace: Option<String>,
+    pub warning: bool,
+    pub reason: String,
+    pub note: String,
+    pub regarding: Option<EventObject>,
+    /// Occurrences the reporter folded into this event
+    pub count: i32,
+    pub last_seen: Option<DateTime<Utc>>,
+}
+
+pub fn event_item(event: &Event) -> Option<ClusterEvent> {
+    let series = event.series.as_ref();
+    let last_seen = series
+        .map(|s| s.last_observed_time.0)
+        .or_else(|| event.event_time.as_ref().map(|t| t.0))
+        .or_else(|| event.deprecated_last_timestamp.as_ref().map(|t| t.0))
+        .or_else(|| event.metadata.creation_timestamp.as_ref().map(|t| t.0));
+
+    Some(ClusterEvent {
+        uid: event.uid()?,
+        namespace: event.namespace(),
+        warning: event.type_.as_deref() == Some("Warning"),
+        reason: event.reason.clone().unwrap_or_default(),
+        note: event.note.clone().unwrap_or_default(),
+        regarding: event.regarding.as_ref().and_then(|r| {
+            Some(EventObject {
+                kind: r.kind.clone()?,
+                api_version: r.api_version.clone().unwrap_or_default(),
+                namespace: r.namespace.clone(),
+                name: r.name.clone()?,
+            })
+        }),
+        count: series
+            .map(|s| s.count)
+            .or(event.deprecated_count)
+            .unwrap_or(1),
+        last_seen,
+    })
+}
+
+/// Which event types a feed shows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EventTypeFilter {
+    #[default]
+    All,
+    Normal,
+    Warning,
+}
+
+impl EventTypeFilter {
+    pub fn all() -> [Self; 3] {
+        [Self::All, Self::Normal, Self::Warning]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::All => "All",
+            Self::Normal => "Normal",
+            Self::Warning => "Warning",
+        }
+    }
+
+    fn accepts(&self, event: &ClusterEvent) -> bool {
+        match self {
+            Self::All => true,
+            Self::Normal => !event.warning,
+            Self::Warning => event.warning,
+        }
+    }
+}
+
+/// Events with the same object, type, reason and note, shown as one row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventGroup {
+    pub namespace: Option<String>,
+    pub warning: bool,
+    pub reason: String,
+    pub note: String,
+    pub regarding: Option<EventObject>,
+    pub count: i32,
+    pub last_seen: Option<DateTime<Utc>>,
+}
+
+impl EventGroup {
+    /// Time since the group last fired
+    pub fn age(&self) -> String {
+        self.last_seen
+            .as_ref()
+            .map(format_age)
+            .unwrap_or_else(|| "-".to_string())
+    }
+}
+
+/// Events of the cluster, fed by a watch.
+#[derive(Default)]
+pub struct EventStore {
+    events: HashMap<String, ClusterEvent>,
+    /// Last watch failure, cleared by the next update
+    pub error: Option<String>,
+    /// Whether the initial list has arrived
+    pub synced: bool,
+}
+
+impl EventStore {
+    /// Applies a watch event. Returns true if the stored events changed.
+    pub fn apply(&mut self, event: ResourceEvent<ClusterEvent>) -> bool {
+        match event {
+            ResourceEvent::Restarted(events) => {
+                self.events = events.into_iter().map(|e| (e.uid.clone(), e)).collect();
+                self.synced = true;
+                self.error = None;
+            }
+            ResourceEvent::Applied(event) => {
+                self.events.insert(event.uid.clone(), event);
+                self.error = None;
+            }
+            ResourceEvent::Deleted(event) => {
+                self.events.remove(&event.uid);
+            }
+            ResourceEvent::Error(message) => self.error = Some(message),
+        }
+        true
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+        self.error = None;
+        self.synced = false;
+    }
+
+    /// Normal and Warning totals over `namespaces` (empty = all).
+    pub fn counts(&self, namespaces: &[String]) -> (i32, i32) {
+        self.events
+            .values()
+            .filter(|e| in_namespaces(e, namespaces))
+            .fold((0, 0), |(normal, warning), e| {
+                if e.warning {
+                    (normal, warning + e.count)
+                } else {
+                    (normal + e.count, warning)
+                }
+            })
+    }
+
+    /// Grouped events in `namespaces` (empty = all), newest first.
+    pub fn feed(&self, filter: EventTypeFilter, namespaces: &[String]) -> Vec<EventGroup> {
+        group(
+            self.events
+                .values()
+                .filter(|e| filter.accepts(e) && in_namespaces(e, namespaces)),
+        )
+    }
+
+    /// Grouped events about `item`, newest first.
+    pub fn for_object(&self, item: &ResourceItem) -> Vec<EventGroup> {
+        group(
+            self.events
+                .values()
+                .filter(|e| e.regarding.as_ref().is_some_and(|r| r.matches(item))),
+        )
+    }
+}
+
+fn in_namespaces(event: &ClusterEvent, namespaces: &[String]) -> bool {
+    namespaces.is_empty()
+        || event
+            .namespace
+            .as_ref()
+            .is_some_and(|ns| namespaces.contains(ns))
+}
+
+fn group<'a>(events: impl Iterator<Item = &'a ClusterEvent>) -> Vec<EventGroup> {
+    let mut groups: HashMap<_, EventGroup> = HashMap::new();
+    for event in events {
+        let key = (
+            event.namespace.clone(),
+            event.warning,
+            event.reason.clone(),
+            event.note.clone(),
+            event.regarding.clone(),
+        );
+        let group = groups.entry(key).or_insert_with(|| EventGroup {
+            namespace: event.namespace.clone(),
+            warning: event.warning,
+            reason: event.reason.clone(),
+            note: event.note.clone(),
+            regarding: event.regarding.clone(),
+            count: 0,
+            last_seen: None,
+        });
+        group.count += event.count;
+        group.last_seen = group.last_seen.max(event.last_seen);
+    }
+
+    let mut groups: Vec<EventGroup> = groups.into_values().collect();
+    groups.sort_by(|a, b| b.last_seen.cmp(&a.last_seen));
+    groups
+}
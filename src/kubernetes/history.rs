@@ -0,0 +1,313 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fs;
+use std::path::Path;
+
+use super::metrics::{MetricsSnapshot, Usage};
+
+/// The longest window charts offer; older samples are dropped.
+const RETENTION_SECS: i64 = 6 * 60 * 60;
+
+/// What a usage series is recorded for.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SeriesKey {
+    /// Sum over all nodes
+    Cluster,
+    Node(String),
+    /// Sum over the namespace's pods
+    Namespace(String),
+    Pod {
+        namespace: String,
+        name: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Sample {
+    /// Unix seconds of the metrics-server scrape
+    pub timestamp: i64,
+    pub usage: Usage,
+}
+
+/// How far back a usage chart reaches.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HistoryWindow {
+    #[default]
+    FifteenMinutes,
+    OneHour,
+    SixHours,
+}
+
+impl HistoryWindow {
+    pub fn all() -> [Self; 3] {
+        [Self::FifteenMinutes, Self::OneHour, Self::SixHours]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::FifteenMinutes => "15m",
+            Self::OneHour => "1h",
+            Self::SixHours => "6h",
+        }
+    }
+
+    pub fn seconds(&self) -> i64 {
+        match self {
+            Self::FifteenMinutes => 15 * 60,
+            Self::OneHour => 60 * 60,
+            Self::SixHours => RETENTION_SECS,
+        }
+    }
+
+    /// Unix seconds of the window's left edge
+    pub fn start(&self) -> i64 {
+        Utc::now().timestamp() - self.seconds()
+    }
+}
+
+/// Usage samples per node, namespace and pod, kept for `RETENTION_SECS`.
+/// Each series is a ring buffer: new samples push out those past retention.
+#[derive(Debug, Clone, Default)]
+pub struct MetricsHistory {
+    series: HashMap<SeriesKey, VecDeque<Sample>>,
+}
+
+impl MetricsHistory {
+    pub fn record(&mut self, snapshot: &MetricsSnapshot) {
+        let timestamp = snapshot.taken_at.timestamp();
+
+        let mut namespaces: HashMap<&str, Usage> = HashMap::new();
+        for pod in snapshot.pods.values() {
+            self.push(
+                SeriesKey::Pod {
+                    namespace: pod.namespace.clone(),
+                    name: pod.name.clone(),
+                },
+                timestamp,
+                pod.usage,
+            );
+            let total = namespaces.entry(&pod.namespace).or_default();
+            *total = *total + pod.usage;
+        }
+        for (namespace, usage) in namespaces {
+            self.push(
+                SeriesKey::Namespace(namespace.to_string()),
+                timestamp,
+                usage,
+            );
+        }
+
+        for node in snapshot.nodes.values() {
+            self.push(SeriesKey::Node(node.name.clone()), timestamp, node.usage);
+        }
+        if !snapshot.nodes.is_empty() {
+            let cluster = snapshot.nodes.values().map(|n| n.usage).sum();
+            self.push(SeriesKey::Cluster, timestamp, cluster);
+        }
+
+        self.prune(timestamp - RETENTION_SECS);
+    }
+
+    fn push(&mut self, key: SeriesKey, timestamp: i64, usage: Usage) {
+        self.series
+            .entry(key)
+            .or_default()
+            .push_back(Sample { timestamp, usage });
+    }
+
+    /// Drops samples before `oldest`, and series left empty (deleted pods).
+    fn prune(&mut self, oldest: i64) {
+        self.series.retain(|_, samples| {
+            while samples.front().is_some_and(|s| s.timestamp < oldest) {
+                samples.pop_front();
+            }
+            !samples.is_empty()
+        });
+    }
+
+    /// Samples of `key` within `window`, oldest first.
+    pub fn samples(&self, key: &SeriesKey, window: HistoryWindow) -> Vec<Sample> {
+        let start = window.start();
+        self.series
+            .get(key)
+            .into_iter()
+            .flatten()
+            .filter(|s| s.timestamp >= start)
+            .copied()
+            .collect()
+    }
+
+    /// Per-scrape sums of `keys` within `window`, oldest first; e.g. the
+    /// namespaces a filter selects.
+    pub fn combined(&self, keys: &[SeriesKey], window: HistoryWindow) -> Vec<Sample> {
+        let mut sums: BTreeMap<i64, Usage> = BTreeMap::new();
+        for key in keys {
+            for sample in self.samples(key, window) {
+                let total = sums.entry(sample.timestamp).or_default();
+                *total = *total + sample.usage;
+            }
+        }
+        sums.into_iter()
+            .map(|(timestamp, usage)| Sample { timestamp, usage })
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.series.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.series.clear();
+    }
+
+    /// Puts `older` samples, e.g. loaded from disk, before those recorded since.
+    pub fn merge(&mut self, older: MetricsHistory) {
+        for (key, samples) in older.series {
+            let series = self.series.entry(key).or_default();
+            let first = series.front().map(|s| s.timestamp);
+            for sample in samples.into_iter().rev() {
+                if first.map_or(true, |first| sample.timestamp < first) {
+                    series.push_front(sample);
+                }
+            }
+        }
+        self.prune(Utc::now().timestamp() - RETENTION_SECS);
+    }
+
+    /// Reads history saved by `save`; a missing file is an empty history.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        // JSON objects only take string keys, so series are stored as pairs
+        let series: Vec<(SeriesKey, VecDeque<Sample>)> = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+        let mut history = Self {
+            series: series.into_iter().collect(),
+        };
+        history.prune(Utc::now().timestamp() - RETENTION_SECS);
+        Ok(history)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let series: Vec<(&SeriesKey, &VecDeque<Sample>)> = self.series.iter().collect();
+        let json = serde_json::to_string(&series).context("Failed to serialize metrics history")?;
+        fs::write(path, json).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kubernetes::metrics::{NodeMetrics, PodMetrics};
+
+    fn snapshot(
+        taken_at: i64,
+        pods: &[(&str, &str, f64)],
+        nodes: &[(&str, f64)],
+    ) -> MetricsSnapshot {
+        let usage = |cpu: f64| Usage { cpu, memory: 0.0 };
+        let mut snapshot = MetricsSnapshot::new(
+            pods.iter()
+                .map(|(namespace, name, cpu)| PodMetrics {
+                    namespace: namespace.to_string(),
+                    name: name.to_string(),
+                    timestamp: None,
+                    usage: usage(*cpu),
+                    containers: Vec::new(),
+                })
+                .collect(),
+            nodes
+                .iter()
+                .map(|(name, cpu)| NodeMetrics {
+                    name: name.to_string(),
+                    timestamp: None,
+                    usage: usage(*cpu),
+                })
+                .collect(),
+        );
+        snapshot.taken_at = chrono::DateTime::from_timestamp(taken_at, 0).unwrap();
+        snapshot
+    }
+
+    fn cpu(samples: &[Sample]) -> Vec<f64> {
+        samples.iter().map(|s| s.usage.cpu).collect()
+    }
+
+    #[test]
+    fn record_sums_namespaces_and_nodes() {
+        let now = Utc::now().timestamp();
+        let mut history = MetricsHistory::default();
+        history.record(&snapshot(
+            now,
+            &[
+                ("default", "a", 0.5),
+                ("default", "b", 0.25),
+                ("kube-system", "dns", 0.1),
+            ],
+            &[("node-1", 1.0), ("node-2", 2.0)],
+        ));
+
+        let window = HistoryWindow::FifteenMinutes;
+        let namespace = SeriesKey::Namespace("default".to_string());
+        assert_eq!(cpu(&history.samples(&namespace, window)), vec![0.75]);
+        assert_eq!(
+            cpu(&history.samples(&SeriesKey::Cluster, window)),
+            vec![3.0]
+        );
+
+        let both = [namespace, SeriesKey::Namespace("kube-system".to_string())];
+        assert_eq!(cpu(&history.combined(&both, window)), vec![0.85]);
+    }
+
+    #[test]
+    fn record_drops_samples_past_retention() {
+        let now = Utc::now().timestamp();
+        let mut history = MetricsHistory::default();
+        history.record(&snapshot(
+            now - RETENTION_SECS - 60,
+            &[("default", "gone", 1.0)],
+            &[],
+        ));
+        history.record(&snapshot(now, &[("default", "web", 2.0)], &[]));
+
+        let gone = SeriesKey::Pod {
+            namespace: "default".to_string(),
+            name: "gone".to_string(),
+        };
+        assert!(history.samples(&gone, HistoryWindow::SixHours).is_empty());
+        let namespace = SeriesKey::Namespace("default".to_string());
+        assert_eq!(
+            cpu(&history.samples(&namespace, HistoryWindow::SixHours)),
+            vec![2.0]
+        );
+    }
+
+    #[test]
+    fn merge_puts_older_samples_first() {
+        let now = Utc::now().timestamp();
+        let mut older = MetricsHistory::default();
+        older.record(&snapshot(now - 120, &[], &[("node-1", 1.0)]));
+        older.record(&snapshot(now - 60, &[], &[("node-1", 2.0)]));
+        let mut history = MetricsHistory::default();
+        history.record(&snapshot(now - 60, &[], &[("node-1", 5.0)]));
+        history.record(&snapshot(now, &[], &[("node-1", 3.0)]));
+
+        history.merge(older);
+        let node = SeriesKey::Node("node-1".to_string());
+        // The overlapping scrape keeps the copy recorded since connecting
+        assert_eq!(
+            cpu(&history.samples(&node, HistoryWindow::FifteenMinutes)),
+            vec![1.0, 5.0, 3.0]
+        );
+    }
+}
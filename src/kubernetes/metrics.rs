@@ -0,0 +1,242 @@
+use chrono::{DateTime, Utc};
+use kube::api::{ApiResource, DynamicObject};
+use kube::ResourceExt;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use super::stats::{parse_quantity, pod_total, quantity_at};
+use super::{ResourceItem, ResourceKind};
+
+/// `metrics.k8s.io` is served by metrics-server, outside k8s-openapi.
+const METRICS_GROUP: &str = "metrics.k8s.io";
+const METRICS_VERSION: &str = "v1beta1";
+
+/// CPU in cores and memory in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Usage {
+    pub cpu: f64,
+    pub memory: f64,
+}
+
+impl Usage {
+    fn from_quantities(quantities: &BTreeMap<String, String>) -> Self {
+        let value = |key: &str| {
+            quantities
+                .get(key)
+                .and_then(|q| parse_quantity(q))
+                .unwrap_or(0.0)
+        };
+        Self {
+            cpu: value("cpu"),
+            memory: value("memory"),
+        }
+    }
+}
+
+impl std::ops::Add for Usage {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            cpu: self.cpu + other.cpu,
+            memory: self.memory + other.memory,
+        }
+    }
+}
+
+impl std::iter::Sum for Usage {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |total, usage| total + usage)
+    }
+}
+
+/// The fields of a PodMetrics object besides its metadata.
+#[derive(Debug, Deserialize)]
+struct PodMetricsData {
+    timestamp: Option<DateTime<Utc>>,
+    #[serde(default)]
+    containers: Vec<ContainerMetricsData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContainerMetricsData {
+    name: String,
+    #[serde(default)]
+    usage: BTreeMap<String, String>,
+}
+
+/// The fields of a NodeMetrics object besides its metadata.
+#[derive(Debug, Deserialize)]
+struct NodeMetricsData {
+    timestamp: Option<DateTime<Utc>>,
+    #[serde(default)]
+    usage: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PodMetrics {
+    pub namespace: String,
+    pub name: String,
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Sum over the containers
+    pub usage: Usage,
+    pub containers: Vec<(String, Usage)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeMetrics {
+    pub name: String,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub usage: Usage,
+}
+
+pub(super) fn pod_metrics_resource() -> ApiResource {
+    metrics_resource("PodMetrics", "pods")
+}
+
+pub(super) fn node_metrics_resource() -> ApiResource {
+    metrics_resource("NodeMetrics", "nodes")
+}
+
+fn metrics_resource(kind: &str, plural: &str) -> ApiResource {
+    ApiResource {
+        group: METRICS_GROUP.to_string(),
+        version: METRICS_VERSION.to_string(),
+        api_version: format!("{}/{}", METRICS_GROUP, METRICS_VERSION),
+        kind: kind.to_string(),
+        plural: plural.to_string(),
+    }
+}
+
+pub(super) fn pod_metrics(object: &DynamicObject) -> Option<PodMetrics> {
+    let data: PodMetricsData = serde_json::from_value(object.data.clone()).ok()?;
+    let containers: Vec<(String, Usage)> = data
+        .containers
+        .into_iter()
+        .map(|c| (c.name, Usage::from_quantities(&c.usage)))
+        .collect();
+    Some(PodMetrics {
+        namespace: object.namespace()?,
+        name: object.name_any(),
+        timestamp: data.timestamp,
+        usage: containers.iter().map(|(_, usage)| *usage).sum(),
+        containers,
+    })
+}
+
+pub(super) fn node_metrics(object: &DynamicObject) -> Option<NodeMetrics> {
+    let data: NodeMetricsData = serde_json::from_value(object.data.clone()).ok()?;
+    Some(NodeMetrics {
+        name: object.name_any(),
+        timestamp: data.timestamp,
+        usage: Usage::from_quantities(&data.usage),
+    })
+}
+
+/// Latest usage of every pod and node, as one metrics-server scrape.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetricsSnapshot {
+    pub pods: HashMap<(String, String), PodMetrics>,
+    pub nodes: HashMap<String, NodeMetrics>,
+    pub taken_at: DateTime<Utc>,
+}
+
+impl MetricsSnapshot {
+    pub fn new(pods: Vec<PodMetrics>, nodes: Vec<NodeMetrics>) -> Self {
+        Self {
+            pods: pods
+                .into_iter()
+                .map(|p| ((p.namespace.clone(), p.name.clone()), p))
+                .collect(),
+            nodes: nodes.into_iter().map(|n| (n.name.clone(), n)).collect(),
+            taken_at: Utc::now(),
+        }
+    }
+
+    /// Usage of a listed pod or node.
+    pub fn usage(&self, item: &ResourceItem) -> Option<Usage> {
+        match item.kind {
+            ResourceKind::Pod => self
+                .pods
+                .get(&(item.namespace.clone()?, item.name.clone()))
+                .map(|p| p.usage),
+            ResourceKind::Node => self.nodes.get(&item.name).map(|n| n.usage),
+            _ => None,
+        }
+    }
+}
+
+/// What a pod's or node's usage is measured against: the pod's requests, or
+/// the node's allocatable capacity.
+pub fn usage_reference(item: &ResourceItem) -> Option<Usage> {
+    match item.kind {
+        ResourceKind::Pod => {
+            let spec = item.metadata.get("spec")?;
+            Some(Usage {
+                cpu: pod_total(spec, "requests", "cpu"),
+                memory: pod_total(spec, "requests", "memory"),
+            })
+        }
+        ResourceKind::Node => {
+            let allocatable = item.metadata.pointer("/status/allocatable");
+            Some(Usage {
+                cpu: quantity_at(allocatable, "cpu"),
+                memory: quantity_at(allocatable, "memory"),
+            })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn pod_metrics_sums_containers() {
+        let object: DynamicObject = serde_json::from_value(json!({
+            "apiVersion": "metrics.k8s.io/v1beta1",
+            "kind": "PodMetrics",
+            "metadata": { "name": "web-0", "namespace": "default" },
+            "timestamp": "2024-05-01T12:00:00Z",
+            "window": "15s",
+            "containers": [
+                { "name": "app", "usage": { "cpu": "150m", "memory": "64Mi" } },
+                { "name": "proxy", "usage": { "cpu": "50m", "memory": "16Mi" } }
+            ]
+        }))
+        .unwrap();
+
+        let metrics = pod_metrics(&object).unwrap();
+        assert_eq!(metrics.namespace, "default");
+        assert_eq!(metrics.containers.len(), 2);
+        assert!((metrics.usage.cpu - 0.2).abs() < 1e-9);
+        assert_eq!(metrics.usage.memory, 80.0 * 1024.0 * 1024.0);
+    }
+
+    #[test]
+    fn snapshot_looks_up_pods_by_namespace() {
+        let pod = |namespace: &str, cpu: f64| PodMetrics {
+            namespace: namespace.to_string(),
+            name: "web".to_string(),
+            timestamp: None,
+            usage: Usage { cpu, memory: 0.0 },
+            containers: Vec::new(),
+        };
+        let snapshot = MetricsSnapshot::new(vec![pod("default", 1.0), pod("staging", 2.0)], vec![]);
+        let item = ResourceItem {
+            kind: ResourceKind::Pod,
+            name: "web".to_string(),
+            namespace: Some("staging".to_string()),
+            status: String::new(),
+            age: String::new(),
+            restart_count: None,
+            node_name: None,
+            pod_ip: None,
+            columns: Vec::new(),
+            metadata: json!({}),
+        };
+        assert_eq!(snapshot.usage(&item).map(|u| u.cpu), Some(2.0));
+    }
+}
@@ -1,11 +1,37 @@
+mod apply;
+mod cache;
 mod client;
+mod convert;
+mod events;
+mod history;
+mod metrics;
+mod port_forward;
+mod prometheus;
 mod resources;
+mod stats;
+mod watch;
 
+pub use apply::{FieldError, SaveError, SaveMode};
+pub use cache::{CacheKey, ResourceCache};
 pub use client::KubeClient;
+pub use events::{ClusterEvent, EventGroup, EventObject, EventStore, EventTypeFilter};
+pub use history::{HistoryWindow, MetricsHistory, Sample, SeriesKey};
+pub use metrics::{usage_reference, MetricsSnapshot, NodeMetrics, PodMetrics, Usage};
+pub use port_forward::PortForwardStats;
+pub use prometheus::{
+    default_templates, has_pod_metrics, PrometheusEndpoint, QuerySeries, QueryTemplate, QueryUnit,
+};
+pub use resources::{
+    ContainerInfo, DeleteOptions, DeletePropagation, DrainEvent, DrainOptions, DrainPod,
+    DrainPodState, ExecEvent, LogLine, LogOptions, PortForwardSpec, Revision, RolloutStatus,
+    TerminalInput,
+};
+pub use stats::{parse_quantity, ClusterStats, ResourceTotals};
+pub use watch::ResourceEvent;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ResourceKind {
     Namespace,
     Pod,
@@ -22,6 +48,26 @@ pub enum ResourceKind {
     PersistentVolume,
     PersistentVolumeClaim,
     Node,
+    /// A CRD or other non-built-in API found through discovery
+    Custom(CustomResourceKind),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CustomResourceKind {
+    pub group: String,
+    pub version: String,
+    pub kind: String,
+    pub plural: String,
+    pub namespaced: bool,
+    /// From the CRD's `additionalPrinterColumns` (empty for non-CRD APIs)
+    pub printer_columns: Vec<PrinterColumn>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PrinterColumn {
+    pub name: String,
+    pub json_path: String,
+    pub type_: String,
 }
 
 impl ResourceKind {
@@ -62,6 +108,29 @@ impl ResourceKind {
             Self::PersistentVolume => "PersistentVolumes",
             Self::PersistentVolumeClaim => "PersistentVolumeClaims",
             Self::Node => "Nodes",
+            Self::Custom(custom) => &custom.kind,
+        }
+    }
+
+    /// Singular API kind, as used in manifests and `kind/name` references.
+    pub fn kind_name(&self) -> &str {
+        match self {
+            Self::Namespace => "Namespace",
+            Self::Pod => "Pod",
+            Self::Deployment => "Deployment",
+            Self::StatefulSet => "StatefulSet",
+            Self::DaemonSet => "DaemonSet",
+            Self::ReplicaSet => "ReplicaSet",
+            Self::Service => "Service",
+            Self::Job => "Job",
+            Self::CronJob => "CronJob",
+            Self::ConfigMap => "ConfigMap",
+            Self::Secret => "Secret",
+            Self::Ingress => "Ingress",
+            Self::PersistentVolume => "PersistentVolume",
+            Self::PersistentVolumeClaim => "PersistentVolumeClaim",
+            Self::Node => "Node",
+            Self::Custom(custom) => &custom.kind,
         }
     }
 
@@ -78,8 +147,48 @@ impl ResourceKind {
             Self::Service | Self::Ingress => "Network",
             Self::ConfigMap | Self::Secret => "Config",
             Self::PersistentVolume | Self::PersistentVolumeClaim => "Storage",
+            Self::Custom(custom) => &custom.group,
         }
     }
+
+    pub fn is_namespaced(&self) -> bool {
+        match self {
+            Self::Namespace | Self::Node | Self::PersistentVolume => false,
+            Self::Custom(custom) => custom.namespaced,
+            _ => true,
+        }
+    }
+
+    /// Kind-specific table columns, filled from `ResourceItem::columns` in order.
+    pub fn extra_columns(&self) -> Vec<String> {
+        let columns: &[&str] = match self {
+            Self::PersistentVolume => &["CAPACITY", "ACCESS MODES", "STORAGE CLASS", "CLAIM"],
+            Self::PersistentVolumeClaim => &["CAPACITY", "ACCESS MODES", "STORAGE CLASS", "VOLUME"],
+            Self::Custom(custom) => {
+                return custom
+                    .printer_columns
+                    .iter()
+                    .map(|c| c.name.to_uppercase())
+                    .collect()
+            }
+            _ => &[],
+        };
+        columns.iter().map(|c| c.to_string()).collect()
+    }
+}
+
+/// Points at a single object, e.g. the volume a claim is bound to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceRef {
+    pub kind: ResourceKind,
+    pub namespace: Option<String>,
+    pub name: String,
+}
+
+impl ResourceRef {
+    pub fn matches(&self, item: &ResourceItem) -> bool {
+        item.kind == self.kind && item.namespace == self.namespace && item.name == self.name
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,5 +201,304 @@ pub struct ResourceItem {
     pub restart_count: Option<i32>,
     pub node_name: Option<String>,
     pub pod_ip: Option<String>,
+    /// Values for `ResourceKind::extra_columns`
+    #[serde(default)]
+    pub columns: Vec<String>,
     pub metadata: serde_json::Value,
 }
+
+impl ResourceItem {
+    pub fn to_ref(&self) -> ResourceRef {
+        ResourceRef {
+            kind: self.kind.clone(),
+            namespace: self.namespace.clone(),
+            name: self.name.clone(),
+        }
+    }
+
+    /// Init and regular containers of a pod, in spec order.
+    pub fn containers(&self) -> Vec<ContainerInfo> {
+        match self.kind {
+            ResourceKind::Pod => serde_json::from_value(self.metadata.clone())
+                .map(|pod| convert::pod_containers(&pod))
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Whether the object has a `scale` subresource the app can patch.
+    pub fn is_scalable(&self) -> bool {
+        matches!(
+            self.kind,
+            ResourceKind::Deployment | ResourceKind::StatefulSet | ResourceKind::ReplicaSet
+        )
+    }
+
+    /// Whether a node is marked unschedulable.
+    pub fn is_cordoned(&self) -> bool {
+        self.kind == ResourceKind::Node
+            && self
+                .metadata
+                .pointer("/spec/unschedulable")
+                .and_then(|u| u.as_bool())
+                .unwrap_or(false)
+    }
+
+    /// Replica progress for scalable workloads.
+    pub fn rollout_status(&self) -> Option<RolloutStatus> {
+        if !self.is_scalable() {
+            return None;
+        }
+        let spec = self.metadata.get("spec");
+        let status = self.metadata.get("status");
+        let count = |value: Option<&serde_json::Value>, key: &str| {
+            value
+                .and_then(|v| v.get(key))
+                .and_then(|v| v.as_i64())
+                .unwrap_or(0) as i32
+        };
+        // Controllers treat a missing `spec.replicas` as 1
+        let desired = spec
+            .and_then(|s| s.get("replicas"))
+            .and_then(|r| r.as_i64())
+            .unwrap_or(1) as i32;
+        let message = status
+            .and_then(|s| s.get("conditions"))
+            .and_then(|c| c.as_array())
+            .into_iter()
+            .flatten()
+            .find(|c| c.get("type").and_then(|t| t.as_str()) == Some("Progressing"))
+            .and_then(|c| c.get("message"))
+            .and_then(|m| m.as_str())
+            .map(str::to_string);
+
+        Some(RolloutStatus {
+            desired,
+            // ReplicaSets have no rollout of their own; all their pods are current
+            updated: match self.kind {
+                ResourceKind::ReplicaSet => count(status, "replicas"),
+                _ => count(status, "updatedReplicas"),
+            },
+            ready: count(status, "readyReplicas"),
+            available: count(status, "availableReplicas"),
+            paused: spec
+                .and_then(|s| s.get("paused"))
+                .and_then(|p| p.as_bool())
+                .unwrap_or(false),
+            message,
+        })
+    }
+
+    /// Ports that can be forwarded: a pod's container ports or a service's ports.
+    pub fn forward_ports(&self) -> Vec<u16> {
+        let Some(spec) = self.metadata.get("spec") else {
+            return Vec::new();
+        };
+        let port_list = |value: Option<&serde_json::Value>, key: &str| -> Vec<u16> {
+            value
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|port| port.get(key)?.as_u64())
+                .filter_map(|port| u16::try_from(port).ok())
+                .collect()
+        };
+
+        let mut ports: Vec<u16> = match self.kind {
+            ResourceKind::Pod => spec
+                .get("containers")
+                .and_then(|c| c.as_array())
+                .into_iter()
+                .flatten()
+                .flat_map(|container| port_list(container.get("ports"), "containerPort"))
+                .collect(),
+            ResourceKind::Service => port_list(spec.get("ports"), "port"),
+            _ => Vec::new(),
+        };
+        ports.sort_unstable();
+        ports.dedup();
+        ports
+    }
+
+    /// Label selector (`k=v,k2 in (a,b)`) for the pods this object manages or targets.
+    pub fn pod_selector(&self) -> Option<String> {
+        let spec = self.metadata.get("spec")?;
+        let selector = match self.kind {
+            ResourceKind::Deployment
+            | ResourceKind::StatefulSet
+            | ResourceKind::DaemonSet
+            | ResourceKind::ReplicaSet
+            | ResourceKind::Job => {
+                let selector = spec.get("selector")?;
+                let mut terms = label_terms(selector.get("matchLabels"));
+                for expr in selector
+                    .get("matchExpressions")
+                    .and_then(|e| e.as_array())
+                    .into_iter()
+                    .flatten()
+                {
+                    let key = expr.get("key")?.as_str()?;
+                    let values: Vec<&str> = expr
+                        .get("values")
+                        .and_then(|v| v.as_array())
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|v| v.as_str())
+                        .collect();
+                    terms.push(match expr.get("operator")?.as_str()? {
+                        "In" => format!("{} in ({})", key, values.join(",")),
+                        "NotIn" => format!("{} notin ({})", key, values.join(",")),
+                        "Exists" => key.to_string(),
+                        "DoesNotExist" => format!("!{}", key),
+                        _ => return None,
+                    });
+                }
+                terms
+            }
+            ResourceKind::Service => label_terms(spec.get("selector")),
+            _ => return None,
+        };
+
+        // An empty selector would match every pod in the namespace
+        (!selector.is_empty()).then(|| selector.join(","))
+    }
+
+    /// The PV a PVC is bound to, or the PVC that claims a PV.
+    pub fn bound_ref(&self) -> Option<ResourceRef> {
+        let spec = self.metadata.get("spec")?;
+        match self.kind {
+            ResourceKind::PersistentVolumeClaim => Some(ResourceRef {
+                kind: ResourceKind::PersistentVolume,
+                namespace: None,
+                name: spec.get("volumeName")?.as_str()?.to_string(),
+            }),
+            ResourceKind::PersistentVolume => {
+                let claim = spec.get("claimRef")?;
+                Some(ResourceRef {
+                    kind: ResourceKind::PersistentVolumeClaim,
+                    namespace: claim
+                        .get("namespace")
+                        .and_then(|ns| ns.as_str())
+                        .map(str::to_string),
+                    name: claim.get("name")?.as_str()?.to_string(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+fn label_terms(labels: Option<&serde_json::Value>) -> Vec<String> {
+    labels
+        .and_then(|l| l.as_object())
+        .into_iter()
+        .flatten()
+        .filter_map(|(k, v)| Some(format!("{}={}", k, v.as_str()?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn item(kind: ResourceKind, metadata: serde_json::Value) -> ResourceItem {
+        ResourceItem {
+            kind,
+            name: "web".to_string(),
+            namespace: Some("default".to_string()),
+            status: String::new(),
+            age: String::new(),
+            restart_count: None,
+            node_name: None,
+            pod_ip: None,
+            columns: Vec::new(),
+            metadata,
+        }
+    }
+
+    #[test]
+    fn pod_selector_joins_labels_and_expressions() {
+        let deployment = item(
+            ResourceKind::Deployment,
+            json!({
+                "spec": {
+                    "selector": {
+                        "matchLabels": { "app": "web", "tier": "front" },
+                        "matchExpressions": [
+                            { "key": "env", "operator": "In", "values": ["prod", "stage"] },
+                            { "key": "canary", "operator": "DoesNotExist" }
+                        ]
+                    }
+                }
+            }),
+        );
+        assert_eq!(
+            deployment.pod_selector().as_deref(),
+            Some("app=web,tier=front,env in (prod,stage),!canary")
+        );
+    }
+
+    #[test]
+    fn pod_selector_reads_service_selector() {
+        let service = item(
+            ResourceKind::Service,
+            json!({ "spec": { "selector": { "app": "web" } } }),
+        );
+        assert_eq!(service.pod_selector().as_deref(), Some("app=web"));
+    }
+
+    #[test]
+    fn pod_selector_skips_empty_and_unsupported() {
+        // A headless service without a selector must not match every pod
+        let service = item(ResourceKind::Service, json!({ "spec": {} }));
+        assert_eq!(service.pod_selector(), None);
+
+        let configmap = item(ResourceKind::ConfigMap, json!({ "spec": {} }));
+        assert_eq!(configmap.pod_selector(), None);
+
+        let unknown_operator = item(
+            ResourceKind::Job,
+            json!({
+                "spec": {
+                    "selector": {
+                        "matchExpressions": [{ "key": "env", "operator": "Gt", "values": ["1"] }]
+                    }
+                }
+            }),
+        );
+        assert_eq!(unknown_operator.pod_selector(), None);
+    }
+
+    #[test]
+    fn forward_ports_sorts_and_dedups_container_ports() {
+        // Sidecars often expose the same port as the main container
+        let pod = item(
+            ResourceKind::Pod,
+            json!({
+                "spec": {
+                    "containers": [
+                        { "name": "app", "ports": [{ "containerPort": 8080 }, { "containerPort": 9090 }] },
+                        { "name": "proxy", "ports": [{ "containerPort": 8080 }, { "containerPort": 80 }] }
+                    ]
+                }
+            }),
+        );
+        assert_eq!(pod.forward_ports(), vec![80, 8080, 9090]);
+    }
+
+    #[test]
+    fn forward_ports_reads_service_ports_only() {
+        let service = item(
+            ResourceKind::Service,
+            json!({ "spec": { "ports": [{ "port": 443, "targetPort": 8443 }, { "port": 70000 }] } }),
+        );
+        assert_eq!(service.forward_ports(), vec![443]);
+
+        let deployment = item(
+            ResourceKind::Deployment,
+            json!({ "spec": { "replicas": 2 } }),
+        );
+        assert!(deployment.forward_ports().is_empty());
+    }
+}
@@ -0,0 +1,199 @@
+use anyhow::{Context, Result};
+use k8s_openapi::api::core::v1::{Pod, Service};
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+use kube::{api::ListParams, Api, ResourceExt};
+use log::{info, warn};
+use std::net::Ipv4Addr;
+use std::sync::atomic::{AtomicU16, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinSet;
+
+/// Live counters for one forward, shared between its tokio tasks and the UI.
+#[derive(Debug, Default)]
+pub struct PortForwardStats {
+    /// Bytes received from the pod
+    pub bytes_in: AtomicU64,
+    /// Bytes sent to the pod
+    pub bytes_out: AtomicU64,
+    /// Open local connections
+    pub connections: AtomicUsize,
+    local_port: AtomicU16,
+    pod: Mutex<Option<String>>,
+    error: Mutex<Option<String>>,
+}
+
+impl PortForwardStats {
+    /// Port the listener is bound to; `None` until it is listening.
+    pub fn local_port(&self) -> Option<u16> {
+        match self.local_port.load(Ordering::Relaxed) {
+            0 => None,
+            port => Some(port),
+        }
+    }
+
+    /// Pod the forward resolved to.
+    pub fn pod(&self) -> Option<String> {
+        self.pod.lock().ok()?.clone()
+    }
+
+    /// Why the forward stopped, if it failed.
+    pub fn error(&self) -> Option<String> {
+        self.error.lock().ok()?.clone()
+    }
+
+    pub fn set_error(&self, error: String) {
+        if let Ok(mut slot) = self.error.lock() {
+            *slot = Some(error);
+        }
+    }
+}
+
+/// Pod and container port behind `port` of `service`.
+pub(super) async fn resolve_service(
+    pods: &Api<Pod>,
+    service: &Service,
+    port: u16,
+) -> Result<(String, u16)> {
+    let spec = service.spec.as_ref().context("Service has no spec")?;
+    let selector = spec
+        .selector
+        .as_ref()
+        .filter(|selector| !selector.is_empty())
+        .context("Service has no selector")?;
+    let service_port = spec
+        .ports
+        .iter()
+        .flatten()
+        .find(|p| p.port == i32::from(port))
+        .with_context(|| format!("Service has no port {}", port))?;
+
+    let labels = selector
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join(",");
+    let list = pods.list(&ListParams::default().labels(&labels)).await?;
+    let pod = list
+        .items
+        .iter()
+        .find(|pod| is_ready(pod))
+        .context("No ready pod backs the service")?;
+
+    let target_port = match &service_port.target_port {
+        None => port,
+        Some(IntOrString::Int(target)) => *target as u16,
+        Some(IntOrString::String(name)) => named_port(pod, name)
+            .with_context(|| format!("Pod {} has no port named {}", pod.name_any(), name))?,
+    };
+    Ok((pod.name_any(), target_port))
+}
+
+fn is_ready(pod: &Pod) -> bool {
+    pod.status
+        .as_ref()
+        .and_then(|status| status.conditions.as_ref())
+        .is_some_and(|conditions| {
+            conditions
+                .iter()
+                .any(|c| c.type_ == "Ready" && c.status == "True")
+        })
+}
+
+fn named_port(pod: &Pod, name: &str) -> Option<u16> {
+    pod.spec
+        .as_ref()?
+        .containers
+        .iter()
+        .flat_map(|container| container.ports.iter().flatten())
+        .find(|port| port.name.as_deref() == Some(name))
+        .map(|port| port.container_port as u16)
+}
+
+/// Listens on localhost and opens a port-forward stream to `pod` for every
+/// accepted connection. Runs until the task is aborted, which also drops the
+/// connection tasks.
+pub(super) async fn serve(
+    api: Api<Pod>,
+    pod: String,
+    port: u16,
+    preferred_local: u16,
+    stats: Arc<PortForwardStats>,
+) -> Result<()> {
+    let listener = match TcpListener::bind((Ipv4Addr::LOCALHOST, preferred_local)).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            warn!(
+                "Port {} unavailable ({}), using a free one",
+                preferred_local, e
+            );
+            TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?
+        }
+    };
+    let local_port = listener.local_addr()?.port();
+    stats.local_port.store(local_port, Ordering::Relaxed);
+    if let Ok(mut slot) = stats.pod.lock() {
+        *slot = Some(pod.clone());
+    }
+    info!("Forwarding localhost:{} -> {}:{}", local_port, pod, port);
+
+    let mut connections = JoinSet::new();
+    loop {
+        let (socket, _) = listener.accept().await?;
+        let (api, pod, stats) = (api.clone(), pod.clone(), stats.clone());
+
+        connections.spawn(async move {
+            stats.connections.fetch_add(1, Ordering::Relaxed);
+            if let Err(e) = forward_connection(&api, &pod, port, socket, &stats).await {
+                warn!("Port-forward connection to {}:{} failed: {}", pod, port, e);
+            }
+            stats.connections.fetch_sub(1, Ordering::Relaxed);
+        });
+
+        // Reap connections that already closed
+        while connections.try_join_next().is_some() {}
+    }
+}
+
+async fn forward_connection(
+    api: &Api<Pod>,
+    pod: &str,
+    port: u16,
+    socket: TcpStream,
+    stats: &PortForwardStats,
+) -> Result<()> {
+    let mut forwarder = api.portforward(pod, &[port]).await?;
+    let upstream = forwarder
+        .take_stream(port)
+        .context("Port-forward stream missing")?;
+
+    let (mut local_read, mut local_write) = socket.into_split();
+    let (mut remote_read, mut remote_write) = tokio::io::split(upstream);
+    let result = tokio::try_join!(
+        pump(&mut local_read, &mut remote_write, &stats.bytes_out),
+        pump(&mut remote_read, &mut local_write, &stats.bytes_in),
+    );
+
+    forwarder.abort();
+    result?;
+    Ok(())
+}
+
+/// Copies until EOF, counting bytes as they go so the UI sees live totals.
+async fn pump(
+    reader: &mut (impl AsyncRead + Unpin),
+    writer: &mut (impl AsyncWrite + Unpin),
+    counter: &AtomicU64,
+) -> std::io::Result<()> {
+    let mut buf = vec![0u8; 16 * 1024];
+    loop {
+        let n = reader.read(&mut buf).await?;
+        if n == 0 {
+            let _ = writer.shutdown().await;
+            return Ok(());
+        }
+        writer.write_all(&buf[..n]).await?;
+        counter.fetch_add(n as u64, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,274 @@
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use super::ResourceKind;
+
+/// Where Prometheus's HTTP API is reached.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum PrometheusEndpoint {
+    /// Base URL, e.g. `http://prometheus.example.com:9090`
+    Url { url: String },
+    /// A Service reached through the API server's service proxy; `service`
+    /// is `name`, `name:port` or `scheme:name:port`, as the proxy takes it
+    Service { namespace: String, service: String },
+}
+
+impl Default for PrometheusEndpoint {
+    /// The Service the Prometheus Operator creates
+    fn default() -> Self {
+        Self::Service {
+            namespace: "monitoring".to_string(),
+            service: "prometheus-operated:9090".to_string(),
+        }
+    }
+}
+
+/// How a query's values are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QueryUnit {
+    Cores,
+    Bytes,
+    BytesPerSecond,
+    Count,
+}
+
+impl QueryUnit {
+    pub fn all() -> [Self; 4] {
+        [Self::Cores, Self::Bytes, Self::BytesPerSecond, Self::Count]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Cores => "cores",
+            Self::Bytes => "bytes",
+            Self::BytesPerSecond => "bytes/s",
+            Self::Count => "count",
+        }
+    }
+
+    /// The unit after this one in `all`, wrapping around.
+    pub fn next(&self) -> Self {
+        let units = Self::all();
+        let ix = units.iter().position(|u| u == self).unwrap_or(0);
+        units[(ix + 1) % units.len()]
+    }
+}
+
+/// A PromQL query run for the selected object. `$namespace` is replaced with
+/// its namespace and `$pod` with a regex matching the pods it selects.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryTemplate {
+    pub name: String,
+    pub query: String,
+    pub unit: QueryUnit,
+}
+
+impl QueryTemplate {
+    fn new(name: &str, query: &str, unit: QueryUnit) -> Self {
+        Self {
+            name: name.to_string(),
+            query: query.to_string(),
+            unit,
+        }
+    }
+
+    /// The query for `pods` in `namespace`.
+    pub fn render(&self, namespace: &str, pods: &[String]) -> String {
+        self.query
+            .replace("$namespace", namespace)
+            .replace("$pod", &pod_pattern(pods))
+    }
+}
+
+pub fn default_templates() -> Vec<QueryTemplate> {
+    vec![
+        QueryTemplate::new(
+            "CPU",
+            r#"sum by (pod) (rate(container_cpu_usage_seconds_total{namespace="$namespace", pod=~"$pod", container!="", container!="POD"}[5m]))"#,
+            QueryUnit::Cores,
+        ),
+        QueryTemplate::new(
+            "Memory",
+            r#"sum by (pod) (container_memory_working_set_bytes{namespace="$namespace", pod=~"$pod", container!="", container!="POD"})"#,
+            QueryUnit::Bytes,
+        ),
+        QueryTemplate::new(
+            "Restarts",
+            r#"sum by (pod) (kube_pod_container_status_restarts_total{namespace="$namespace", pod=~"$pod"})"#,
+            QueryUnit::Count,
+        ),
+        QueryTemplate::new(
+            "Network received",
+            r#"sum by (pod) (rate(container_network_receive_bytes_total{namespace="$namespace", pod=~"$pod"}[5m]))"#,
+            QueryUnit::BytesPerSecond,
+        ),
+        QueryTemplate::new(
+            "Network sent",
+            r#"sum by (pod) (rate(container_network_transmit_bytes_total{namespace="$namespace", pod=~"$pod"}[5m]))"#,
+            QueryUnit::BytesPerSecond,
+        ),
+    ]
+}
+
+/// Kinds the Metrics tab offers: pods and what runs them.
+pub fn has_pod_metrics(kind: &ResourceKind) -> bool {
+    matches!(
+        kind,
+        ResourceKind::Pod
+            | ResourceKind::Deployment
+            | ResourceKind::StatefulSet
+            | ResourceKind::DaemonSet
+            | ResourceKind::ReplicaSet
+            | ResourceKind::Job
+    )
+}
+
+/// Regex matching exactly `pods`, escaped for a PromQL string literal.
+fn pod_pattern(pods: &[String]) -> String {
+    pods.iter()
+        .map(|pod| regex::escape(pod).replace('\\', "\\\\"))
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+/// One labelled line of a range query's result.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuerySeries {
+    pub label: String,
+    /// Unix seconds and value, oldest first
+    pub points: Vec<(i64, f64)>,
+}
+
+/// Parses a `/api/v1/query_range` response body.
+pub(super) fn parse_range_response(body: &str) -> Result<Vec<QuerySeries>> {
+    let response: Value =
+        serde_json::from_str(body).context("Prometheus returned a non-JSON response")?;
+    if response.get("status").and_then(|s| s.as_str()) != Some("success") {
+        let error = response
+            .get("error")
+            .and_then(|e| e.as_str())
+            .unwrap_or("unknown error");
+        return Err(anyhow!("Prometheus query failed: {}", error));
+    }
+
+    let results = response
+        .pointer("/data/result")
+        .and_then(|r| r.as_array())
+        .map(|r| r.as_slice())
+        .unwrap_or_default();
+    Ok(results
+        .iter()
+        .map(|result| {
+            let metric: BTreeMap<String, String> = result
+                .get("metric")
+                .cloned()
+                .and_then(|m| serde_json::from_value(m).ok())
+                .unwrap_or_default();
+            let points = result
+                .get("values")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|point| {
+                    let timestamp = point.get(0)?.as_f64()? as i64;
+                    // Values come as strings, including "NaN" and "+Inf"
+                    let value: f64 = point.get(1)?.as_str()?.parse().ok()?;
+                    value.is_finite().then_some((timestamp, value))
+                })
+                .collect();
+            QuerySeries {
+                label: series_label(&metric),
+                points,
+            }
+        })
+        .collect())
+}
+
+/// The `pod` label when the query keeps it, otherwise all labels.
+fn series_label(metric: &BTreeMap<String, String>) -> String {
+    if let Some(pod) = metric.get("pod") {
+        return pod.clone();
+    }
+    if metric.is_empty() {
+        return "value".to_string();
+    }
+    metric
+        .iter()
+        .map(|(key, value)| format!("{}=\"{}\"", key, value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_range_response_reads_series() {
+        let body = json!({
+            "status": "success",
+            "data": {
+                "resultType": "matrix",
+                "result": [
+                    {
+                        "metric": { "pod": "web-0" },
+                        "values": [[1714564800.0, "0.25"], [1714564815.0, "NaN"], [1714564830, "0.5"]]
+                    },
+                    {
+                        "metric": { "job": "kubelet", "namespace": "default" },
+                        "values": [[1714564800, "+Inf"]]
+                    },
+                    { "metric": {}, "values": [] }
+                ]
+            }
+        })
+        .to_string();
+
+        let series = parse_range_response(&body).unwrap();
+        assert_eq!(series.len(), 3);
+        assert_eq!(series[0].label, "web-0");
+        assert_eq!(
+            series[0].points,
+            vec![(1714564800, 0.25), (1714564830, 0.5)]
+        );
+        assert_eq!(series[1].label, r#"job="kubelet", namespace="default""#);
+        assert!(series[1].points.is_empty());
+        assert_eq!(series[2].label, "value");
+    }
+
+    #[test]
+    fn parse_range_response_surfaces_errors() {
+        let body = json!({
+            "status": "error",
+            "errorType": "bad_data",
+            "error": "parse error at char 5"
+        })
+        .to_string();
+
+        let error = parse_range_response(&body).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Prometheus query failed: parse error at char 5"
+        );
+        assert!(parse_range_response("<html>Bad Gateway</html>").is_err());
+    }
+
+    #[test]
+    fn render_matches_exactly_the_selected_pods() {
+        let template = QueryTemplate::new(
+            "Memory",
+            r#"container_memory_working_set_bytes{namespace="$namespace", pod=~"$pod"}"#,
+            QueryUnit::Bytes,
+        );
+        let pods = vec!["web-7d4b9c-x2x5q".to_string(), "web.canary-0".to_string()];
+
+        assert_eq!(
+            template.render("shop", &pods),
+            r#"container_memory_working_set_bytes{namespace="shop", pod=~"web\\-7d4b9c\\-x2x5q|web\\.canary\\-0"}"#
+        );
+    }
+}
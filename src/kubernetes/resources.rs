@@ -11,11 +11,232 @@ pub struct PodDetails {
     pub containers: Vec<ContainerInfo>,
 }
 
-#[allow(dead_code)]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ContainerInfo {
     pub name: String,
     pub image: String,
     pub ready: bool,
     pub restart_count: i32,
+    /// Runs to completion before the regular containers start
+    #[serde(default)]
+    pub init: bool,
+}
+
+/// Which log to read and how much of it; maps onto `LogParams`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogOptions {
+    /// `None` lets the API server pick (only valid for single-container pods)
+    pub container: Option<String>,
+    /// Log of the previous, terminated instance of the container
+    pub previous: bool,
+    pub tail_lines: Option<i64>,
+    pub since_seconds: Option<i64>,
+    pub timestamps: bool,
+}
+
+impl Default for LogOptions {
+    fn default() -> Self {
+        Self {
+            container: None,
+            previous: false,
+            tail_lines: Some(1000),
+            since_seconds: None,
+            timestamps: false,
+        }
+    }
+}
+
+/// One line of container output, tagged with where it came from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogLine {
+    pub pod: String,
+    pub container: Option<String>,
+    pub text: String,
+}
+
+/// Sent from the terminal view into an exec session.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TerminalInput {
+    Data(Vec<u8>),
+    Resize { cols: u16, rows: u16 },
+}
+
+/// Progress of an exec session.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExecEvent {
+    /// The command that is now running
+    Started(String),
+    Output(Vec<u8>),
+    /// The session ended, with the failure message if it didn't succeed
+    Exited(Option<String>),
+}
+
+/// A pod or service port to expose on localhost. Services are resolved to a
+/// ready pod behind them when the forward starts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortForwardSpec {
+    pub namespace: String,
+    pub kind: super::ResourceKind,
+    pub name: String,
+    pub remote_port: u16,
+    /// Preferred local port; a free one is picked if it is taken
+    pub local_port: u16,
+}
+
+impl PortForwardSpec {
+    /// `svc/postgres`-style label
+    pub fn target(&self) -> String {
+        let prefix = match self.kind {
+            super::ResourceKind::Service => "svc",
+            _ => "pod",
+        };
+        format!("{}/{}", prefix, self.name)
+    }
+
+    /// Same forward, ignoring which local port it ended up on.
+    pub fn same_target(&self, other: &Self) -> bool {
+        self.namespace == other.namespace
+            && self.kind == other.kind
+            && self.name == other.name
+            && self.remote_port == other.remote_port
+    }
+}
+
+/// What the garbage collector does with objects owned by a deleted object.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DeletePropagation {
+    /// Delete the owner now and its dependents in the background
+    #[default]
+    Background,
+    /// Delete the dependents first; the owner stays until they are gone
+    Foreground,
+    /// Keep the dependents, without an owner
+    Orphan,
+}
+
+impl DeletePropagation {
+    pub fn all() -> [Self; 3] {
+        [Self::Background, Self::Foreground, Self::Orphan]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Background => "Background",
+            Self::Foreground => "Foreground",
+            Self::Orphan => "Orphan",
+        }
+    }
+}
+
+/// Maps onto `DeleteParams`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeleteOptions {
+    /// Seconds to wait for graceful termination; `None` uses the object's default
+    pub grace_period: Option<u32>,
+    /// Grace period 0: the object is removed without waiting for the kubelet
+    pub force: bool,
+    pub propagation: DeletePropagation,
+}
+
+/// Replica counts of a Deployment, StatefulSet or ReplicaSet, for showing
+/// scale and rollout progress.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RolloutStatus {
+    /// `spec.replicas`
+    pub desired: i32,
+    /// Replicas running the current template
+    pub updated: i32,
+    pub ready: i32,
+    pub available: i32,
+    /// Deployment rollouts only
+    pub paused: bool,
+    /// Message of the `Progressing` condition, when there is one
+    pub message: Option<String>,
+}
+
+impl RolloutStatus {
+    pub fn is_complete(&self) -> bool {
+        self.updated >= self.desired && self.ready >= self.desired
+    }
+}
+
+/// One entry of a workload's rollout history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Revision {
+    pub revision: i64,
+    /// The ReplicaSet or ControllerRevision that recorded it
+    pub name: String,
+    /// `kubernetes.io/change-cause` annotation
+    pub change_cause: Option<String>,
+    pub images: Vec<String>,
+    pub age: String,
+    /// The pod template of this revision
+    pub template: serde_json::Value,
+    /// Whether the workload is currently at this revision
+    pub current: bool,
+}
+
+/// How a node is drained, following `kubectl drain`'s flags.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrainOptions {
+    /// Overrides each pod's termination grace period
+    pub grace_period: Option<u32>,
+    /// Also evict pods no controller will recreate
+    pub force: bool,
+    /// Also evict pods with emptyDir volumes, whose data is lost
+    pub delete_emptydir_data: bool,
+    /// How long evictions blocked by a PodDisruptionBudget are retried
+    pub timeout_secs: u64,
+}
+
+impl Default for DrainOptions {
+    fn default() -> Self {
+        Self {
+            grace_period: None,
+            force: false,
+            delete_emptydir_data: false,
+            timeout_secs: 300,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrainPodState {
+    Pending,
+    /// Left on the node, e.g. DaemonSet pods
+    Skipped(String),
+    /// A disruption budget refused the eviction for now; it is retried
+    Blocked(String),
+    /// Eviction accepted, waiting for the pod to go away
+    Terminating,
+    Evicted,
+    Failed(String),
+}
+
+impl DrainPodState {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Skipped(_) | Self::Evicted | Self::Failed(_))
+    }
+}
+
+/// A pod on a node being drained.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrainPod {
+    pub namespace: String,
+    pub name: String,
+    pub state: DrainPodState,
+}
+
+/// Progress of a node drain.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrainEvent {
+    /// The node is cordoned; these are the pods found on it
+    Pods(Vec<DrainPod>),
+    Update {
+        namespace: String,
+        name: String,
+        state: DrainPodState,
+    },
+    /// The drain ended, with the failure message if pods were left behind
+    Finished(Option<String>),
 }
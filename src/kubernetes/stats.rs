@@ -0,0 +1,262 @@
+use serde_json::Value;
+
+use super::{ResourceItem, ResourceKind, ResourceRef};
+
+/// Pod phases in lifecycle order.
+const POD_PHASES: [&str; 5] = ["Running", "Pending", "Succeeded", "Failed", "Unknown"];
+
+/// Summed requests and limits of a resource across the given pods, against
+/// what every node offers.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResourceTotals {
+    pub requests: f64,
+    pub limits: f64,
+    pub allocatable: f64,
+}
+
+impl ResourceTotals {
+    pub fn request_fraction(&self) -> Option<f64> {
+        (self.allocatable > 0.0).then(|| self.requests / self.allocatable)
+    }
+
+    pub fn limit_fraction(&self) -> Option<f64> {
+        (self.allocatable > 0.0).then(|| self.limits / self.allocatable)
+    }
+}
+
+/// Numbers behind the dashboard's stat cards.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClusterStats {
+    pub nodes_ready: usize,
+    pub nodes_total: usize,
+    /// Pod counts by phase, in lifecycle order, leaving out empty phases
+    pub pod_phases: Vec<(&'static str, usize)>,
+    pub pods_total: usize,
+    /// In cores
+    pub cpu: ResourceTotals,
+    /// In bytes
+    pub memory: ResourceTotals,
+    pub workloads_total: usize,
+    /// Deployments, StatefulSets and DaemonSets short of their desired replicas
+    pub unhealthy: Vec<ResourceRef>,
+}
+
+impl ClusterStats {
+    /// `workloads` may mix Deployments, StatefulSets and DaemonSets.
+    pub fn compute(
+        nodes: &[ResourceItem],
+        pods: &[ResourceItem],
+        workloads: &[ResourceItem],
+    ) -> Self {
+        let mut stats = Self {
+            nodes_total: nodes.len(),
+            pods_total: pods.len(),
+            workloads_total: workloads.len(),
+            ..Self::default()
+        };
+
+        for node in nodes {
+            if node.status.split(',').next() == Some("Ready") {
+                stats.nodes_ready += 1;
+            }
+            let allocatable = node.metadata.pointer("/status/allocatable");
+            stats.cpu.allocatable += quantity_at(allocatable, "cpu");
+            stats.memory.allocatable += quantity_at(allocatable, "memory");
+        }
+
+        let mut phases = [0usize; POD_PHASES.len()];
+        for pod in pods {
+            let phase = pod
+                .metadata
+                .pointer("/status/phase")
+                .and_then(|p| p.as_str())
+                .unwrap_or("Unknown");
+            let ix = POD_PHASES
+                .iter()
+                .position(|p| *p == phase)
+                .unwrap_or(POD_PHASES.len() - 1);
+            phases[ix] += 1;
+
+            // Finished pods no longer hold their requests
+            if matches!(phase, "Succeeded" | "Failed") {
+                continue;
+            }
+            if let Some(spec) = pod.metadata.get("spec") {
+                stats.cpu.requests += pod_total(spec, "requests", "cpu");
+                stats.cpu.limits += pod_total(spec, "limits", "cpu");
+                stats.memory.requests += pod_total(spec, "requests", "memory");
+                stats.memory.limits += pod_total(spec, "limits", "memory");
+            }
+        }
+        stats.pod_phases = POD_PHASES
+            .into_iter()
+            .zip(phases)
+            .filter(|(_, count)| *count > 0)
+            .collect();
+
+        stats.unhealthy = workloads
+            .iter()
+            .filter(|w| !is_healthy(w))
+            .map(|w| w.to_ref())
+            .collect();
+
+        stats
+    }
+}
+
+fn is_healthy(workload: &ResourceItem) -> bool {
+    match workload.kind {
+        ResourceKind::DaemonSet => {
+            let status = workload.metadata.get("status");
+            let count = |key: &str| {
+                status
+                    .and_then(|s| s.get(key))
+                    .and_then(|v| v.as_i64())
+                    .unwrap_or(0)
+            };
+            count("numberReady") >= count("desiredNumberScheduled")
+                && count("updatedNumberScheduled") >= count("desiredNumberScheduled")
+        }
+        _ => workload
+            .rollout_status()
+            .map_or(true, |status| status.is_complete()),
+    }
+}
+
+/// A pod's effective request or limit, the way the scheduler counts it: the
+/// larger of its containers' sum and its biggest init container, plus overhead.
+pub(super) fn pod_total(spec: &Value, field: &str, resource: &str) -> f64 {
+    let container_value =
+        |c: &Value| quantity_at(c.pointer(&format!("/resources/{}", field)), resource);
+    let containers = |key: &str| {
+        spec.get(key)
+            .and_then(|c| c.as_array())
+            .into_iter()
+            .flatten()
+    };
+
+    let regular: f64 = containers("containers").map(container_value).sum();
+    let init = containers("initContainers")
+        .map(container_value)
+        .fold(0.0, f64::max);
+    regular.max(init) + quantity_at(spec.get("overhead"), resource)
+}
+
+pub(super) fn quantity_at(map: Option<&Value>, key: &str) -> f64 {
+    map.and_then(|m| m.get(key))
+        .and_then(|q| q.as_str())
+        .and_then(parse_quantity)
+        .unwrap_or(0.0)
+}
+
+/// Parses a Kubernetes quantity (`250m`, `1.5`, `512Mi`, `1e3`) into base
+/// units: cores for CPU, bytes for memory.
+pub fn parse_quantity(quantity: &str) -> Option<f64> {
+    let quantity = quantity.trim();
+    let split = quantity
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-' || c == '+'))
+        .unwrap_or(quantity.len());
+    let (number, suffix) = quantity.split_at(split);
+    let number: f64 = number.parse().ok()?;
+
+    let multiplier = match suffix {
+        "" => 1.0,
+        "n" => 1e-9,
+        "u" => 1e-6,
+        "m" => 1e-3,
+        "k" => 1e3,
+        "M" => 1e6,
+        "G" => 1e9,
+        "T" => 1e12,
+        "P" => 1e15,
+        "E" => 1e18,
+        "Ki" => 1024.0,
+        "Mi" => 1024f64.powi(2),
+        "Gi" => 1024f64.powi(3),
+        "Ti" => 1024f64.powi(4),
+        "Pi" => 1024f64.powi(5),
+        "Ei" => 1024f64.powi(6),
+        exponent => {
+            let exponent: i32 = exponent.strip_prefix(['e', 'E'])?.parse().ok()?;
+            10f64.powi(exponent)
+        }
+    };
+    Some(number * multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn item(kind: ResourceKind, metadata: Value) -> ResourceItem {
+        ResourceItem {
+            kind,
+            name: "web".to_string(),
+            namespace: Some("default".to_string()),
+            status: String::new(),
+            age: String::new(),
+            restart_count: None,
+            node_name: None,
+            pod_ip: None,
+            columns: Vec::new(),
+            metadata,
+        }
+    }
+
+    #[test]
+    fn parse_quantity_handles_suffixes() {
+        assert_eq!(parse_quantity("2"), Some(2.0));
+        assert_eq!(parse_quantity("250m"), Some(0.25));
+        assert_eq!(parse_quantity("1.5"), Some(1.5));
+        assert_eq!(parse_quantity("512Mi"), Some(512.0 * 1024.0 * 1024.0));
+        assert_eq!(parse_quantity("1Gi"), Some(1024f64.powi(3)));
+        assert_eq!(parse_quantity("2k"), Some(2000.0));
+        assert_eq!(parse_quantity("1e3"), Some(1000.0));
+        assert_eq!(parse_quantity(" 100M "), Some(1e8));
+    }
+
+    #[test]
+    fn parse_quantity_rejects_garbage() {
+        assert_eq!(parse_quantity(""), None);
+        assert_eq!(parse_quantity("Mi"), None);
+        assert_eq!(parse_quantity("10Xi"), None);
+    }
+
+    #[test]
+    fn compute_counts_init_containers_and_skips_finished_pods() {
+        let node = item(
+            ResourceKind::Node,
+            json!({
+                "status": { "allocatable": { "cpu": "4", "memory": "8Gi" } }
+            }),
+        );
+        let running = item(
+            ResourceKind::Pod,
+            json!({
+                "spec": {
+                    "containers": [
+                        { "resources": { "requests": { "cpu": "250m" } } },
+                        { "resources": { "requests": { "cpu": "250m" } } }
+                    ],
+                    "initContainers": [{ "resources": { "requests": { "cpu": "1" } } }]
+                },
+                "status": { "phase": "Running" }
+            }),
+        );
+        let finished = item(
+            ResourceKind::Pod,
+            json!({
+                "spec": { "containers": [{ "resources": { "requests": { "cpu": "2" } } }] },
+                "status": { "phase": "Succeeded" }
+            }),
+        );
+
+        let stats = ClusterStats::compute(&[node], &[running, finished], &[]);
+        assert_eq!(stats.cpu.allocatable, 4.0);
+        // The init container's 1 core outweighs the 0.5 of the regular containers
+        assert_eq!(stats.cpu.requests, 1.0);
+        assert_eq!(stats.cpu.request_fraction(), Some(0.25));
+        assert_eq!(stats.pods_total, 2);
+    }
+}
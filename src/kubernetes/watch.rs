@@ -0,0 +1,70 @@
+use futures::channel::mpsc::UnboundedSender;
+use futures::StreamExt;
+use kube::{
+    runtime::{watcher, WatchStreamExt},
+    Api, Resource,
+};
+use log::{debug, warn};
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+
+use super::ResourceItem;
+
+/// Incremental change pushed from a running watch into the app state.
+#[derive(Debug, Clone)]
+pub enum ResourceEvent<T = ResourceItem> {
+    /// Full relist completed (initial sync or after a watch desync).
+    Restarted(Vec<T>),
+    /// Object was added or modified.
+    Applied(T),
+    /// Object was removed from the cluster.
+    Deleted(T),
+    /// Watch failed; it will retry with backoff.
+    Error(String),
+}
+
+/// Streams watch events for `api` into `tx` until the receiver is dropped.
+///
+/// Objects received during the initial list are buffered and delivered as a
+/// single `Restarted` batch so the UI never renders a half-populated list.
+pub(super) async fn run_watcher<K, T>(
+    api: Api<K>,
+    convert: impl Fn(&K) -> Option<T>,
+    tx: UnboundedSender<ResourceEvent<T>>,
+) where
+    K: Resource + Clone + DeserializeOwned + Debug + Send + 'static,
+{
+    let mut stream = watcher(api, watcher::Config::default())
+        .default_backoff()
+        .boxed();
+    let mut init_buffer = Vec::new();
+
+    while let Some(event) = stream.next().await {
+        let update = match event {
+            Ok(watcher::Event::Init) => {
+                init_buffer.clear();
+                None
+            }
+            Ok(watcher::Event::InitApply(obj)) => {
+                init_buffer.extend(convert(&obj));
+                None
+            }
+            Ok(watcher::Event::InitDone) => {
+                Some(ResourceEvent::Restarted(std::mem::take(&mut init_buffer)))
+            }
+            Ok(watcher::Event::Apply(obj)) => convert(&obj).map(ResourceEvent::Applied),
+            Ok(watcher::Event::Delete(obj)) => convert(&obj).map(ResourceEvent::Deleted),
+            Err(e) => {
+                warn!("Watch error: {}", e);
+                Some(ResourceEvent::Error(e.to_string()))
+            }
+        };
+
+        if let Some(update) = update {
+            if tx.unbounded_send(update).is_err() {
+                debug!("Watch receiver dropped, stopping watcher");
+                return;
+            }
+        }
+    }
+}
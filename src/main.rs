@@ -45,7 +45,7 @@ fn main() {
                 }),
                 ..Default::default()
             },
-            |_, cx| cx.new(|cx| app::KubeSparkApp::new(cx)),
+            |window, cx| cx.new(|cx| app::KubeSparkApp::new(window, cx)),
         )
         .unwrap();
     });
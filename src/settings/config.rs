@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::kubernetes::{default_templates, PortForwardSpec, PrometheusEndpoint, QueryTemplate};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppSettings {
     pub appearance: AppearanceSettings,
@@ -130,8 +132,47 @@ pub struct KubernetesSettings {
     /// Show resource metrics (CPU, memory)
     pub show_metrics: bool,
 
+    /// Save sampled usage history to disk so charts survive restarts
+    #[serde(default)]
+    pub persist_metrics_history: bool,
+
     /// Enable event notifications
     pub enable_notifications: bool,
+
+    /// Recently selected namespaces, most recent first
+    #[serde(default)]
+    pub recent_namespaces: Vec<String>,
+
+    /// Lines kept in the log view before the oldest are dropped
+    #[serde(default = "default_log_max_lines")]
+    pub log_max_lines: usize,
+
+    /// Port forwards started automatically when their context connects
+    #[serde(default)]
+    pub port_forward_favorites: Vec<PortForwardFavorite>,
+
+    /// Offer the detail view's Metrics tab, queried from `prometheus`
+    #[serde(default)]
+    pub prometheus_enabled: bool,
+
+    #[serde(default)]
+    pub prometheus: PrometheusEndpoint,
+
+    /// PromQL the Metrics tab charts, in order
+    #[serde(default = "default_templates")]
+    pub prometheus_queries: Vec<QueryTemplate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortForwardFavorite {
+    /// Kubeconfig context the forward belongs to
+    pub context: String,
+    #[serde(flatten)]
+    pub spec: PortForwardSpec,
+}
+
+fn default_log_max_lines() -> usize {
+    50_000
 }
 
 impl Default for KubernetesSettings {
@@ -145,7 +186,14 @@ impl Default for KubernetesSettings {
             kubeconfig_path: String::new(),
             context: String::new(),
             show_metrics: true,
+            persist_metrics_history: false,
             enable_notifications: true,
+            recent_namespaces: Vec::new(),
+            log_max_lines: default_log_max_lines(),
+            port_forward_favorites: Vec::new(),
+            prometheus_enabled: false,
+            prometheus: PrometheusEndpoint::default(),
+            prometheus_queries: default_templates(),
         }
     }
 }
@@ -96,6 +96,14 @@ impl SettingsManager {
 
         self.save_settings()
     }
+
+    /// Directory next to the settings file for other state the app keeps
+    pub fn data_dir(&self) -> PathBuf {
+        self.config_path
+            .parent()
+            .map(|dir| dir.to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
 }
 
 impl Default for SettingsManager {
@@ -1,6 +1,9 @@
 use super::config::*;
 use super::manager::SettingsManager;
+use crate::kubernetes::{default_templates, PrometheusEndpoint, QueryTemplate, QueryUnit};
 use crate::theme::{ThemeColors, ThemeExt};
+use crate::ui::chip::chip;
+use crate::ui::text_field::TextField;
 use gpui::prelude::*;
 use gpui::{InteractiveElement, *};
 
@@ -34,6 +37,73 @@ impl SettingsTab {
     }
 }
 
+/// Text settings edited in place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsField {
+    PrometheusUrl,
+    PrometheusNamespace,
+    PrometheusService,
+    QueryName(usize),
+    Query(usize),
+}
+
+impl SettingsField {
+    pub fn value(&self, settings: &KubernetesSettings) -> String {
+        let query = |ix: usize| settings.prometheus_queries.get(ix);
+        match (self, &settings.prometheus) {
+            (Self::PrometheusUrl, PrometheusEndpoint::Url { url }) => url.clone(),
+            (Self::PrometheusNamespace, PrometheusEndpoint::Service { namespace, .. }) => {
+                namespace.clone()
+            }
+            (Self::PrometheusService, PrometheusEndpoint::Service { service, .. }) => {
+                service.clone()
+            }
+            (Self::QueryName(ix), _) => query(*ix).map(|q| q.name.clone()).unwrap_or_default(),
+            (Self::Query(ix), _) => query(*ix).map(|q| q.query.clone()).unwrap_or_default(),
+            _ => String::new(),
+        }
+    }
+
+    pub fn apply(&self, settings: &mut KubernetesSettings, value: String) {
+        match (self, &mut settings.prometheus) {
+            (Self::PrometheusUrl, PrometheusEndpoint::Url { url }) => *url = value,
+            (Self::PrometheusNamespace, PrometheusEndpoint::Service { namespace, .. }) => {
+                *namespace = value
+            }
+            (Self::PrometheusService, PrometheusEndpoint::Service { service, .. }) => {
+                *service = value
+            }
+            (Self::QueryName(ix), _) => {
+                if let Some(query) = settings.prometheus_queries.get_mut(*ix) {
+                    query.name = value;
+                }
+            }
+            (Self::Query(ix), _) => {
+                if let Some(query) = settings.prometheus_queries.get_mut(*ix) {
+                    query.query = value;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A text setting being edited; saved when editing ends with Enter or
+/// another field is picked.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingsEdit {
+    pub field: SettingsField,
+    pub input: TextField,
+}
+
+impl SettingsEdit {
+    pub fn new(field: SettingsField, settings: &KubernetesSettings) -> Self {
+        let mut input = TextField::new(field.value(settings));
+        input.focused = true;
+        Self { field, input }
+    }
+}
+
 pub struct SettingsPanel;
 
 impl SettingsPanel {
@@ -45,13 +115,25 @@ impl SettingsPanel {
         let settings = settings_manager.get_settings();
         let active_tab = SettingsTab::Appearance;
 
-        Self::render_panel(settings_manager, settings, active_tab, on_close, on_change)
+        Self::render_panel(
+            settings_manager,
+            settings,
+            active_tab,
+            |_, _, _| {},
+            None,
+            |_, _, _| {},
+            on_close,
+            on_change,
+        )
     }
 
     pub fn render_panel(
         settings_manager: Arc<SettingsManager>,
         settings: AppSettings,
         active_tab: SettingsTab,
+        on_tab: impl Fn(SettingsTab, &mut Window, &mut App) + 'static + Clone,
+        editing: Option<&SettingsEdit>,
+        on_edit: impl Fn(SettingsField, &mut Window, &mut App) + 'static + Clone,
         on_close: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
         on_change: impl Fn(&mut Window, &mut App) + 'static + Clone,
     ) -> impl IntoElement {
@@ -67,38 +149,44 @@ impl SettingsPanel {
                 on_close,
                 &colors,
             ))
-            .child(Self::render_tab_bar(active_tab, &colors))
+            .child(Self::render_tab_bar(active_tab, on_tab, &colors))
             .child(
-                div().flex_1().child(match active_tab {
-                    SettingsTab::Appearance => Self::render_appearance_tab(
-                        settings_manager.clone(),
-                        &settings.appearance,
-                        on_change,
-                        &colors,
-                    )
-                    .into_any_element(),
-                    SettingsTab::Kubernetes => Self::render_kubernetes_tab(
-                        settings_manager.clone(),
-                        &settings.kubernetes,
-                        on_change,
-                        &colors,
-                    )
-                    .into_any_element(),
-                    SettingsTab::Editor => Self::render_editor_tab(
-                        settings_manager.clone(),
-                        &settings.editor,
-                        on_change,
-                        &colors,
-                    )
-                    .into_any_element(),
-                    SettingsTab::Window => Self::render_window_tab(
-                        settings_manager.clone(),
-                        &settings.window,
-                        on_change,
-                        &colors,
-                    )
-                    .into_any_element(),
-                }),
+                div()
+                    .id("settings-content")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .child(match active_tab {
+                        SettingsTab::Appearance => Self::render_appearance_tab(
+                            settings_manager.clone(),
+                            &settings.appearance,
+                            on_change,
+                            &colors,
+                        )
+                        .into_any_element(),
+                        SettingsTab::Kubernetes => Self::render_kubernetes_tab(
+                            settings_manager.clone(),
+                            &settings.kubernetes,
+                            editing,
+                            on_edit,
+                            on_change,
+                            &colors,
+                        )
+                        .into_any_element(),
+                        SettingsTab::Editor => Self::render_editor_tab(
+                            settings_manager.clone(),
+                            &settings.editor,
+                            on_change,
+                            &colors,
+                        )
+                        .into_any_element(),
+                        SettingsTab::Window => Self::render_window_tab(
+                            settings_manager.clone(),
+                            &settings.window,
+                            on_change,
+                            &colors,
+                        )
+                        .into_any_element(),
+                    }),
             )
     }
 
@@ -166,7 +254,11 @@ impl SettingsPanel {
             )
     }
 
-    fn render_tab_bar(active_tab: SettingsTab, colors: &ThemeColors) -> impl IntoElement {
+    fn render_tab_bar(
+        active_tab: SettingsTab,
+        on_tab: impl Fn(SettingsTab, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
         let tabs = SettingsTab::all();
 
         div()
@@ -186,6 +278,7 @@ impl SettingsPanel {
                 let bg_sidebar = colors.bg_sidebar;
                 let text_active = colors.text_inverse;
                 let text_inactive = colors.text_secondary;
+                let on_tab = on_tab.clone();
 
                 div()
                     .id(tab_name.clone())
@@ -212,6 +305,7 @@ impl SettingsPanel {
                             style.bg(rgb(0x2d2d30)).text_color(rgb(0xffffff))
                         }
                     })
+                    .on_click(move |_, win, cx| on_tab(tab, win, cx))
                     .child(tab_name)
             }))
     }
@@ -451,6 +545,8 @@ impl SettingsPanel {
     fn render_kubernetes_tab(
         settings_manager: Arc<SettingsManager>,
         settings: &KubernetesSettings,
+        editing: Option<&SettingsEdit>,
+        on_edit: impl Fn(SettingsField, &mut Window, &mut App) + 'static + Clone,
         on_change: impl Fn(&mut Window, &mut App) + 'static + Clone,
         colors: &ThemeColors,
     ) -> impl IntoElement {
@@ -547,6 +643,27 @@ impl SettingsPanel {
                         )
                         .into_any_element()
                     },
+                    {
+                        let on_change = on_change.clone();
+                        Self::render_slider(
+                            "Log Buffer Lines",
+                            "Lines kept in the log view before the oldest are dropped",
+                            settings.log_max_lines as f32,
+                            1_000.0,
+                            500_000.0,
+                            {
+                                let sm = sm.clone();
+                                move |val, win, cx| {
+                                    let _ = sm.update_settings(|s| {
+                                        s.kubernetes.log_max_lines = val as usize
+                                    });
+                                    on_change(win, cx);
+                                }
+                            },
+                            colors,
+                        )
+                        .into_any_element()
+                    },
                     {
                         let sm = sm.clone();
                         let on_change = on_change.clone();
@@ -564,6 +681,24 @@ impl SettingsPanel {
                         )
                         .into_any_element()
                     },
+                    {
+                        let sm = sm.clone();
+                        let on_change = on_change.clone();
+                        Self::render_toggle(
+                            "Keep Metrics History",
+                            "Save usage history to disk so charts survive restarts",
+                            settings.persist_metrics_history,
+                            move |win, cx| {
+                                let _ = sm.update_settings(|s| {
+                                    s.kubernetes.persist_metrics_history =
+                                        !s.kubernetes.persist_metrics_history
+                                });
+                                on_change(win, cx);
+                            },
+                            colors,
+                        )
+                        .into_any_element()
+                    },
                 ],
                 colors,
             ))
@@ -589,6 +724,242 @@ impl SettingsPanel {
                 }],
                 colors,
             ))
+            .child(Self::render_prometheus_section(
+                settings_manager,
+                settings,
+                editing,
+                on_edit,
+                on_change,
+                colors,
+            ))
+    }
+
+    fn render_prometheus_section(
+        settings_manager: Arc<SettingsManager>,
+        settings: &KubernetesSettings,
+        editing: Option<&SettingsEdit>,
+        on_edit: impl Fn(SettingsField, &mut Window, &mut App) + 'static + Clone,
+        on_change: impl Fn(&mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        let sm = settings_manager;
+        // Applies `update` to the settings and lets the app pick the change up
+        let updater = |update: fn(&mut KubernetesSettings)| {
+            let sm = sm.clone();
+            let on_change = on_change.clone();
+            move |_: &ClickEvent, win: &mut Window, cx: &mut App| {
+                let _ = sm.update_settings(|s| update(&mut s.kubernetes));
+                on_change(win, cx);
+            }
+        };
+        let field = |field: SettingsField, placeholder: &str| {
+            let on_edit = on_edit.clone();
+            let input = match editing.filter(|e| e.field == field) {
+                Some(edit) => edit.input.clone(),
+                None => TextField::new(field.value(settings)),
+            };
+            input
+                .render(
+                    ("settings-field", Self::field_index(field)),
+                    placeholder,
+                    colors,
+                )
+                .on_click(move |_, win, cx| on_edit(field, win, cx))
+        };
+        let labelled = |label: &str, child: AnyElement| {
+            div()
+                .flex()
+                .items_center()
+                .gap_3()
+                .child(
+                    div()
+                        .w(px(120.0))
+                        .text_sm()
+                        .text_color(colors.text_secondary)
+                        .child(label.to_string()),
+                )
+                .child(div().flex_1().child(child))
+        };
+
+        let is_url = matches!(settings.prometheus, PrometheusEndpoint::Url { .. });
+        let endpoint = div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .px_4()
+            .py_3()
+            .bg(colors.bg_panel)
+            .rounded_lg()
+            .child(labelled(
+                "Source",
+                div()
+                    .flex()
+                    .gap_1()
+                    .child(
+                        chip(("prometheus-source", 0), "Service proxy", !is_url, colors).on_click(
+                            updater(|s| {
+                                if matches!(s.prometheus, PrometheusEndpoint::Url { .. }) {
+                                    s.prometheus = PrometheusEndpoint::default();
+                                }
+                            }),
+                        ),
+                    )
+                    .child(
+                        chip(("prometheus-source", 1), "URL", is_url, colors).on_click(updater(
+                            |s| {
+                                if !matches!(s.prometheus, PrometheusEndpoint::Url { .. }) {
+                                    s.prometheus = PrometheusEndpoint::Url { url: String::new() };
+                                }
+                            },
+                        )),
+                    )
+                    .into_any_element(),
+            ))
+            .map(|this| {
+                if is_url {
+                    this.child(labelled(
+                        "URL",
+                        field(SettingsField::PrometheusUrl, "http://prometheus:9090")
+                            .into_any_element(),
+                    ))
+                } else {
+                    this.child(labelled(
+                        "Namespace",
+                        field(SettingsField::PrometheusNamespace, "monitoring").into_any_element(),
+                    ))
+                    .child(labelled(
+                        "Service",
+                        field(SettingsField::PrometheusService, "prometheus-operated:9090")
+                            .into_any_element(),
+                    ))
+                }
+            });
+
+        let queries = settings
+            .prometheus_queries
+            .iter()
+            .enumerate()
+            .map(|(ix, query)| {
+                let sm_unit = sm.clone();
+                let on_change_unit = on_change.clone();
+                let sm_remove = sm.clone();
+                let on_change_remove = on_change.clone();
+                let unit = query.unit;
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .px_4()
+                    .py_3()
+                    .bg(colors.bg_panel)
+                    .rounded_lg()
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .child(field(SettingsField::QueryName(ix), "Name"))
+                            .child(div().flex_1())
+                            // Cycles through the units
+                            .child(
+                                chip(("query-unit", ix), unit.label(), false, colors).on_click(
+                                    move |_, win, cx| {
+                                        let next = unit.next();
+                                        let _ = sm_unit.update_settings(|s| {
+                                            if let Some(q) =
+                                                s.kubernetes.prometheus_queries.get_mut(ix)
+                                            {
+                                                q.unit = next;
+                                            }
+                                        });
+                                        on_change_unit(win, cx);
+                                    },
+                                ),
+                            )
+                            .child(
+                                chip(("query-remove", ix), "Remove", false, colors).on_click(
+                                    move |_, win, cx| {
+                                        let _ = sm_remove.update_settings(|s| {
+                                            if ix < s.kubernetes.prometheus_queries.len() {
+                                                s.kubernetes.prometheus_queries.remove(ix);
+                                            }
+                                        });
+                                        on_change_remove(win, cx);
+                                    },
+                                ),
+                            ),
+                    )
+                    .child(field(SettingsField::Query(ix), "PromQL").w_full())
+                    .into_any_element()
+            })
+            .collect::<Vec<_>>();
+
+        let mut children = vec![
+            {
+                let sm = sm.clone();
+                let on_change = on_change.clone();
+                Self::render_toggle(
+                    "Enable Prometheus",
+                    "Chart PromQL queries in a Metrics tab of pod and workload details",
+                    settings.prometheus_enabled,
+                    move |win, cx| {
+                        let _ = sm.update_settings(|s| {
+                            s.kubernetes.prometheus_enabled = !s.kubernetes.prometheus_enabled
+                        });
+                        on_change(win, cx);
+                    },
+                    colors,
+                )
+                .into_any_element()
+            },
+            endpoint.into_any_element(),
+            div()
+                .text_xs()
+                .text_color(colors.text_muted)
+                .child(
+                    "Queries run for the selected pod or workload: $namespace is its \
+                     namespace and $pod a regex matching its pods.",
+                )
+                .into_any_element(),
+        ];
+        children.extend(queries);
+        children.push(
+            div()
+                .flex()
+                .gap_2()
+                .child(
+                    chip(("query-actions", 0), "Add query", false, colors).on_click(updater(|s| {
+                        s.prometheus_queries.push(QueryTemplate {
+                            name: "New query".to_string(),
+                            query: String::new(),
+                            unit: QueryUnit::Count,
+                        })
+                    })),
+                )
+                .child(
+                    chip(
+                        ("query-actions", 1),
+                        "Restore default queries",
+                        false,
+                        colors,
+                    )
+                    .on_click(updater(|s| s.prometheus_queries = default_templates())),
+                )
+                .into_any_element(),
+        );
+
+        Self::render_section("Prometheus", children, colors)
+    }
+
+    /// Distinct element ids for the text fields.
+    fn field_index(field: SettingsField) -> usize {
+        match field {
+            SettingsField::PrometheusUrl => 0,
+            SettingsField::PrometheusNamespace => 1,
+            SettingsField::PrometheusService => 2,
+            SettingsField::QueryName(ix) => 3 + ix * 2,
+            SettingsField::Query(ix) => 4 + ix * 2,
+        }
     }
 
     fn render_editor_tab(
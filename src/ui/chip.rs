@@ -0,0 +1,35 @@
+use crate::theme::ThemeColors;
+use gpui::*;
+
+/// Small pill button used for toolbars and option pickers; `selected`
+/// fills it with the accent color.
+pub fn chip(
+    id: impl Into<ElementId>,
+    label: impl Into<SharedString>,
+    selected: bool,
+    colors: &ThemeColors,
+) -> Stateful<Div> {
+    div()
+        .id(id)
+        .px_2()
+        .py_0p5()
+        .rounded_md()
+        .text_xs()
+        .cursor(CursorStyle::PointingHand)
+        .when(selected, |this| {
+            this.bg(colors.accent).text_color(colors.text_inverse)
+        })
+        .when(!selected, |this| {
+            this.bg(colors.bg_element)
+                .text_color(colors.text_secondary)
+                .hover({
+                    let cloned = colors.clone();
+                    move |style| {
+                        style
+                            .bg(cloned.bg_element_hover)
+                            .text_color(cloned.text_primary)
+                    }
+                })
+        })
+        .child(label.into())
+}
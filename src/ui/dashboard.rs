@@ -1,21 +1,60 @@
+use crate::kubernetes::{ClusterStats, EventGroup, EventTypeFilter, HistoryWindow, ResourceTotals};
 use crate::theme::ThemeColors;
+use crate::ui::chip::chip;
+use crate::ui::events::{render_event_row, EventAction};
 use crate::ui::glass::{GlassExt, GlassStyle};
+use crate::ui::port_forwards::format_bytes;
+use crate::ui::usage_chart::UsageHistory;
 use gpui::*;
 
+/// Event groups listed in the feed; the rest are dropped.
+const MAX_FEED_EVENTS: usize = 100;
+
+/// What the dashboard's event feed shows.
+pub struct EventFeed {
+    pub groups: Vec<EventGroup>,
+    /// Event totals by type, over all of `namespaces`
+    pub normal: i32,
+    pub warning: i32,
+    pub filter: EventTypeFilter,
+    /// Namespaces the feed covers; empty = all
+    pub namespaces: Vec<String>,
+    pub error: Option<String>,
+    pub synced: bool,
+}
+
 pub struct DashboardView {
     glass_style: GlassStyle,
+    /// `None` until the first node list arrives
+    stats: Option<ClusterStats>,
+    /// `None` while metrics are off or metrics-server is missing
+    usage: Option<UsageHistory>,
+    events: EventFeed,
     colors: ThemeColors,
 }
 
 impl DashboardView {
-    pub fn new(glass_style: GlassStyle, colors: &ThemeColors) -> Self {
+    pub fn new(
+        glass_style: GlassStyle,
+        stats: Option<ClusterStats>,
+        usage: Option<UsageHistory>,
+        events: EventFeed,
+        colors: &ThemeColors,
+    ) -> Self {
         Self {
             glass_style,
+            stats,
+            usage,
+            events,
             colors: colors.clone(),
         }
     }
 
-    pub fn render(&self) -> impl IntoElement {
+    pub fn render(
+        &self,
+        on_usage_window: impl Fn(HistoryWindow, &mut Window, &mut App) + 'static + Clone,
+        on_event_action: impl Fn(EventAction, &mut Window, &mut App) + 'static + Clone,
+    ) -> impl IntoElement {
         div()
             .flex()
             .flex_col()
@@ -30,8 +69,8 @@ impl DashboardView {
                     .gap_4()
                     .p_6()
                     .pt_0()
-                    .child(self.render_resource_chart())
-                    .child(self.render_recent_events()),
+                    .child(self.render_resource_chart(on_usage_window))
+                    .child(self.render_recent_events(on_event_action)),
             )
     }
 
@@ -53,24 +92,141 @@ impl DashboardView {
     }
 
     fn render_stats(&self) -> impl IntoElement {
-        div()
-            .flex()
-            .gap_4()
-            .p_6()
-            .child(self.render_stat_card("Nodes", "3", "Ready"))
-            .child(self.render_stat_card("Pods", "12", "Running"))
-            .child(self.render_stat_card("CPU", "45%", "Usage"))
-            .child(self.render_stat_card("Memory", "2.1 GB", "Usage"))
+        let colors = &self.colors;
+        let row = div().flex().gap_4().p_6();
+        let Some(stats) = &self.stats else {
+            return row
+                .child(self.render_stat_card(
+                    "Nodes",
+                    "–".to_string(),
+                    "Loading…".to_string(),
+                    colors.text_muted,
+                    None,
+                ))
+                .child(self.render_stat_card(
+                    "Pods",
+                    "–".to_string(),
+                    "Loading…".to_string(),
+                    colors.text_muted,
+                    None,
+                ));
+        };
+
+        let nodes_color = if stats.nodes_ready == stats.nodes_total {
+            colors.status_ok
+        } else {
+            colors.status_warning
+        };
+        let phases = if stats.pod_phases.is_empty() {
+            "No pods".to_string()
+        } else {
+            stats
+                .pod_phases
+                .iter()
+                .map(|(phase, count)| format!("{} {}", count, phase))
+                .collect::<Vec<_>>()
+                .join(" · ")
+        };
+        let failed_pods = stats
+            .pod_phases
+            .iter()
+            .any(|(phase, count)| *phase == "Failed" && *count > 0);
+        let workloads_color = if stats.unhealthy.is_empty() {
+            colors.status_ok
+        } else {
+            colors.status_warning
+        };
+        let workloads_subtitle = match stats.unhealthy.len() {
+            0 => "All at desired replicas".to_string(),
+            1 => format!("{} not ready", stats.unhealthy[0].name),
+            n => format!("{} not at desired replicas", n),
+        };
+
+        row.child(self.render_stat_card(
+            "Nodes",
+            format!("{}/{}", stats.nodes_ready, stats.nodes_total),
+            "Ready".to_string(),
+            nodes_color,
+            None,
+        ))
+        .child(self.render_stat_card(
+            "Pods",
+            stats.pods_total.to_string(),
+            phases,
+            if failed_pods {
+                colors.status_warning
+            } else {
+                colors.status_ok
+            },
+            None,
+        ))
+        .child(self.render_capacity_card("CPU", &stats.cpu, |cores| format!("{:.2} cores", cores)))
+        .child(
+            self.render_capacity_card("Memory", &stats.memory, |bytes| format_bytes(bytes as u64)),
+        )
+        .child(self.render_stat_card(
+            "Workloads",
+            format!(
+                "{}/{}",
+                stats.workloads_total - stats.unhealthy.len(),
+                stats.workloads_total
+            ),
+            workloads_subtitle,
+            workloads_color,
+            None,
+        ))
+    }
+
+    /// Requests as the headline, limits and allocatable below, with a bar
+    /// of requests against allocatable. Requests only count pods in the
+    /// selected namespaces, while allocatable covers every node.
+    fn render_capacity_card(
+        &self,
+        title: &str,
+        totals: &ResourceTotals,
+        format: impl Fn(f64) -> String,
+    ) -> impl IntoElement {
+        let colors = &self.colors;
+        let requested = totals.request_fraction();
+        let color = match requested {
+            Some(f) if f >= 0.9 => colors.status_error,
+            Some(f) if f >= 0.7 => colors.status_warning,
+            _ => colors.status_ok,
+        };
+        let value = requested
+            .map(|f| format!("{:.0}%", f * 100.0))
+            .unwrap_or_else(|| "–".to_string());
+        let subtitle = format!(
+            "{} requested in selected namespaces vs {} cluster capacity · limits {}{}",
+            format(totals.requests),
+            format(totals.allocatable),
+            format(totals.limits),
+            totals
+                .limit_fraction()
+                .map(|f| format!(" ({:.0}%)", f * 100.0))
+                .unwrap_or_default()
+        );
+
+        self.render_stat_card(title, value, subtitle, color, requested)
     }
 
-    fn render_stat_card(&self, title: &str, value: &str, subtitle: &str) -> impl IntoElement {
+    fn render_stat_card(
+        &self,
+        title: &str,
+        value: String,
+        subtitle: String,
+        subtitle_color: Rgba,
+        fraction: Option<f64>,
+    ) -> impl IntoElement {
         div()
-            .w(px(200.0))
-            .h(px(100.0))
+            .flex_1()
+            .min_w(px(160.0))
+            .h(px(110.0))
             .flex()
             .flex_col()
             .justify_center()
             .items_center()
+            .px_3()
             .glass_card(self.glass_style, &self.colors)
             .child(
                 div()
@@ -84,133 +240,188 @@ impl DashboardView {
                     .text_2xl()
                     .font_weight(FontWeight::BOLD)
                     .text_color(self.colors.text_primary)
-                    .child(value.to_string()),
+                    .child(value),
             )
+            .when_some(fraction, |this, fraction| {
+                this.child(
+                    div()
+                        .mt_1()
+                        .h(px(4.0))
+                        .w_full()
+                        .rounded_sm()
+                        .bg(self.colors.bg_element)
+                        .child(
+                            div()
+                                .h_full()
+                                .w(relative(fraction.clamp(0.0, 1.0) as f32))
+                                .rounded_sm()
+                                .bg(subtitle_color),
+                        ),
+                )
+            })
             .child(
                 div()
                     .text_xs()
-                    .text_color(self.colors.status_ok) // Use status_ok for green
+                    .text_color(subtitle_color)
                     .mt_1()
-                    .child(subtitle.to_string()),
+                    .text_center()
+                    .child(subtitle),
             )
     }
 
-    fn render_resource_chart(&self) -> impl IntoElement {
+    fn render_resource_chart(
+        &self,
+        on_window: impl Fn(HistoryWindow, &mut Window, &mut App) + 'static + Clone,
+    ) -> impl IntoElement {
+        let colors = &self.colors;
         div()
             .flex()
             .flex_col()
             .flex_1()
-            .glass_card(self.glass_style, &self.colors)
+            .glass_card(self.glass_style, colors)
             .p_4()
             .child(
                 div()
                     .text_sm()
                     .font_weight(FontWeight::MEDIUM)
-                    .text_color(self.colors.text_secondary)
+                    .text_color(colors.text_secondary)
                     .mb_4()
                     .child("Resource Usage History"),
             )
-            .child(
-                div()
+            .child(match &self.usage {
+                Some(usage) => usage
+                    .render("dashboard-usage-window", on_window, colors)
+                    .into_any_element(),
+                None => div()
+                    .flex_1()
                     .flex()
-                    .items_end()
-                    .justify_between()
-                    .gap_2()
-                    .h_full()
-                    .pb_2()
-                    .children((0..20).map(|i| {
-                        let height = 20.0 + (i as f32 * 7.0 % 60.0) + (i as f32 * 3.0 % 20.0);
-                        div().w_full().h(px(height)).rounded_t_sm().bg(hsla(
-                            210.0 / 360.0,
-                            0.6,
-                            0.6,
-                            0.5,
-                        )) // Blue-ish bars
-                    })),
-            )
+                    .items_center()
+                    .justify_center()
+                    .text_sm()
+                    .text_color(colors.text_muted)
+                    .child("Usage history needs metrics-server and Show Metrics enabled.")
+                    .into_any_element(),
+            })
     }
 
-    fn render_recent_events(&self) -> impl IntoElement {
-        let events = vec![
-            (
-                "Normal",
-                "Scheduled",
-                "pod/nginx-deployment-5d59d67564-abcde",
-                "Successfully assigned default/nginx to node-1",
-            ),
-            (
-                "Normal",
-                "Pulling",
-                "pod/nginx-deployment-5d59d67564-abcde",
-                "Pulling image 'nginx:latest'",
-            ),
-            (
-                "Normal",
-                "Created",
-                "pod/nginx-deployment-5d59d67564-abcde",
-                "Created container nginx",
-            ),
-            (
-                "Normal",
-                "Started",
-                "pod/nginx-deployment-5d59d67564-abcde",
-                "Started container nginx",
-            ),
-            (
-                "Warning",
-                "BackOff",
-                "pod/crash-loop-pod",
-                "Back-off restarting failed container",
-            ),
-        ];
+    fn render_recent_events(
+        &self,
+        on_action: impl Fn(EventAction, &mut Window, &mut App) + 'static + Clone,
+    ) -> impl IntoElement {
+        let feed = &self.events;
+        let colors = &self.colors;
+
+        let filter_chips = EventTypeFilter::all()
+            .into_iter()
+            .enumerate()
+            .map(|(ix, filter)| {
+                let on_action = on_action.clone();
+                chip(
+                    ("event-filter", ix),
+                    filter.label(),
+                    feed.filter == filter,
+                    colors,
+                )
+                .on_click(move |_, win, cx| on_action(EventAction::SetFilter(filter), win, cx))
+            });
+
+        let scope = if feed.namespaces.is_empty() {
+            "All namespaces".to_string()
+        } else {
+            feed.namespaces.join(", ")
+        };
+
+        let body = if let Some(error) = feed.error.as_ref().filter(|_| !feed.synced) {
+            div()
+                .text_xs()
+                .text_color(colors.status_error)
+                .child(format!("Couldn't watch events: {}", error))
+        } else if !feed.synced {
+            div()
+                .text_xs()
+                .text_color(colors.text_muted)
+                .child("Loading events…")
+        } else if feed.groups.is_empty() {
+            div()
+                .text_xs()
+                .text_color(colors.text_muted)
+                .child("No events.")
+        } else {
+            div().flex().flex_col().gap_2().children(
+                feed.groups
+                    .iter()
+                    .take(MAX_FEED_EVENTS)
+                    .enumerate()
+                    .map(|(ix, group)| {
+                        let row = render_event_row(group, true, colors).id(("event", ix));
+                        match group.regarding.clone() {
+                            Some(object) => {
+                                let on_action = on_action.clone();
+                                row.cursor(CursorStyle::PointingHand)
+                                    .hover({
+                                        let cloned = colors.clone();
+                                        move |style| style.bg(cloned.bg_element_hover)
+                                    })
+                                    .on_click(move |_, win, cx| {
+                                        on_action(EventAction::Open(object.clone()), win, cx)
+                                    })
+                            }
+                            None => row,
+                        }
+                    }),
+            )
+        };
 
         div()
             .flex()
             .flex_col()
             .flex_1()
-            .glass_card(self.glass_style, &self.colors)
+            .glass_card(self.glass_style, colors)
             .p_4()
-            .child(
-                div()
-                    .text_sm()
-                    .font_weight(FontWeight::MEDIUM)
-                    .text_color(self.colors.text_secondary)
-                    .mb_4()
-                    .child("Recent Events"),
-            )
             .child(
                 div()
                     .flex()
-                    .flex_col()
-                    .gap_2()
-                    .children(events.into_iter().map(|(kind, reason, object, message)| {
+                    .items_center()
+                    .justify_between()
+                    .mb_4()
+                    .child(
                         div()
                             .flex()
                             .flex_col()
-                            .gap_px()
-                            .p_2()
-                            .rounded_md()
-                            .bg(hsla(0.0, 0.0, 1.0, 0.03))
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .font_weight(FontWeight::MEDIUM)
+                                    .text_color(colors.text_secondary)
+                                    .child("Recent Events"),
+                            )
                             .child(
                                 div()
                                     .flex()
-                                    .items_center()
                                     .gap_2()
+                                    .text_xs()
+                                    .text_color(colors.text_muted)
+                                    .child(scope)
+                                    .child(format!("{} normal", feed.normal))
                                     .child(
                                         div()
-                                            .text_xs()
-                                            .font_weight(FontWeight::BOLD)
-                                            .text_color(if kind == "Warning" {
-                                                rgb(0xffaa00)
+                                            .text_color(if feed.warning > 0 {
+                                                colors.status_warning
                                             } else {
-                                                rgb(0xcccccc)
+                                                colors.text_muted
                                             })
-                                            .child(reason),
-                                    )
-                                    .child(div().text_xs().text_color(rgb(0x666666)).child(object)),
-                            )
-                            .child(div().text_xs().text_color(rgb(0xaaaaaa)).child(message))
-                    })),
+                                            .child(format!("{} warning", feed.warning)),
+                                    ),
+                            ),
+                    )
+                    .child(div().flex().gap_1().children(filter_chips)),
+            )
+            .child(
+                div()
+                    .id("event-feed")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .child(body),
             )
     }
 }
@@ -0,0 +1,260 @@
+use crate::kubernetes::{
+    DeleteOptions, DeletePropagation, ResourceItem, ResourceKind, ResourceRef,
+};
+use crate::theme::ThemeColors;
+use crate::ui::chip::chip;
+use gpui::*;
+
+/// Grace periods offered besides the object's default.
+pub(super) const GRACE_PERIODS: [u32; 4] = [5, 30, 120, 600];
+
+/// Dependents listed before the rest are summarized.
+const MAX_LISTED_DEPENDENTS: usize = 12;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DeleteAction {
+    SetGracePeriod(Option<u32>),
+    ToggleForce,
+    SetPropagation(DeletePropagation),
+    Confirm,
+    Cancel,
+}
+
+/// Confirmation for deleting one object, with the options it is deleted with.
+#[derive(Clone, Debug)]
+pub struct DeleteDialog {
+    pub target: ResourceItem,
+    pub options: DeleteOptions,
+    /// Owned objects the garbage collector would act on; `None` while loading
+    pub dependents: Option<Result<Vec<ResourceRef>, String>>,
+}
+
+impl DeleteDialog {
+    pub fn new(target: ResourceItem) -> Self {
+        Self {
+            target,
+            options: DeleteOptions::default(),
+            dependents: None,
+        }
+    }
+
+    pub fn render(
+        &self,
+        on_action: impl Fn(DeleteAction, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        let target = &self.target;
+        let options = &self.options;
+        let title = format!("Delete {} {}?", target.kind.kind_name(), target.name);
+
+        let option_chip =
+            |id: (&'static str, usize), label: String, selected: bool, action: DeleteAction| {
+                let on_action = on_action.clone();
+                chip(id, label, selected, colors)
+                    .on_click(move |_, win, cx| on_action(action.clone(), win, cx))
+            };
+
+        let grace_chips = std::iter::once(None)
+            .chain(GRACE_PERIODS.into_iter().map(Some))
+            .enumerate()
+            .map(|(ix, period)| {
+                option_chip(
+                    ("delete-grace", ix),
+                    grace_label(period),
+                    !options.force && options.grace_period == period,
+                    DeleteAction::SetGracePeriod(period),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let propagation_chips = DeletePropagation::all()
+            .into_iter()
+            .enumerate()
+            .map(|(ix, propagation)| {
+                option_chip(
+                    ("delete-propagation", ix),
+                    propagation.label().to_string(),
+                    options.propagation == propagation,
+                    DeleteAction::SetPropagation(propagation),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let on_cancel = on_action.clone();
+        let on_confirm = on_action.clone();
+
+        // Backdrop; swallows clicks meant for the views underneath
+        div()
+            .id("delete-dialog-backdrop")
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full()
+            .flex()
+            .items_center()
+            .justify_center()
+            .bg(hsla(0.0, 0.0, 0.0, 0.45))
+            .occlude()
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_3()
+                    .w(px(480.0))
+                    .p_4()
+                    .rounded_lg()
+                    .bg(colors.bg_panel)
+                    .border_1()
+                    .border_color(colors.border)
+                    .text_sm()
+                    .child(
+                        div()
+                            .text_lg()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(colors.text_primary)
+                            .child(title),
+                    )
+                    .when_some(target.namespace.clone(), |this, ns| {
+                        this.child(
+                            div()
+                                .text_color(colors.text_muted)
+                                .child(format!("Namespace: {}", ns)),
+                        )
+                    })
+                    .child(option_row("Grace period", grace_chips, colors))
+                    .child(option_row(
+                        "Force",
+                        vec![option_chip(
+                            ("delete-force", 0),
+                            if options.force {
+                                "☑ Grace period 0".to_string()
+                            } else {
+                                "☐ Grace period 0".to_string()
+                            },
+                            options.force,
+                            DeleteAction::ToggleForce,
+                        )],
+                        colors,
+                    ))
+                    .child(option_row("Dependents", propagation_chips, colors))
+                    .child(self.render_dependents(colors))
+                    .child(
+                        div()
+                            .flex()
+                            .justify_end()
+                            .gap_2()
+                            .pt_2()
+                            .child(
+                                div()
+                                    .id("delete-cancel")
+                                    .px_4()
+                                    .py_2()
+                                    .rounded_md()
+                                    .bg(colors.bg_element)
+                                    .text_color(colors.text_primary)
+                                    .cursor(CursorStyle::PointingHand)
+                                    .hover({
+                                        let cloned = colors.clone();
+                                        move |style| style.bg(cloned.bg_element_hover)
+                                    })
+                                    .on_click(move |_, win, cx| {
+                                        on_cancel(DeleteAction::Cancel, win, cx)
+                                    })
+                                    .child("Cancel"),
+                            )
+                            .child(
+                                div()
+                                    .id("delete-confirm")
+                                    .px_4()
+                                    .py_2()
+                                    .rounded_md()
+                                    .bg(colors.status_error)
+                                    .text_color(colors.text_inverse)
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .cursor(CursorStyle::PointingHand)
+                                    .on_click(move |_, win, cx| {
+                                        on_confirm(DeleteAction::Confirm, win, cx)
+                                    })
+                                    .child("Delete"),
+                            ),
+                    ),
+            )
+    }
+
+    fn render_dependents(&self, colors: &ThemeColors) -> impl IntoElement {
+        let orphan = self.options.propagation == DeletePropagation::Orphan;
+        let note = |text: String| div().text_xs().text_color(colors.text_muted).child(text);
+
+        let body = if self.target.kind == ResourceKind::Namespace {
+            note("Everything in the namespace is deleted with it.".to_string())
+        } else {
+            match &self.dependents {
+                None => note("Looking for dependents…".to_string()),
+                Some(Err(error)) => div()
+                    .text_xs()
+                    .text_color(colors.status_warning)
+                    .child(format!("Couldn't list dependents: {}", error)),
+                Some(Ok(dependents)) if dependents.is_empty() => {
+                    note("No dependent objects.".to_string())
+                }
+                Some(Ok(dependents)) => div()
+                    .flex()
+                    .flex_col()
+                    .gap_0p5()
+                    .child(note(if orphan {
+                        format!("{} dependents are kept without an owner:", dependents.len())
+                    } else {
+                        format!("{} dependents are deleted with it:", dependents.len())
+                    }))
+                    .children(dependents.iter().take(MAX_LISTED_DEPENDENTS).map(|dep| {
+                        div()
+                            .pl_2()
+                            .text_xs()
+                            .text_color(colors.text_secondary)
+                            .child(format!("{} {}", dep.kind.kind_name(), dep.name))
+                    }))
+                    .when(dependents.len() > MAX_LISTED_DEPENDENTS, |this| {
+                        this.child(note(format!(
+                            "…and {} more",
+                            dependents.len() - MAX_LISTED_DEPENDENTS
+                        )))
+                    }),
+            }
+        };
+
+        div()
+            .p_2()
+            .rounded_md()
+            .bg(colors.bg_element)
+            .max_h(px(200.0))
+            .overflow_hidden()
+            .child(body)
+    }
+}
+
+pub(super) fn grace_label(period: Option<u32>) -> String {
+    match period {
+        None => "Default".to_string(),
+        Some(secs) if secs % 60 == 0 => format!("{}m", secs / 60),
+        Some(secs) => format!("{}s", secs),
+    }
+}
+
+pub(super) fn option_row(
+    label: &'static str,
+    chips: Vec<Stateful<Div>>,
+    colors: &ThemeColors,
+) -> impl IntoElement {
+    div()
+        .flex()
+        .items_center()
+        .gap_2()
+        .child(
+            div()
+                .w(px(100.0))
+                .text_xs()
+                .text_color(colors.text_muted)
+                .child(label),
+        )
+        .child(div().flex().flex_wrap().gap_1().children(chips))
+}
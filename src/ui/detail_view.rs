@@ -2,38 +2,158 @@ use gpui::prelude::*;
 
 use gpui::*;
 
-use crate::kubernetes::ResourceItem;
+use crate::kubernetes::{
+    EventGroup, HistoryWindow, ResourceItem, ResourceKind, ResourceRef, SaveMode,
+};
+use crate::settings::EditorSettings;
 use crate::theme::ThemeColors;
+use crate::ui::drain_dialog::NodeAction;
+use crate::ui::events::render_event_row;
 use crate::ui::glass::{GlassExt, GlassStyle};
+use crate::ui::prometheus::{MetricsAction, PrometheusMetrics};
+use crate::ui::rollout::{
+    has_history, DetailTab, HistoryAction, RolloutControls, RolloutHistory, WorkloadAction,
+};
+use crate::ui::usage_chart::UsageHistory;
+use crate::ui::yaml_editor::{render_yaml, YamlAction, YamlEditor};
+
+/// Something done from the detail panel, for the app to carry out.
+#[derive(Clone, Debug)]
+pub enum DetailAction {
+    Back,
+    SelectTab(DetailTab),
+    ViewLogs(ResourceItem),
+    OpenShell(ResourceItem),
+    PortForward(ResourceItem, u16),
+    Navigate(ResourceRef),
+    Delete(ResourceItem),
+    Workload(WorkloadAction),
+    Node(NodeAction),
+    UsageWindow(HistoryWindow),
+    Yaml(YamlAction),
+    History(HistoryAction),
+    Metrics(MetricsAction),
+}
+
+/// What the detail panel shows for the selected object.
+pub struct DetailProps {
+    pub selected: Option<ResourceItem>,
+    pub glass_style: GlassStyle,
+    pub can_go_back: bool,
+    pub tab: DetailTab,
+    /// Replicas asked for by a scale still being applied
+    pub replica_target: Option<i32>,
+    pub events: Vec<EventGroup>,
+    pub usage: Option<UsageHistory>,
+    pub yaml_editor: Option<YamlEditor>,
+    pub editor_settings: EditorSettings,
+    pub history: Option<RolloutHistory>,
+    /// The Metrics tab, offered while Prometheus is enabled
+    pub metrics: Option<PrometheusMetrics>,
+}
 
 pub struct DetailView;
 
 impl DetailView {
     pub fn new(
-        selected: Option<ResourceItem>,
-        glass_style: GlassStyle,
-        on_view_logs: impl Fn(String, &mut Window, &mut App) + 'static + Clone,
+        props: DetailProps,
+        on_action: impl Fn(DetailAction, &mut Window, &mut App) + 'static + Clone,
         colors: &ThemeColors,
     ) -> impl IntoElement {
         let mut base = div()
             .flex()
             .flex_col()
             .size_full()
-            .glass_panel(glass_style, colors)
-            .child(Self::render_header(colors));
-
-        if let Some(resource) = selected {
-            base = base.child(div().flex_1().p_4().child(Self::render_resource_detail(
-                resource,
-                on_view_logs,
+            .glass_panel(props.glass_style, colors)
+            .child(Self::render_header(
+                props.can_go_back,
+                on_action.clone(),
                 colors,
-            )));
+            ));
+
+        if let Some(resource) = props.selected.clone() {
+            let mut tabs = vec![DetailTab::Overview];
+            if has_history(&resource.kind) {
+                tabs.push(DetailTab::History);
+            }
+            if props.metrics.is_some() {
+                tabs.push(DetailTab::Metrics);
+            }
+            if tabs.len() > 1 {
+                let on_action = on_action.clone();
+                base = base.child(Self::render_tabs(
+                    &tabs,
+                    props.tab,
+                    move |tab, win, cx| on_action(DetailAction::SelectTab(tab), win, cx),
+                    colors,
+                ));
+            }
+            let tab = if tabs.contains(&props.tab) {
+                props.tab
+            } else {
+                DetailTab::Overview
+            };
+
+            let content = div().flex_1().p_4();
+            base = base.child(match tab {
+                DetailTab::History => content.child(props.history.unwrap_or_default().render(
+                    move |action, win, cx| on_action(DetailAction::History(action), win, cx),
+                    colors,
+                )),
+                DetailTab::Metrics => content.child(props.metrics.unwrap_or_default().render(
+                    move |action, win, cx| on_action(DetailAction::Metrics(action), win, cx),
+                    colors,
+                )),
+                DetailTab::Overview => content.child(Self::render_resource_detail(
+                    resource, props, on_action, colors,
+                )),
+            });
         }
 
         base
     }
 
-    fn render_header(colors: &ThemeColors) -> impl IntoElement {
+    fn render_tabs(
+        tabs: &[DetailTab],
+        active: DetailTab,
+        on_tab: impl Fn(DetailTab, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        div()
+            .flex()
+            .gap_4()
+            .px_4()
+            .border_b_1()
+            .border_color(colors.border)
+            .children(tabs.iter().copied().enumerate().map(|(ix, tab)| {
+                let on_tab = on_tab.clone();
+                let selected = tab == active;
+                div()
+                    .id(("detail-tab", ix))
+                    .py_2()
+                    .text_sm()
+                    .cursor(CursorStyle::PointingHand)
+                    .border_b_2()
+                    .border_color(if selected {
+                        colors.accent
+                    } else {
+                        gpui::transparent_black()
+                    })
+                    .text_color(if selected {
+                        colors.text_primary
+                    } else {
+                        colors.text_muted
+                    })
+                    .on_click(move |_, win, cx| on_tab(tab, win, cx))
+                    .child(tab.label())
+            }))
+    }
+
+    fn render_header(
+        can_go_back: bool,
+        on_action: impl Fn(DetailAction, &mut Window, &mut App) + 'static,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
         div()
             .flex()
             .items_center()
@@ -44,10 +164,36 @@ impl DetailView {
             .border_color(colors.border)
             .child(
                 div()
-                    .text_lg()
-                    .font_weight(FontWeight::SEMIBOLD)
-                    .text_color(colors.text_primary)
-                    .child("Details"),
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .children(if can_go_back {
+                        Some(
+                            div()
+                                .id("detail-back")
+                                .px_2()
+                                .py_1()
+                                .rounded_md()
+                                .text_sm()
+                                .text_color(colors.text_secondary)
+                                .cursor(CursorStyle::PointingHand)
+                                .hover({
+                                    let cloned = colors.clone();
+                                    move |style| style.bg(cloned.bg_element_hover)
+                                })
+                                .on_click(move |_, win, cx| on_action(DetailAction::Back, win, cx))
+                                .child("←"),
+                        )
+                    } else {
+                        None
+                    })
+                    .child(
+                        div()
+                            .text_lg()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(colors.text_primary)
+                            .child("Details"),
+                    ),
             )
             .child(
                 div()
@@ -63,7 +209,8 @@ impl DetailView {
 
     fn render_resource_detail(
         resource: ResourceItem,
-        on_view_logs: impl Fn(String, &mut Window, &mut App) + 'static + Clone,
+        props: DetailProps,
+        on_action: impl Fn(DetailAction, &mut Window, &mut App) + 'static + Clone,
         colors: &ThemeColors,
     ) -> impl IntoElement {
         let title_str = "Basic Information".to_string();
@@ -73,7 +220,7 @@ impl DetailView {
         let label_status = "Status".to_string();
         let label_age = "Age".to_string();
 
-        let items = vec![
+        let mut items = vec![
             (label_name, resource.name.clone()),
             (label_kind, resource.kind.display_name().to_string()),
             (
@@ -86,20 +233,162 @@ impl DetailView {
             (label_status, resource.status.clone()),
             (label_age, resource.age.clone()),
         ];
+        items.extend(
+            resource
+                .kind
+                .extra_columns()
+                .iter()
+                .zip(resource.columns.iter())
+                .map(|(label, value)| (title_case(label), value.clone())),
+        );
 
         let mut content = div()
             .flex()
             .flex_col()
             .gap_4()
-            .child(Self::render_info_section(title_str, items, colors));
+            .child(Self::render_info_section(title_str, items, colors))
+            .child(Self::render_actions(
+                &resource,
+                {
+                    let on_action = on_action.clone();
+                    move |resource, win, cx| on_action(DetailAction::Delete(resource), win, cx)
+                },
+                {
+                    let on_action = on_action.clone();
+                    move |action, win, cx| on_action(DetailAction::Node(action), win, cx)
+                },
+                colors,
+            ));
+
+        if let Some(status) = resource.rollout_status() {
+            content = content.child(RolloutControls::new(
+                &resource,
+                status,
+                props.replica_target,
+                {
+                    let on_action = on_action.clone();
+                    move |action, win, cx| on_action(DetailAction::Workload(action), win, cx)
+                },
+                colors,
+            ));
+        }
+
+        // Logs for a pod, or aggregated across the pods a workload/service selects
+        if resource.kind == ResourceKind::Pod || resource.pod_selector().is_some() {
+            let logs_resource = resource.clone();
+            let shell_resource = resource.clone();
+            let on_shell = on_action.clone();
+            let on_action = on_action.clone();
+            content = content.child(
+                div()
+                    .flex()
+                    .justify_end()
+                    .gap_2()
+                    // Exec only makes sense against a single running pod
+                    .when(resource.kind == ResourceKind::Pod, |this| {
+                        this.child(
+                            div()
+                                .id("open-shell-btn")
+                                .px_4()
+                                .py_2()
+                                .bg(colors.bg_element)
+                                .rounded_md()
+                                .text_sm()
+                                .text_color(colors.text_primary)
+                                .cursor(CursorStyle::PointingHand)
+                                .hover({
+                                    let cloned = colors.clone();
+                                    move |style| style.bg(cloned.bg_element_hover)
+                                })
+                                .on_click(move |_, win, cx| {
+                                    on_shell(
+                                        DetailAction::OpenShell(shell_resource.clone()),
+                                        win,
+                                        cx,
+                                    )
+                                })
+                                .child("Open Shell"),
+                        )
+                    })
+                    .child(
+                        div()
+                            .id("view-logs-btn")
+                            .px_4()
+                            .py_2()
+                            .bg(colors.bg_element)
+                            .rounded_md()
+                            .text_sm()
+                            .text_color(colors.text_primary)
+                            .cursor(CursorStyle::PointingHand)
+                            .hover({
+                                let cloned = colors.clone();
+                                move |style| style.bg(cloned.bg_element_hover)
+                            })
+                            .on_click(move |_, win, cx| {
+                                on_action(DetailAction::ViewLogs(logs_resource.clone()), win, cx)
+                            })
+                            .child("View Logs"),
+                    ),
+            );
+        }
 
-        // Add Logs button for Pods
-        if resource.kind == crate::kubernetes::ResourceKind::Pod {
-            let pod_name = resource.name.clone();
+        let ports = resource.forward_ports();
+        if !ports.is_empty() {
+            content = content.child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .items_center()
+                    .justify_end()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(colors.text_muted)
+                            .child("Port forward:"),
+                    )
+                    .children(ports.into_iter().map(|port| {
+                        let forward_resource = resource.clone();
+                        let on_action = on_action.clone();
+                        div()
+                            .id(("port-forward-btn", port as usize))
+                            .px_3()
+                            .py_1()
+                            .bg(colors.bg_element)
+                            .rounded_md()
+                            .text_sm()
+                            .text_color(colors.text_primary)
+                            .cursor(CursorStyle::PointingHand)
+                            .hover({
+                                let cloned = colors.clone();
+                                move |style| style.bg(cloned.bg_element_hover)
+                            })
+                            .on_click(move |_, win, cx| {
+                                on_action(
+                                    DetailAction::PortForward(forward_resource.clone(), port),
+                                    win,
+                                    cx,
+                                )
+                            })
+                            .child(port.to_string())
+                    })),
+            );
+        }
+
+        // Jump between a claim and the volume it is bound to
+        if let Some(target) = resource.bound_ref() {
+            let label = match target.kind {
+                ResourceKind::PersistentVolume => format!("Go to Volume {}", target.name),
+                _ => match &target.namespace {
+                    Some(ns) => format!("Go to Claim {}/{}", ns, target.name),
+                    None => format!("Go to Claim {}", target.name),
+                },
+            };
+            let on_navigate = on_action.clone();
             content = content.child(
                 div().flex().justify_end().child(
                     div()
-                        .id("view-logs-btn")
+                        .id("bound-resource-btn")
                         .px_4()
                         .py_2()
                         .bg(colors.bg_element)
@@ -111,13 +400,148 @@ impl DetailView {
                             let cloned = colors.clone();
                             move |style| style.bg(cloned.bg_element_hover)
                         })
-                        .on_click(move |_, win, cx| on_view_logs(pod_name.clone(), win, cx))
-                        .child("View Logs"),
+                        .on_click(move |_, win, cx| {
+                            on_navigate(DetailAction::Navigate(target.clone()), win, cx)
+                        })
+                        .child(label),
                 ),
             );
         }
 
-        content.child(Self::render_yaml_section(&resource, colors))
+        if let Some(usage) = &props.usage {
+            let on_action = on_action.clone();
+            content = content.child(Self::render_usage(
+                usage,
+                move |window, win, cx| on_action(DetailAction::UsageWindow(window), win, cx),
+                colors,
+            ));
+        }
+        content = content.child(Self::render_events(&props.events, colors));
+
+        content.child(Self::render_yaml_section(
+            &resource,
+            props.yaml_editor.as_ref(),
+            &props.editor_settings,
+            move |action, win, cx| on_action(DetailAction::Yaml(action), win, cx),
+            colors,
+        ))
+    }
+
+    /// CPU and memory history of a pod or node, from metrics-server samples.
+    fn render_usage(
+        usage: &UsageHistory,
+        on_window: impl Fn(HistoryWindow, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(colors.text_primary)
+                    .pb_2()
+                    .border_b_1()
+                    .border_color(colors.border)
+                    .child("Usage"),
+            )
+            .child(div().flex().h(px(220.0)).child(usage.render(
+                "detail-usage-window",
+                on_window,
+                colors,
+            )))
+    }
+
+    /// Events the cluster recorded about the object.
+    fn render_events(events: &[EventGroup], colors: &ThemeColors) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(colors.text_primary)
+                    .pb_2()
+                    .border_b_1()
+                    .border_color(colors.border)
+                    .child(format!("Events ({})", events.len())),
+            )
+            .when(events.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(colors.text_muted)
+                        .child("No recent events."),
+                )
+            })
+            .children(
+                events
+                    .iter()
+                    .map(|group| render_event_row(group, false, colors)),
+            )
+    }
+
+    /// Changes that can be made to any object.
+    fn render_actions(
+        resource: &ResourceItem,
+        on_delete: impl Fn(ResourceItem, &mut Window, &mut App) + 'static,
+        on_node_action: impl Fn(NodeAction, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        let delete_resource = resource.clone();
+        let node_button = |id: &'static str, label: &'static str, action: NodeAction| {
+            let on_node_action = on_node_action.clone();
+            div()
+                .id(id)
+                .px_4()
+                .py_2()
+                .bg(colors.bg_element)
+                .rounded_md()
+                .text_sm()
+                .text_color(colors.text_primary)
+                .cursor(CursorStyle::PointingHand)
+                .hover({
+                    let cloned = colors.clone();
+                    move |style| style.bg(cloned.bg_element_hover)
+                })
+                .on_click(move |_, win, cx| on_node_action(action.clone(), win, cx))
+                .child(label)
+        };
+        let cordoned = resource.is_cordoned();
+
+        div()
+            .flex()
+            .justify_end()
+            .gap_2()
+            .when(resource.kind == ResourceKind::Node, |this| {
+                this.child(if cordoned {
+                    node_button("uncordon-btn", "Uncordon", NodeAction::SetCordoned(false))
+                } else {
+                    node_button("cordon-btn", "Cordon", NodeAction::SetCordoned(true))
+                })
+                .child(node_button("drain-btn", "Drain…", NodeAction::Drain))
+            })
+            .child(
+                div()
+                    .id("delete-btn")
+                    .px_4()
+                    .py_2()
+                    .bg(colors.bg_element)
+                    .rounded_md()
+                    .text_sm()
+                    .text_color(colors.status_error)
+                    .cursor(CursorStyle::PointingHand)
+                    .hover({
+                        let cloned = colors.clone();
+                        move |style| style.bg(cloned.bg_element_hover)
+                    })
+                    .on_click(move |_, win, cx| on_delete(delete_resource.clone(), win, cx))
+                    .child("Delete"),
+            )
     }
 
     fn render_info_section(
@@ -162,9 +586,129 @@ impl DetailView {
             }))
     }
 
-    fn render_yaml_section(resource: &ResourceItem, colors: &ThemeColors) -> impl IntoElement {
-        let yaml = serde_yaml::to_string(&resource.metadata)
-            .unwrap_or_else(|_| "Failed to serialize".to_string());
+    fn render_yaml_section(
+        resource: &ResourceItem,
+        editor: Option<&YamlEditor>,
+        settings: &EditorSettings,
+        on_action: impl Fn(YamlAction, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        let read_only = match editor {
+            Some(_) => String::new(),
+            None => serde_yaml::to_string(&resource.metadata)
+                .unwrap_or_else(|_| "Failed to serialize".to_string()),
+        };
+
+        let button = |id: &'static str, label: String, action: YamlAction| {
+            let on_action = on_action.clone();
+            div()
+                .id(id)
+                .px_2()
+                .py_0p5()
+                .rounded_md()
+                .text_xs()
+                .font_weight(FontWeight::NORMAL)
+                .text_color(colors.text_secondary)
+                .cursor(CursorStyle::PointingHand)
+                .hover({
+                    let cloned = colors.clone();
+                    move |style| {
+                        style
+                            .bg(cloned.bg_element_hover)
+                            .text_color(cloned.text_primary)
+                    }
+                })
+                .on_click(move |_, win, cx| on_action(action.clone(), win, cx))
+                .child(label)
+        };
+
+        let controls = match editor {
+            None => div().child(button("yaml-edit", "Edit".to_string(), YamlAction::Edit)),
+            Some(editor) if editor.saving => div()
+                .text_xs()
+                .font_weight(FontWeight::NORMAL)
+                .text_color(colors.text_muted)
+                .child("Saving…"),
+            Some(YamlEditor {
+                review: Some(review),
+                ..
+            }) => div()
+                .flex()
+                .items_center()
+                .gap_1()
+                .child(
+                    div()
+                        .pr_2()
+                        .text_xs()
+                        .font_weight(FontWeight::NORMAL)
+                        .text_color(colors.text_muted)
+                        .child(format!(
+                            "Dry run: +{} −{}",
+                            review.diff.added, review.diff.removed
+                        )),
+                )
+                .child(button(
+                    "yaml-back",
+                    "Back".to_string(),
+                    YamlAction::BackToEdit,
+                ))
+                .child(button(
+                    "yaml-confirm",
+                    match review.force {
+                        true => format!("Confirm Force {}", review.mode.label()),
+                        false => format!("Confirm {}", review.mode.label()),
+                    },
+                    YamlAction::ConfirmSave,
+                )),
+            Some(editor) => div()
+                .flex()
+                .items_center()
+                .gap_1()
+                .when_some(editor.status.clone(), |this, status| {
+                    let (text, color) = match status {
+                        Ok(text) => (text, colors.status_ok),
+                        Err(text) => (text, colors.status_error),
+                    };
+                    this.child(
+                        div()
+                            .max_w(px(320.0))
+                            .overflow_hidden()
+                            .whitespace_nowrap()
+                            .pr_2()
+                            .text_xs()
+                            .font_weight(FontWeight::NORMAL)
+                            .text_color(color)
+                            .child(text),
+                    )
+                })
+                .when(editor.conflict, |this| {
+                    this.child(button(
+                        "yaml-reload",
+                        "Reload".to_string(),
+                        YamlAction::Reload,
+                    ))
+                    .child(button(
+                        "yaml-force",
+                        format!("Force {}", editor.mode.label()),
+                        YamlAction::ForceSave,
+                    ))
+                })
+                .child(button(
+                    "yaml-cancel",
+                    "Cancel".to_string(),
+                    YamlAction::Cancel,
+                ))
+                .child(button(
+                    "yaml-replace",
+                    SaveMode::Replace.label().to_string(),
+                    YamlAction::Save(SaveMode::Replace),
+                ))
+                .child(button(
+                    "yaml-apply",
+                    SaveMode::Apply.label().to_string(),
+                    YamlAction::Save(SaveMode::Apply),
+                )),
+        };
 
         div()
             .flex()
@@ -172,29 +716,38 @@ impl DetailView {
             .gap_2()
             .child(
                 div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
                     .text_sm()
                     .font_weight(FontWeight::SEMIBOLD)
                     .text_color(colors.text_primary)
                     .pb_2()
                     .border_b_1()
                     .border_color(colors.border)
-                    .child("YAML"),
-            )
-            .child(
-                div()
-                    .p_3()
-                    .bg(if Hsla::from(colors.bg_app).l > 0.5 {
-                        Hsla::from(colors.bg_element_active).opacity(0.1) // Light mode, slight tint
-                    } else {
-                        Hsla::from(colors.bg_sidebar) // Dark mode
-                    })
-                    .rounded_md()
-                    .text_xs()
-                    .font_family("'JetBrains Mono', 'Fira Code', 'SF Mono', Menlo, Monaco, 'Courier New', monospace")
-                    .text_color(colors.text_secondary)
-                    .max_h(px(500.0))
-                    .line_height(relative(1.6))
-                    .child(yaml),
+                    .child("YAML")
+                    .child(controls),
             )
+            .child(match editor.and_then(|editor| editor.review.as_ref()) {
+                Some(review) => review.diff.render(colors).into_any_element(),
+                None => {
+                    render_yaml(&read_only, editor, settings, on_action, colors).into_any_element()
+                }
+            })
     }
 }
+
+/// "ACCESS MODES" -> "Access Modes"
+fn title_case(column: &str) -> String {
+    column
+        .split(' ')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_string() + &chars.as_str().to_lowercase(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,288 @@
+use crate::kubernetes::{DrainEvent, DrainOptions, DrainPod, DrainPodState};
+use crate::theme::ThemeColors;
+use crate::ui::chip::chip;
+use crate::ui::delete_dialog::{grace_label, option_row, GRACE_PERIODS};
+use gpui::*;
+
+/// Node maintenance buttons of the detail view.
+#[derive(Clone, Debug, PartialEq)]
+pub enum NodeAction {
+    SetCordoned(bool),
+    /// Open the drain dialog
+    Drain,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DrainAction {
+    SetGracePeriod(Option<u32>),
+    ToggleForce,
+    ToggleDeleteEmptyDirData,
+    Start,
+    /// Stop evicting; the node stays cordoned
+    Stop,
+    Close,
+}
+
+/// Options, then per-pod progress, of draining one node.
+#[derive(Clone, Debug)]
+pub struct DrainDialog {
+    pub node: String,
+    pub options: DrainOptions,
+    pub running: bool,
+    /// Empty until the drain has listed the node's pods
+    pub pods: Vec<DrainPod>,
+    /// Set once the drain ends; `Err` carries why pods were left behind
+    pub outcome: Option<Result<(), String>>,
+}
+
+impl DrainDialog {
+    pub fn new(node: String) -> Self {
+        Self {
+            node,
+            options: DrainOptions::default(),
+            running: false,
+            pods: Vec::new(),
+            outcome: None,
+        }
+    }
+
+    pub fn apply(&mut self, event: DrainEvent) {
+        match event {
+            DrainEvent::Pods(pods) => self.pods = pods,
+            DrainEvent::Update {
+                namespace,
+                name,
+                state,
+            } => {
+                if let Some(pod) = self
+                    .pods
+                    .iter_mut()
+                    .find(|p| p.namespace == namespace && p.name == name)
+                {
+                    pod.state = state;
+                }
+            }
+            DrainEvent::Finished(error) => {
+                self.running = false;
+                self.outcome = Some(match error {
+                    Some(message) => Err(message),
+                    None => Ok(()),
+                });
+            }
+        }
+    }
+
+    pub fn render(
+        &self,
+        on_action: impl Fn(DrainAction, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        let options = &self.options;
+        let started = self.running || self.outcome.is_some();
+
+        let option_chip =
+            |id: (&'static str, usize), label: String, selected: bool, action: DrainAction| {
+                let on_action = on_action.clone();
+                chip(id, label, selected, colors)
+                    .on_click(move |_, win, cx| on_action(action.clone(), win, cx))
+            };
+        let check =
+            |checked: bool, label: &str| format!("{} {}", if checked { "☑" } else { "☐" }, label);
+        let button = |id: &'static str, label: &'static str, primary: bool, action: DrainAction| {
+            let on_action = on_action.clone();
+            div()
+                .id(id)
+                .px_4()
+                .py_2()
+                .rounded_md()
+                .bg(if primary {
+                    colors.status_warning
+                } else {
+                    colors.bg_element
+                })
+                .text_color(if primary {
+                    colors.text_inverse
+                } else {
+                    colors.text_primary
+                })
+                .when(primary, |this| this.font_weight(FontWeight::SEMIBOLD))
+                .cursor(CursorStyle::PointingHand)
+                .on_click(move |_, win, cx| on_action(action.clone(), win, cx))
+                .child(label)
+        };
+
+        let body = if started {
+            self.render_progress(colors).into_any_element()
+        } else {
+            let grace_chips = std::iter::once(None)
+                .chain(GRACE_PERIODS.into_iter().map(Some))
+                .enumerate()
+                .map(|(ix, period)| {
+                    option_chip(
+                        ("drain-grace", ix),
+                        grace_label(period),
+                        options.grace_period == period,
+                        DrainAction::SetGracePeriod(period),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            div()
+                .flex()
+                .flex_col()
+                .gap_3()
+                .child(div().text_xs().text_color(colors.text_muted).child(
+                    "The node is cordoned, then its pods are evicted. Evictions a \
+                     PodDisruptionBudget refuses are retried; DaemonSet and static \
+                     pods stay on the node.",
+                ))
+                .child(option_row("Grace period", grace_chips, colors))
+                .child(option_row(
+                    "Unmanaged pods",
+                    vec![option_chip(
+                        ("drain-force", 0),
+                        check(options.force, "Evict pods without a controller"),
+                        options.force,
+                        DrainAction::ToggleForce,
+                    )],
+                    colors,
+                ))
+                .child(option_row(
+                    "Local storage",
+                    vec![option_chip(
+                        ("drain-emptydir", 0),
+                        check(options.delete_emptydir_data, "Delete emptyDir data"),
+                        options.delete_emptydir_data,
+                        DrainAction::ToggleDeleteEmptyDirData,
+                    )],
+                    colors,
+                ))
+                .into_any_element()
+        };
+
+        let buttons = div().flex().justify_end().gap_2().pt_2();
+        let buttons = if self.running {
+            buttons.child(button("drain-stop", "Stop", false, DrainAction::Stop))
+        } else if self.outcome.is_some() {
+            buttons.child(button("drain-close", "Close", false, DrainAction::Close))
+        } else {
+            buttons
+                .child(button("drain-cancel", "Cancel", false, DrainAction::Close))
+                .child(button("drain-start", "Drain", true, DrainAction::Start))
+        };
+
+        // Backdrop; swallows clicks meant for the views underneath
+        div()
+            .id("drain-dialog-backdrop")
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full()
+            .flex()
+            .items_center()
+            .justify_center()
+            .bg(hsla(0.0, 0.0, 0.0, 0.45))
+            .occlude()
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_3()
+                    .w(px(560.0))
+                    .p_4()
+                    .rounded_lg()
+                    .bg(colors.bg_panel)
+                    .border_1()
+                    .border_color(colors.border)
+                    .text_sm()
+                    .child(
+                        div()
+                            .text_lg()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(colors.text_primary)
+                            .child(format!("Drain node {}", self.node)),
+                    )
+                    .child(body)
+                    .child(buttons),
+            )
+    }
+
+    fn render_progress(&self, colors: &ThemeColors) -> impl IntoElement {
+        let evicted = self
+            .pods
+            .iter()
+            .filter(|p| p.state == DrainPodState::Evicted)
+            .count();
+        let to_evict = self
+            .pods
+            .iter()
+            .filter(|p| !matches!(p.state, DrainPodState::Skipped(_)))
+            .count();
+
+        let summary = match &self.outcome {
+            None if self.pods.is_empty() => {
+                ("Cordoning and listing pods…".to_string(), colors.text_muted)
+            }
+            None => (
+                format!("Evicted {} of {} pods…", evicted, to_evict),
+                colors.status_info,
+            ),
+            Some(Ok(())) => (
+                format!("Drained: {} pods evicted", evicted),
+                colors.status_ok,
+            ),
+            Some(Err(message)) => (message.clone(), colors.status_error),
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(div().text_color(summary.1).child(summary.0))
+            .child(
+                div()
+                    .id("drain-pods")
+                    .flex()
+                    .flex_col()
+                    .gap_0p5()
+                    .p_2()
+                    .rounded_md()
+                    .bg(colors.bg_element)
+                    .max_h(px(320.0))
+                    .overflow_y_scroll()
+                    .children(self.pods.iter().map(|pod| {
+                        let (state, color) = match &pod.state {
+                            DrainPodState::Pending => ("Waiting".to_string(), colors.text_muted),
+                            DrainPodState::Skipped(reason) => {
+                                (format!("Skipped: {}", reason), colors.text_muted)
+                            }
+                            DrainPodState::Blocked(reason) => {
+                                (format!("Blocked: {}", reason), colors.status_warning)
+                            }
+                            DrainPodState::Terminating => {
+                                ("Terminating…".to_string(), colors.status_info)
+                            }
+                            DrainPodState::Evicted => ("Evicted".to_string(), colors.status_ok),
+                            DrainPodState::Failed(reason) => {
+                                (format!("Failed: {}", reason), colors.status_error)
+                            }
+                        };
+
+                        div()
+                            .flex()
+                            .gap_2()
+                            .text_xs()
+                            .child(
+                                div()
+                                    .w(px(220.0))
+                                    .flex_none()
+                                    .overflow_hidden()
+                                    .whitespace_nowrap()
+                                    .text_color(colors.text_secondary)
+                                    .child(format!("{}/{}", pod.namespace, pod.name)),
+                            )
+                            .child(div().flex_1().text_color(color).child(state))
+                    })),
+            )
+    }
+}
@@ -0,0 +1,68 @@
+use crate::kubernetes::{EventGroup, EventObject, EventTypeFilter};
+use crate::theme::ThemeColors;
+use gpui::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum EventAction {
+    SetFilter(EventTypeFilter),
+    /// Open the object an event is about
+    Open(EventObject),
+}
+
+/// One grouped event; `show_object` adds the `kind/name` it is about.
+pub fn render_event_row(group: &EventGroup, show_object: bool, colors: &ThemeColors) -> Div {
+    div()
+        .flex()
+        .flex_col()
+        .gap_px()
+        .p_2()
+        .rounded_md()
+        .bg(colors.bg_element)
+        .child(
+            div()
+                .flex()
+                .items_center()
+                .gap_2()
+                .text_xs()
+                .child(
+                    div()
+                        .font_weight(FontWeight::BOLD)
+                        .text_color(if group.warning {
+                            colors.status_warning
+                        } else {
+                            colors.text_secondary
+                        })
+                        .child(group.reason.clone()),
+                )
+                .when(show_object, |this| {
+                    this.when_some(group.regarding.as_ref(), |this, object| {
+                        this.child(
+                            div()
+                                .flex_1()
+                                .overflow_hidden()
+                                .whitespace_nowrap()
+                                .text_color(colors.text_muted)
+                                .child(format!("{}/{}", object.kind.to_lowercase(), object.name)),
+                        )
+                    })
+                })
+                .when(!show_object, |this| this.child(div().flex_1()))
+                .when(group.count > 1, |this| {
+                    this.child(
+                        div()
+                            .px_1()
+                            .rounded_sm()
+                            .bg(colors.bg_element_hover)
+                            .text_color(colors.text_secondary)
+                            .child(format!("×{}", group.count)),
+                    )
+                })
+                .child(div().text_color(colors.text_muted).child(group.age())),
+        )
+        .child(
+            div()
+                .text_xs()
+                .text_color(colors.text_secondary)
+                .child(group.note.clone()),
+        )
+}
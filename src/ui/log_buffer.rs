@@ -0,0 +1,450 @@
+use crate::kubernetes::LogLine;
+use crate::ui::log_parse::{FieldFilter, StructuredLog};
+use crate::ui::logs::LogMatcher;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::ops::Range;
+use std::rc::Rc;
+
+/// Lines sampled from the end of the buffer to detect structured logs.
+const STRUCTURED_SAMPLE: usize = 200;
+/// Cap on field names offered as columns.
+const MAX_KNOWN_FIELDS: usize = 30;
+
+/// What the list shows and what search navigation steps through.
+#[derive(Clone, Default)]
+pub struct LogQuery {
+    pub matcher: Option<LogMatcher>,
+    /// Hide lines that don't match `matcher`
+    pub only_matching: bool,
+    pub fields: Vec<FieldFilter>,
+}
+
+impl LogQuery {
+    fn passes_fields(&self, line: &LogLine) -> bool {
+        if self.fields.is_empty() {
+            return true;
+        }
+        let parsed = StructuredLog::parse(&line.text);
+        self.fields
+            .iter()
+            .all(|filter| filter.matches(&line.text, parsed.as_ref()))
+    }
+
+    /// Returns (shown as a row, counted as a search match).
+    fn classify(&self, line: &LogLine) -> (bool, bool) {
+        if !self.passes_fields(line) {
+            return (false, false);
+        }
+        let is_match = self
+            .matcher
+            .as_ref()
+            .is_some_and(|m| m.is_match(&line.text));
+        let shown = is_match || !(self.only_matching && self.matcher.is_some());
+        (shown, is_match)
+    }
+
+    fn filters_rows(&self) -> bool {
+        !self.fields.is_empty() || (self.only_matching && self.matcher.is_some())
+    }
+}
+
+/// Bounded line store behind the virtualized log list. Clones share the same
+/// lines, so the list can read them without copying the buffer every frame.
+#[derive(Clone)]
+pub struct LogBuffer {
+    inner: Rc<RefCell<Inner>>,
+}
+
+struct Inner {
+    lines: VecDeque<LogLine>,
+    max_lines: usize,
+    /// Lines dropped from the front; also the sequence number of `lines[0]`
+    dropped: usize,
+    query: LogQuery,
+    /// Sequence numbers of shown lines; `None` when every line is shown
+    rows: Option<VecDeque<usize>>,
+    /// Sequence numbers of search matches, kept up to date as lines arrive
+    matches: VecDeque<usize>,
+    /// Lines held back while the view is paused, bounded like `lines`
+    pending: VecDeque<LogLine>,
+    /// Extra field names of each of the newest `STRUCTURED_SAMPLE` lines,
+    /// `None` for lines that aren't JSON/logfmt; parsed once on arrival
+    sample: VecDeque<Option<Vec<String>>>,
+    /// Summary of `sample`, updated as lines are appended
+    structured: (bool, Vec<String>),
+}
+
+impl Inner {
+    fn push(&mut self, line: LogLine) {
+        let seq = self.dropped + self.lines.len();
+        let (shown, is_match) = self.query.classify(&line);
+        if let (Some(rows), true) = (self.rows.as_mut(), shown) {
+            rows.push_back(seq);
+        }
+        if is_match {
+            self.matches.push_back(seq);
+        }
+        self.lines.push_back(line);
+    }
+
+    fn trim(&mut self) {
+        let excess = self.lines.len().saturating_sub(self.max_lines);
+        if excess == 0 {
+            return;
+        }
+
+        self.lines.drain(..excess);
+        self.dropped += excess;
+        let dropped = self.dropped;
+        while self.matches.front().is_some_and(|seq| *seq < dropped) {
+            self.matches.pop_front();
+        }
+        if let Some(rows) = self.rows.as_mut() {
+            while rows.front().is_some_and(|seq| *seq < dropped) {
+                rows.pop_front();
+            }
+        }
+    }
+
+    fn sample(&mut self, line: &LogLine) {
+        let keys = StructuredLog::parse(&line.text)
+            .map(|log| log.extra_keys().map(str::to_string).collect());
+        self.sample.push_back(keys);
+        if self.sample.len() > STRUCTURED_SAMPLE.min(self.max_lines) {
+            self.sample.pop_front();
+        }
+    }
+
+    /// Detected when at least half the sampled lines parse.
+    fn summarize_sample(&mut self) {
+        let parsed = self.sample.iter().flatten().count();
+        let mut fields: Vec<String> = Vec::new();
+        for key in self.sample.iter().flatten().flatten() {
+            if fields.len() < MAX_KNOWN_FIELDS && !fields.contains(key) {
+                fields.push(key.clone());
+            }
+        }
+        self.structured = (
+            !self.sample.is_empty() && parsed * 2 >= self.sample.len(),
+            fields,
+        );
+    }
+
+    /// Recomputes rows and matches for the whole buffer.
+    fn rescan(&mut self) {
+        let lines = std::mem::take(&mut self.lines);
+        self.rows = self.query.filters_rows().then(VecDeque::new);
+        self.matches.clear();
+        for line in lines {
+            self.push(line);
+        }
+    }
+}
+
+impl LogBuffer {
+    pub fn new(max_lines: usize) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(Inner {
+                lines: VecDeque::new(),
+                max_lines: max_lines.max(1),
+                dropped: 0,
+                query: LogQuery::default(),
+                rows: None,
+                matches: VecDeque::new(),
+                pending: VecDeque::new(),
+                sample: VecDeque::new(),
+                structured: (false, Vec::new()),
+            })),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.borrow().lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.borrow().lines.is_empty()
+    }
+
+    pub fn max_lines(&self) -> usize {
+        self.inner.borrow().max_lines
+    }
+
+    /// How many of the oldest lines have been evicted.
+    pub fn dropped(&self) -> usize {
+        self.inner.borrow().dropped
+    }
+
+    pub fn line(&self, ix: usize) -> Option<LogLine> {
+        self.inner.borrow().lines.get(ix).cloned()
+    }
+
+    /// Sequence number of line `ix`; stays stable as older lines are dropped.
+    pub fn seq(&self, ix: usize) -> usize {
+        self.inner.borrow().dropped + ix
+    }
+
+    pub fn line_by_seq(&self, seq: usize) -> Option<LogLine> {
+        let inner = self.inner.borrow();
+        let ix = seq.checked_sub(inner.dropped)?;
+        inner.lines.get(ix).cloned()
+    }
+
+    pub fn extend(&self, lines: impl IntoIterator<Item = LogLine>) {
+        let mut inner = self.inner.borrow_mut();
+        for line in lines {
+            inner.sample(&line);
+            inner.push(line);
+        }
+        inner.trim();
+        inner.summarize_sample();
+    }
+
+    /// Holds `lines` back until `release`, dropping the oldest past the bound.
+    pub fn hold(&self, lines: impl IntoIterator<Item = LogLine>) {
+        let mut inner = self.inner.borrow_mut();
+        inner.pending.extend(lines);
+        let excess = inner.pending.len().saturating_sub(inner.max_lines);
+        inner.pending.drain(..excess);
+    }
+
+    /// Appends the held lines to the buffer.
+    pub fn release(&self) {
+        let pending = std::mem::take(&mut self.inner.borrow_mut().pending);
+        self.extend(pending);
+    }
+
+    pub fn pending_len(&self) -> usize {
+        self.inner.borrow().pending.len()
+    }
+
+    pub fn clear(&self) {
+        let mut inner = self.inner.borrow_mut();
+        inner.lines.clear();
+        inner.matches.clear();
+        inner.pending.clear();
+        inner.sample.clear();
+        inner.structured = (false, Vec::new());
+        if let Some(rows) = inner.rows.as_mut() {
+            rows.clear();
+        }
+        inner.dropped = 0;
+    }
+
+    /// Whether most recent lines are JSON/logfmt, and the extra field names
+    /// seen in them, in first-seen order.
+    pub fn structured_sample(&self) -> (bool, Vec<String>) {
+        self.inner.borrow().structured.clone()
+    }
+
+    /// Replaces the query and rescans the buffer once.
+    pub fn set_query(&self, query: LogQuery) {
+        let mut inner = self.inner.borrow_mut();
+        inner.query = query;
+        inner.rescan();
+    }
+
+    pub fn row_count(&self) -> usize {
+        let inner = self.inner.borrow();
+        match &inner.rows {
+            Some(rows) => rows.len(),
+            None => inner.lines.len(),
+        }
+    }
+
+    /// Line index shown at `row`.
+    pub fn row_line(&self, row: usize) -> Option<usize> {
+        let inner = self.inner.borrow();
+        match &inner.rows {
+            Some(rows) => rows.get(row).map(|seq| seq - inner.dropped),
+            None => (row < inner.lines.len()).then_some(row),
+        }
+    }
+
+    /// Row at which line `ix` is shown, if it passes the filters.
+    pub fn line_row(&self, ix: usize) -> Option<usize> {
+        let inner = self.inner.borrow();
+        match &inner.rows {
+            Some(rows) => rows.binary_search(&(inner.dropped + ix)).ok(),
+            None => Some(ix),
+        }
+    }
+
+    /// Copies every loaded line, or only the rows the filters show.
+    pub fn snapshot(&self, filtered_only: bool) -> Vec<LogLine> {
+        let inner = self.inner.borrow();
+        match &inner.rows {
+            Some(rows) if filtered_only => rows
+                .iter()
+                .filter_map(|seq| inner.lines.get(seq - inner.dropped).cloned())
+                .collect(),
+            _ => inner.lines.iter().cloned().collect(),
+        }
+    }
+
+    pub fn match_count(&self) -> usize {
+        self.inner.borrow().matches.len()
+    }
+
+    /// Line index of the `n`th match.
+    pub fn match_line(&self, n: usize) -> Option<usize> {
+        let inner = self.inner.borrow();
+        inner.matches.get(n).map(|seq| seq - inner.dropped)
+    }
+
+    /// Byte ranges to highlight in `text` for the current matcher.
+    pub fn highlight_ranges(&self, text: &str) -> Vec<Range<usize>> {
+        self.inner
+            .borrow()
+            .query
+            .matcher
+            .as_ref()
+            .map(|m| m.ranges(text))
+            .unwrap_or_default()
+    }
+}
+
+impl PartialEq for LogBuffer {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl fmt::Debug for LogBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogBuffer")
+            .field("len", &self.len())
+            .field("dropped", &self.dropped())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(texts: &[&str]) -> Vec<LogLine> {
+        texts
+            .iter()
+            .map(|text| LogLine {
+                pod: "web".to_string(),
+                container: None,
+                text: text.to_string(),
+            })
+            .collect()
+    }
+
+    fn texts(buffer: &LogBuffer) -> Vec<String> {
+        (0..buffer.len())
+            .filter_map(|ix| buffer.line(ix))
+            .map(|line| line.text)
+            .collect()
+    }
+
+    #[test]
+    fn evicts_oldest_lines_past_the_bound() {
+        let buffer = LogBuffer::new(3);
+        buffer.extend(lines(&["a", "b"]));
+        buffer.extend(lines(&["c", "d", "e"]));
+
+        assert_eq!(texts(&buffer), ["c", "d", "e"]);
+        assert_eq!(buffer.dropped(), 2);
+    }
+
+    fn search(needle: &str, only_matching: bool) -> LogQuery {
+        LogQuery {
+            matcher: Some(LogMatcher::Substring(needle.to_string())),
+            only_matching,
+            fields: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn match_lines_follow_eviction() {
+        let buffer = LogBuffer::new(3);
+        buffer.set_query(search("err", false));
+        buffer.extend(lines(&["ERR one", "ok", "err two", "ok"]));
+
+        // "ERR one" was evicted along with its match
+        assert_eq!(buffer.match_count(), 1);
+        assert_eq!(buffer.match_line(0), Some(1));
+
+        buffer.extend(lines(&["err three"]));
+        assert_eq!(buffer.match_count(), 2);
+        assert_eq!(buffer.match_line(0), Some(0));
+        assert_eq!(buffer.match_line(1), Some(2));
+        assert_eq!(buffer.match_line(2), None);
+    }
+
+    #[test]
+    fn set_query_rescans_existing_lines() {
+        let buffer = LogBuffer::new(10);
+        buffer.extend(lines(&["warn a", "info", "warn b"]));
+        assert_eq!(buffer.match_count(), 0);
+
+        buffer.set_query(search("warn", false));
+        assert_eq!(buffer.match_count(), 2);
+        assert_eq!(buffer.match_line(1), Some(2));
+
+        buffer.set_query(LogQuery::default());
+        assert_eq!(buffer.match_count(), 0);
+    }
+
+    #[test]
+    fn rows_show_every_line_without_filters() {
+        let buffer = LogBuffer::new(10);
+        buffer.set_query(search("warn", false));
+        buffer.extend(lines(&["warn a", "info"]));
+
+        assert_eq!(buffer.row_count(), 2);
+        assert_eq!(buffer.row_line(1), Some(1));
+        assert_eq!(buffer.row_line(2), None);
+        assert_eq!(buffer.line_row(1), Some(1));
+    }
+
+    #[test]
+    fn only_matching_rows_index_into_lines() {
+        let buffer = LogBuffer::new(4);
+        buffer.set_query(search("warn", true));
+        buffer.extend(lines(&["warn a", "info", "warn b", "info"]));
+
+        assert_eq!(buffer.row_count(), 2);
+        assert_eq!(buffer.row_line(0), Some(0));
+        assert_eq!(buffer.row_line(1), Some(2));
+        assert_eq!(buffer.line_row(2), Some(1));
+        assert_eq!(buffer.line_row(1), None);
+
+        // Evicting "warn a" shifts both rows and line indices
+        buffer.extend(lines(&["warn c"]));
+        assert_eq!(buffer.row_count(), 2);
+        assert_eq!(buffer.row_line(0), Some(1));
+        assert_eq!(buffer.row_line(1), Some(3));
+        assert_eq!(buffer.line_row(3), Some(1));
+    }
+
+    #[test]
+    fn held_lines_are_bounded_and_released_in_order() {
+        let buffer = LogBuffer::new(2);
+        buffer.extend(lines(&["a"]));
+        buffer.hold(lines(&["b", "c", "d"]));
+
+        assert_eq!(buffer.pending_len(), 2);
+        assert_eq!(texts(&buffer), ["a"]);
+
+        buffer.release();
+        assert_eq!(buffer.pending_len(), 0);
+        assert_eq!(texts(&buffer), ["c", "d"]);
+    }
+
+    #[test]
+    fn clones_share_lines() {
+        let buffer = LogBuffer::new(10);
+        let shared = buffer.clone();
+        buffer.extend(lines(&["a"]));
+
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared, buffer);
+    }
+}
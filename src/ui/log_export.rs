@@ -0,0 +1,96 @@
+use crate::kubernetes::LogLine;
+use crate::ui::log_parse::split_timestamp;
+use serde_json::json;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogExportFormat {
+    #[default]
+    Text,
+    /// One JSON object per line with pod, container and timestamp
+    Ndjson,
+}
+
+impl LogExportFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Text => "Text",
+            Self::Ndjson => "NDJSON",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Text => "log",
+            Self::Ndjson => "ndjson",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Text => Self::Ndjson,
+            Self::Ndjson => Self::Text,
+        }
+    }
+}
+
+/// Export choices made in the log toolbar.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LogExport {
+    pub format: LogExportFormat,
+    /// Export only the lines passing search and field filters
+    pub filtered_only: bool,
+    /// Result of the last copy, save or download
+    pub status: Option<Result<String, String>>,
+}
+
+/// Renders `lines` for a file or the clipboard. Plain text prefixes each line
+/// with its source when `with_source` is set, as the aggregated view does.
+pub fn format_lines<'a>(
+    lines: impl IntoIterator<Item = &'a LogLine>,
+    format: LogExportFormat,
+    with_source: bool,
+) -> String {
+    let mut out = String::new();
+
+    for line in lines {
+        match format {
+            LogExportFormat::Text => {
+                if with_source {
+                    match &line.container {
+                        Some(container) => out.push_str(&format!("{}/{} ", line.pod, container)),
+                        None => out.push_str(&format!("{} ", line.pod)),
+                    }
+                }
+                out.push_str(&line.text);
+            }
+            LogExportFormat::Ndjson => {
+                let (timestamp, message) = split_timestamp(&line.text);
+                let record = json!({
+                    "pod": line.pod,
+                    "container": line.container,
+                    "timestamp": timestamp,
+                    "message": message,
+                });
+                out.push_str(&record.to_string());
+            }
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Default file name offered in the save dialog.
+pub fn suggested_file_name(title: &str, format: LogExportFormat) -> String {
+    let stem: String = title
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}.{}", stem.trim_matches('_'), format.extension())
+}
@@ -0,0 +1,269 @@
+use chrono::DateTime;
+use serde_json::Value;
+
+use crate::ui::logs::LogLevel;
+
+const TIMESTAMP_KEYS: &[&str] = &["ts", "time", "timestamp", "@timestamp", "t"];
+const LEVEL_KEYS: &[&str] = &["level", "lvl", "severity", "log.level"];
+const MESSAGE_KEYS: &[&str] = &["msg", "message", "log"];
+
+/// A JSON or logfmt log line split into its top-level fields, in order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StructuredLog {
+    pub fields: Vec<(String, String)>,
+    /// The decoded object for JSON lines, for the full-object inspector
+    pub json: Option<Value>,
+}
+
+impl StructuredLog {
+    /// Parses `text`, ignoring a leading RFC 3339 timestamp as added by
+    /// `LogParams::timestamps`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let body = strip_timestamp(text).trim();
+        if body.starts_with('{') {
+            parse_json(body)
+        } else {
+            parse_logfmt(body)
+        }
+    }
+
+    /// Looks up `key`; the well-known names `timestamp`, `level` and `msg`
+    /// also match their common aliases.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        let aliases: &[&str] = match key {
+            "timestamp" => TIMESTAMP_KEYS,
+            "level" => LEVEL_KEYS,
+            "msg" => MESSAGE_KEYS,
+            _ => &[],
+        };
+
+        self.field(key)
+            .or_else(|| aliases.iter().find_map(|alias| self.field(alias)))
+    }
+
+    fn field(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Field names other than the timestamp, level and message.
+    pub fn extra_keys(&self) -> impl Iterator<Item = &str> {
+        self.fields.iter().map(|(k, _)| k.as_str()).filter(|k| {
+            !TIMESTAMP_KEYS.contains(k) && !LEVEL_KEYS.contains(k) && !MESSAGE_KEYS.contains(k)
+        })
+    }
+
+    /// Pretty-printed object (JSON) or one `key=value` per line (logfmt).
+    pub fn pretty(&self) -> String {
+        match &self.json {
+            Some(json) => serde_json::to_string_pretty(json).unwrap_or_default(),
+            None => self
+                .fields
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+}
+
+fn strip_timestamp(text: &str) -> &str {
+    split_timestamp(text).1
+}
+
+/// Splits off the RFC 3339 timestamp the API server prefixes when
+/// `timestamps` is requested.
+pub fn split_timestamp(text: &str) -> (Option<&str>, &str) {
+    match text.split_once(' ') {
+        Some((first, rest)) if DateTime::parse_from_rfc3339(first).is_ok() => (Some(first), rest),
+        _ => (None, text),
+    }
+}
+
+fn parse_json(body: &str) -> Option<StructuredLog> {
+    let json: Value = serde_json::from_str(body).ok()?;
+    let fields = json
+        .as_object()?
+        .iter()
+        .map(|(k, v)| {
+            let value = match v {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            (k.clone(), value)
+        })
+        .collect();
+
+    Some(StructuredLog {
+        fields,
+        json: Some(json),
+    })
+}
+
+/// `key=value key2="quoted value"`; every token must be a pair, and at least
+/// two are required so ordinary prose with one `=` isn't picked up.
+fn parse_logfmt(body: &str) -> Option<StructuredLog> {
+    let mut fields = Vec::new();
+    let mut rest = body;
+
+    while !rest.is_empty() {
+        let (key, after_key) = rest.split_once('=')?;
+        if key.is_empty()
+            || !key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-' | '@'))
+        {
+            return None;
+        }
+
+        let (value, after_value) = if let Some(quoted) = after_key.strip_prefix('"') {
+            let end = quoted_end(quoted)?;
+            (quoted[..end].replace("\\\"", "\""), &quoted[end + 1..])
+        } else {
+            let end = after_key.find(' ').unwrap_or(after_key.len());
+            (after_key[..end].to_string(), &after_key[end..])
+        };
+
+        fields.push((key.to_string(), value));
+        rest = after_value.trim_start();
+    }
+
+    (fields.len() >= 2).then_some(StructuredLog { fields, json: None })
+}
+
+/// Index of the closing quote, skipping escaped quotes.
+fn quoted_end(s: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (ix, c) in s.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return Some(ix),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldOp {
+    Eq,
+    NotEq,
+    Contains,
+}
+
+/// One `key=value`, `key!=value` or `key~substring` term of the field filter.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldFilter {
+    pub key: String,
+    pub op: FieldOp,
+    pub value: String,
+}
+
+impl FieldFilter {
+    /// Parses whitespace-separated terms; terms without an operator are skipped.
+    pub fn parse_all(input: &str) -> Vec<Self> {
+        input
+            .split_whitespace()
+            .filter_map(|term| {
+                let (key, op, value) = if let Some((k, v)) = term.split_once("!=") {
+                    (k, FieldOp::NotEq, v)
+                } else if let Some((k, v)) = term.split_once('=') {
+                    (k, FieldOp::Eq, v)
+                } else if let Some((k, v)) = term.split_once('~') {
+                    (k, FieldOp::Contains, v)
+                } else {
+                    return None;
+                };
+                (!key.is_empty()).then(|| Self {
+                    key: key.to_string(),
+                    op,
+                    value: value.to_ascii_lowercase(),
+                })
+            })
+            .collect()
+    }
+
+    /// Case-insensitive. Plain-text lines only have a `level`, taken from
+    /// level detection; every other key is missing for them.
+    pub fn matches(&self, text: &str, parsed: Option<&StructuredLog>) -> bool {
+        let actual = match parsed.and_then(|p| p.get(&self.key)) {
+            Some(value) => Some(value.to_ascii_lowercase()),
+            None if self.key == "level" => {
+                LogLevel::detect(text).map(|level| level.name().to_string())
+            }
+            None => None,
+        };
+
+        match self.op {
+            FieldOp::Eq => actual.is_some_and(|v| v == self.value),
+            FieldOp::NotEq => actual.map_or(true, |v| v != self.value),
+            FieldOp::Contains => actual.is_some_and(|v| v.contains(&self.value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_json_objects() {
+        let log = StructuredLog::parse(r#"{"level":"info","msg":"started","port":8080}"#).unwrap();
+        assert_eq!(log.get("level"), Some("info"));
+        assert_eq!(log.get("msg"), Some("started"));
+        // Non-string values keep their JSON text
+        assert_eq!(log.get("port"), Some("8080"));
+        assert!(log.json.is_some());
+        assert_eq!(log.extra_keys().collect::<Vec<_>>(), ["port"]);
+    }
+
+    #[test]
+    fn rejects_non_object_json() {
+        assert_eq!(StructuredLog::parse("[1, 2]"), None);
+        assert_eq!(StructuredLog::parse("{not json"), None);
+    }
+
+    #[test]
+    fn parses_logfmt_with_quoted_values() {
+        let log =
+            StructuredLog::parse(r#"lvl=warn msg="disk \"data\" almost full" used=91%"#).unwrap();
+        assert_eq!(
+            log.fields,
+            [
+                ("lvl".to_string(), "warn".to_string()),
+                ("msg".to_string(), r#"disk "data" almost full"#.to_string()),
+                ("used".to_string(), "91%".to_string()),
+            ]
+        );
+        assert_eq!(log.get("level"), Some("warn"));
+        assert!(log.json.is_none());
+    }
+
+    #[test]
+    fn rejects_prose_as_logfmt() {
+        assert_eq!(StructuredLog::parse("retrying with timeout=5s"), None);
+        assert_eq!(StructuredLog::parse("a=1 and b=2"), None);
+        assert_eq!(StructuredLog::parse(r#"msg="unterminated a=1"#), None);
+    }
+
+    #[test]
+    fn skips_leading_timestamp() {
+        let log = StructuredLog::parse("2024-05-01T10:00:00Z level=error msg=boom").unwrap();
+        assert_eq!(log.get("level"), Some("error"));
+        assert_eq!(log.get("timestamp"), None);
+    }
+
+    #[test]
+    fn field_filters_match_case_insensitively() {
+        let filters = FieldFilter::parse_all("level=ERROR user!=bob path~API skipped");
+        assert_eq!(filters.len(), 3);
+
+        let log = StructuredLog::parse("level=error user=alice path=/api/v1").unwrap();
+        assert!(filters.iter().all(|f| f.matches("", Some(&log))));
+
+        let other = StructuredLog::parse("level=error user=bob path=/api/v1").unwrap();
+        assert!(!filters[1].matches("", Some(&other)));
+    }
+}
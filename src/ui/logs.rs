@@ -1,48 +1,739 @@
+use crate::kubernetes::{ContainerInfo, LogLine, LogOptions};
 use crate::theme::ThemeColors;
+use crate::ui::chip::chip;
 use crate::ui::glass::{GlassExt, GlassStyle};
+use crate::ui::log_buffer::{LogBuffer, LogQuery};
+use crate::ui::log_export::{format_lines, LogExport};
+use crate::ui::log_parse::{FieldFilter, StructuredLog};
+use crate::ui::text_field::TextField;
 use gpui::*;
+use regex::{Regex, RegexBuilder};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+
+const TAIL_CHOICES: &[(Option<i64>, &str)] = &[
+    (Some(100), "100"),
+    (Some(1000), "1000"),
+    (Some(10000), "10000"),
+    (None, "All"),
+];
+
+const SINCE_CHOICES: &[(Option<i64>, &str)] = &[
+    (None, "All time"),
+    (Some(5 * 60), "5m"),
+    (Some(60 * 60), "1h"),
+    (Some(24 * 60 * 60), "24h"),
+];
+
+/// What the log view is reading from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LogTarget {
+    Pod(String),
+    /// Every pod matched by a workload's or service's selector
+    Selector {
+        owner: String,
+        selector: String,
+    },
+}
+
+impl LogTarget {
+    pub fn title(&self) -> String {
+        match self {
+            Self::Pod(name) => name.clone(),
+            Self::Selector { owner, selector } => format!("{} ({})", owner, selector),
+        }
+    }
+}
+
+/// In-view search over the loaded lines.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LogSearch {
+    pub query: TextField,
+    pub regex: bool,
+    pub only_matching: bool,
+    /// Position in the match list last jumped to
+    pub current: Option<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LogAction {
+    FocusSearch,
+    FocusFilter,
+    ToggleRegex,
+    ToggleOnlyMatching,
+    Next,
+    Previous,
+    ToggleColumns,
+    ToggleField(String),
+    /// Open the inspector for the line with this sequence number
+    Inspect(usize),
+    CloseInspector,
+    CycleExportFormat,
+    ToggleExportFiltered,
+    CopyAll,
+    SaveAs,
+    /// Fetch the whole log from the API server rather than the buffer
+    DownloadFull,
+}
+
+const SOURCE_WIDTH: f32 = 160.0;
+const TIME_WIDTH: f32 = 180.0;
+const LEVEL_WIDTH: f32 = 60.0;
+const FIELD_WIDTH: f32 = 140.0;
+
+#[derive(Clone)]
+pub enum LogMatcher {
+    /// Lowercased needle; matched ASCII-case-insensitively
+    Substring(String),
+    Regex(Regex),
+}
+
+impl LogMatcher {
+    pub fn is_match(&self, text: &str) -> bool {
+        match self {
+            Self::Substring(needle) => text.to_ascii_lowercase().contains(needle.as_str()),
+            Self::Regex(re) => re.is_match(text),
+        }
+    }
+
+    /// Byte ranges of every match in `text`.
+    pub fn ranges(&self, text: &str) -> Vec<Range<usize>> {
+        match self {
+            // ASCII lowercasing keeps byte offsets aligned with `text`
+            Self::Substring(needle) => text
+                .to_ascii_lowercase()
+                .match_indices(needle.as_str())
+                .map(|(start, m)| start..start + m.len())
+                .collect(),
+            Self::Regex(re) => re
+                .find_iter(text)
+                .filter(|m| !m.is_empty())
+                .map(|m| m.range())
+                .collect(),
+        }
+    }
+}
+
+impl LogSearch {
+    /// `None` when there is no query; `Err` for an invalid regex.
+    pub fn matcher(&self) -> Option<Result<LogMatcher, String>> {
+        let query = &self.query.text;
+        if query.is_empty() {
+            return None;
+        }
+
+        Some(if self.regex {
+            RegexBuilder::new(query)
+                .case_insensitive(true)
+                .build()
+                .map(LogMatcher::Regex)
+                .map_err(|e| e.to_string())
+        } else {
+            Ok(LogMatcher::Substring(query.to_ascii_lowercase()))
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl LogLevel {
+    /// Finds a level marker near the start of the line: an upper-case word
+    /// such as `ERROR`, or the value of a `level=`/`"level":` field.
+    pub fn detect(text: &str) -> Option<Self> {
+        let mut end = text.len().min(200);
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        let head = &text[..end];
+        let mut after_level_key = false;
+
+        for token in head
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|t| !t.is_empty())
+        {
+            let is_upper = token.chars().all(|c| c.is_ascii_uppercase());
+            if is_upper || after_level_key {
+                let level = match token.to_ascii_lowercase().as_str() {
+                    "error" | "err" | "fatal" | "panic" | "critical" | "crit" => Some(Self::Error),
+                    "warn" | "warning" => Some(Self::Warn),
+                    "info" => Some(Self::Info),
+                    "debug" | "trace" => Some(Self::Debug),
+                    _ => None,
+                };
+                if level.is_some() {
+                    return level;
+                }
+            }
+            after_level_key = matches!(
+                token.to_ascii_lowercase().as_str(),
+                "level" | "lvl" | "severity"
+            );
+        }
+        None
+    }
+
+    /// Reads a structured `level` value such as `warning` or `E`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "error" | "err" | "e" | "fatal" | "panic" | "critical" | "crit" => Some(Self::Error),
+            "warn" | "warning" | "w" => Some(Self::Warn),
+            "info" | "i" => Some(Self::Info),
+            "debug" | "trace" | "d" => Some(Self::Debug),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warn => "warn",
+            Self::Info => "info",
+            Self::Debug => "debug",
+        }
+    }
+
+    pub fn color(&self, colors: &ThemeColors) -> Rgba {
+        match self {
+            Self::Error => colors.status_error,
+            Self::Warn => colors.status_warning,
+            Self::Info => colors.status_info,
+            Self::Debug => colors.text_muted,
+        }
+    }
+}
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct LogView {
-    pub pod_name: String,
+    pub target: LogTarget,
     pub namespace: String,
-    pub logs: Vec<String>,
+    pub logs: LogBuffer,
     pub glass_style: GlassStyle,
+    pub containers: Vec<ContainerInfo>,
+    pub options: LogOptions,
+    /// Follow stream is still connected
+    pub streaming: bool,
+    /// Display is frozen; new lines are held in `logs` until resumed
+    pub paused: bool,
+    pub search: LogSearch,
+    /// Field filter terms such as `level=error`
+    pub field_filter: TextField,
+    /// User choice for the column view; `None` follows detection
+    pub column_view: Option<bool>,
+    /// Extra fields shown as columns after time, level and message
+    pub columns: Vec<String>,
+    /// Sequence number of the line shown in the inspector
+    pub inspected: Option<usize>,
+    pub export: LogExport,
 }
 
 impl LogView {
-    pub fn new(pod_name: String, namespace: String, glass_style: GlassStyle) -> Self {
+    pub fn new(
+        target: LogTarget,
+        namespace: String,
+        containers: Vec<ContainerInfo>,
+        options: LogOptions,
+        max_lines: usize,
+        glass_style: GlassStyle,
+    ) -> Self {
         Self {
-            pod_name,
+            target,
             namespace,
-            logs: vec!["Loading logs...".to_string()],
+            logs: LogBuffer::new(max_lines),
             glass_style,
+            containers,
+            options,
+            streaming: true,
+            paused: false,
+            search: LogSearch::default(),
+            field_filter: TextField::default(),
+            column_view: None,
+            columns: Vec::new(),
+            inspected: None,
+            export: LogExport::default(),
         }
     }
 
-    pub fn set_logs(&mut self, logs: Vec<String>) {
-        self.logs = logs;
+    /// Loaded lines in the chosen export format, and how many there are.
+    pub fn export_text(&self) -> (String, usize) {
+        let lines = self.logs.snapshot(self.export.filtered_only);
+        let text = format_lines(&lines, self.export.format, self.has_many_sources());
+        (text, lines.len())
+    }
+
+    /// Lines come from several pods or containers and need a source prefix.
+    pub fn has_many_sources(&self) -> bool {
+        matches!(self.target, LogTarget::Selector { .. })
+    }
+
+    /// Number of rows the list shows after search and field filters.
+    pub fn row_count(&self) -> usize {
+        self.logs.row_count()
+    }
+
+    /// The text field receiving key presses, if any.
+    pub fn focused_field(&mut self) -> Option<&mut TextField> {
+        if self.search.query.focused {
+            Some(&mut self.search.query)
+        } else if self.field_filter.focused {
+            Some(&mut self.field_filter)
+        } else {
+            None
+        }
+    }
+
+    /// Whether most recent lines are JSON/logfmt, and the extra field names
+    /// seen in them, in first-seen order.
+    pub fn structured_sample(&self) -> (bool, Vec<String>) {
+        self.logs.structured_sample()
+    }
+
+    /// Keeps the newest line in view while following.
+    pub fn scroll_to_end(&self, scroll_handle: &UniformListScrollHandle) {
+        if let Some(last) = self.row_count().checked_sub(1) {
+            scroll_handle.scroll_to_item(last, ScrollStrategy::Top);
+        }
+    }
+
+    /// Re-applies search and field filters after either changed.
+    pub fn query_changed(&mut self) {
+        self.search.current = None;
+        self.logs.set_query(LogQuery {
+            matcher: self.search.matcher().and_then(Result::ok),
+            only_matching: self.search.only_matching,
+            fields: FieldFilter::parse_all(&self.field_filter.text),
+        });
+    }
+
+    /// Moves to the next/previous match. Returns the row to scroll to.
+    pub fn step_match(&mut self, forward: bool) -> Option<usize> {
+        let count = self.logs.match_count();
+        if count == 0 {
+            self.search.current = None;
+            return None;
+        }
+
+        let next = match self.search.current {
+            Some(current) if forward => (current + 1) % count,
+            Some(current) => (current + count - 1) % count,
+            None if forward => 0,
+            None => count - 1,
+        };
+        self.search.current = Some(next);
+
+        self.logs
+            .match_line(next)
+            .and_then(|ix| self.logs.line_row(ix))
+    }
+
+    /// Appends streamed lines. Returns true if the visible log changed.
+    pub fn append_lines(&mut self, lines: Vec<LogLine>) -> bool {
+        if self.paused {
+            self.logs.hold(lines);
+            false
+        } else {
+            self.logs.extend(lines);
+            true
+        }
+    }
+
+    pub fn toggle_paused(&mut self) {
+        self.paused = !self.paused;
+        if !self.paused {
+            self.logs.release();
+        }
+    }
+
+    /// Clears the buffer for a fresh read with `options`.
+    pub fn reset(&mut self, options: LogOptions) {
+        self.options = options;
+        self.logs.clear();
+        self.paused = false;
+        self.streaming = true;
+        self.search.current = None;
+        self.inspected = None;
+    }
+
+    pub fn end_stream(&mut self) {
+        self.streaming = false;
     }
 
     pub fn render(
         &self,
+        scroll_handle: &UniformListScrollHandle,
         on_close: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static + Clone,
+        on_toggle_pause: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+        on_options_change: impl Fn(LogOptions, &mut Window, &mut App) + 'static + Clone,
+        on_action: impl Fn(LogAction, &mut Window, &mut App) + 'static + Clone,
         colors: &ThemeColors,
     ) -> impl IntoElement {
+        let matcher = self.search.matcher();
+        let current_line = self
+            .search
+            .current
+            .and_then(|current| self.logs.match_line(current));
+        let (detected, known_fields) = self.structured_sample();
+        let column_view = self.column_view.unwrap_or(detected);
+
         div()
             .flex()
             .flex_col()
             .size_full()
             .glass_panel(self.glass_style, colors)
-            .child(self.render_header(on_close, colors))
-            .child(self.render_log_content(colors))
+            .child(self.render_header(on_close, on_toggle_pause, colors))
+            .child(self.render_toolbar(on_options_change, on_action.clone(), colors))
+            .child(self.render_search_bar(&matcher, column_view, on_action.clone(), colors))
+            .when(column_view && !known_fields.is_empty(), |this| {
+                this.child(self.render_field_chips(known_fields, on_action.clone(), colors))
+            })
+            .child(self.render_log_content(
+                scroll_handle,
+                current_line,
+                column_view,
+                on_action.clone(),
+                colors,
+            ))
+            .children(self.render_inspector(on_action, colors))
+    }
+
+    fn render_search_bar(
+        &self,
+        matcher: &Option<Result<LogMatcher, String>>,
+        column_view: bool,
+        on_action: impl Fn(LogAction, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        let search = &self.search;
+        let (summary, summary_color) = match matcher {
+            None => (String::new(), colors.text_muted),
+            Some(Err(_)) => ("Invalid regex".to_string(), colors.status_error),
+            Some(Ok(_)) => {
+                let count = self.logs.match_count();
+                let summary = match search.current {
+                    Some(current) if count > 0 => format!("{}/{}", current + 1, count),
+                    _ => format!("{} matches", count),
+                };
+                (summary, colors.text_muted)
+            }
+        };
+
+        let action = |action: LogAction| {
+            let on_action = on_action.clone();
+            move |_: &ClickEvent, win: &mut Window, cx: &mut App| on_action(action.clone(), win, cx)
+        };
+
+        div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .px_4()
+            .py_2()
+            .border_b_1()
+            .border_color(colors.border)
+            .child(
+                search
+                    .query
+                    .render("log-search", "Search logs…", colors)
+                    .on_click(action(LogAction::FocusSearch)),
+            )
+            .child(
+                chip("log-search-regex", ".*".to_string(), search.regex, colors)
+                    .on_click(action(LogAction::ToggleRegex)),
+            )
+            .child(
+                chip(
+                    "log-search-only-matching",
+                    "Only matching".to_string(),
+                    search.only_matching,
+                    colors,
+                )
+                .on_click(action(LogAction::ToggleOnlyMatching)),
+            )
+            .child(
+                chip("log-search-prev", "↑".to_string(), false, colors)
+                    .on_click(action(LogAction::Previous)),
+            )
+            .child(
+                chip("log-search-next", "↓".to_string(), false, colors)
+                    .on_click(action(LogAction::Next)),
+            )
+            .child(div().text_xs().text_color(summary_color).child(summary))
+            .child(div().w(px(1.0)).h(px(16.0)).bg(colors.border))
+            .child(
+                self.field_filter
+                    .render("log-field-filter", "level=error msg~timeout", colors)
+                    .on_click(action(LogAction::FocusFilter)),
+            )
+            .child(
+                chip("log-columns", "Columns".to_string(), column_view, colors)
+                    .on_click(action(LogAction::ToggleColumns)),
+            )
+    }
+
+    /// Fields found in recent lines; clicking one toggles its column.
+    fn render_field_chips(
+        &self,
+        fields: Vec<String>,
+        on_action: impl Fn(LogAction, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .gap_1()
+            .px_4()
+            .py_1()
+            .border_b_1()
+            .border_color(colors.border)
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(colors.text_muted)
+                    .mr_1()
+                    .child("Fields:"),
+            )
+            .children(fields.into_iter().map(|field| {
+                let active = self.columns.contains(&field);
+                let on_action = on_action.clone();
+                chip(
+                    format!("log-field-{}", field),
+                    field.clone(),
+                    active,
+                    colors,
+                )
+                .on_click(move |_, win, cx| {
+                    on_action(LogAction::ToggleField(field.clone()), win, cx)
+                })
+            }))
+    }
+
+    /// Full object of the selected line: pretty JSON, logfmt pairs or raw text.
+    fn render_inspector(
+        &self,
+        on_action: impl Fn(LogAction, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> Option<impl IntoElement> {
+        let line = self.logs.line_by_seq(self.inspected?)?;
+        let body = StructuredLog::parse(&line.text)
+            .map(|log| log.pretty())
+            .unwrap_or_else(|| line.text.clone());
+        let source = match &line.container {
+            Some(container) => format!("{}/{}", line.pod, container),
+            None => line.pod.clone(),
+        };
+
+        Some(
+            div()
+                .flex()
+                .flex_col()
+                .flex_none()
+                .h(px(220.0))
+                .border_t_1()
+                .border_color(colors.border)
+                .child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .justify_between()
+                        .px_4()
+                        .py_1()
+                        .child(div().text_xs().text_color(colors.text_muted).child(source))
+                        .child(
+                            chip("log-inspector-close", "✕".to_string(), false, colors).on_click(
+                                move |_, win, cx| on_action(LogAction::CloseInspector, win, cx),
+                            ),
+                        ),
+                )
+                .child(
+                    div()
+                        .id("log-inspector")
+                        .flex_1()
+                        .overflow_y_scroll()
+                        .px_4()
+                        .pb_2()
+                        .font_family("'JetBrains Mono', 'Fira Code', monospace")
+                        .text_xs()
+                        .text_color(colors.text_primary)
+                        .children(
+                            body.lines()
+                                .map(|l| div().child(l.to_string()))
+                                .collect::<Vec<_>>(),
+                        ),
+                ),
+        )
+    }
+
+    fn render_toolbar(
+        &self,
+        on_change: impl Fn(LogOptions, &mut Window, &mut App) + 'static + Clone,
+        on_action: impl Fn(LogAction, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        let options = &self.options;
+        let action = |action: LogAction| {
+            let on_action = on_action.clone();
+            move |_: &ClickEvent, win: &mut Window, cx: &mut App| on_action(action.clone(), win, cx)
+        };
+        // Container and previous-instance choices only apply to a single pod
+        let single_pod = matches!(self.target, LogTarget::Pod(_));
+
+        let containers = self.containers.iter().map(|container| {
+            let mut label = if container.init {
+                format!("init: {}", container.name)
+            } else {
+                container.name.clone()
+            };
+            if container.restart_count > 0 {
+                label.push_str(&format!(" ↻{}", container.restart_count));
+            }
+
+            let selected = options.container.as_deref() == Some(container.name.as_str());
+            let mut next = options.clone();
+            next.container = Some(container.name.clone());
+            let on_change = on_change.clone();
+
+            chip(
+                format!("log-container-{}", container.name),
+                label,
+                selected,
+                colors,
+            )
+            .on_click(move |_, win, cx| on_change(next.clone(), win, cx))
+        });
+
+        let tail_index = TAIL_CHOICES
+            .iter()
+            .position(|(value, _)| *value == options.tail_lines)
+            .unwrap_or(0);
+        let since_index = SINCE_CHOICES
+            .iter()
+            .position(|(value, _)| *value == options.since_seconds)
+            .unwrap_or(0);
+
+        let mut previous = options.clone();
+        previous.previous = !options.previous;
+        let mut tail = options.clone();
+        tail.tail_lines = TAIL_CHOICES[(tail_index + 1) % TAIL_CHOICES.len()].0;
+        let mut since = options.clone();
+        since.since_seconds = SINCE_CHOICES[(since_index + 1) % SINCE_CHOICES.len()].0;
+        let mut timestamps = options.clone();
+        timestamps.timestamps = !options.timestamps;
+
+        let on_previous = on_change.clone();
+        let on_tail = on_change.clone();
+        let on_since = on_change.clone();
+
+        div()
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .gap_2()
+            .px_4()
+            .py_2()
+            .border_b_1()
+            .border_color(colors.border)
+            .when(single_pod, |this| {
+                this.children(containers)
+                    .child(div().w(px(1.0)).h(px(16.0)).bg(colors.border))
+                    .child(
+                        chip(
+                            "log-previous",
+                            "Previous".to_string(),
+                            options.previous,
+                            colors,
+                        )
+                        .on_click(move |_, win, cx| on_previous(previous.clone(), win, cx)),
+                    )
+            })
+            .child(
+                chip(
+                    "log-tail",
+                    format!("Tail: {}", TAIL_CHOICES[tail_index].1),
+                    false,
+                    colors,
+                )
+                .on_click(move |_, win, cx| on_tail(tail.clone(), win, cx)),
+            )
+            .child(
+                chip(
+                    "log-since",
+                    format!("Since: {}", SINCE_CHOICES[since_index].1),
+                    false,
+                    colors,
+                )
+                .on_click(move |_, win, cx| on_since(since.clone(), win, cx)),
+            )
+            .child(
+                chip(
+                    "log-timestamps",
+                    "Timestamps".to_string(),
+                    options.timestamps,
+                    colors,
+                )
+                .on_click(move |_, win, cx| on_change(timestamps.clone(), win, cx)),
+            )
+            .child(div().w(px(1.0)).h(px(16.0)).bg(colors.border))
+            .child(
+                chip(
+                    "log-export-format",
+                    format!("Format: {}", self.export.format.label()),
+                    false,
+                    colors,
+                )
+                .on_click(action(LogAction::CycleExportFormat)),
+            )
+            .child(
+                chip(
+                    "log-export-filtered",
+                    "Filtered only".to_string(),
+                    self.export.filtered_only,
+                    colors,
+                )
+                .on_click(action(LogAction::ToggleExportFiltered)),
+            )
+            .child(
+                chip("log-copy-all", "Copy all".to_string(), false, colors)
+                    .on_click(action(LogAction::CopyAll)),
+            )
+            .child(
+                chip("log-save-as", "Save as…".to_string(), false, colors)
+                    .on_click(action(LogAction::SaveAs)),
+            )
+            .child(
+                chip(
+                    "log-download-full",
+                    "Download full".to_string(),
+                    false,
+                    colors,
+                )
+                .on_click(action(LogAction::DownloadFull)),
+            )
     }
 
     fn render_header(
         &self,
         on_close: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static + Clone,
+        on_toggle_pause: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
         colors: &ThemeColors,
     ) -> impl IntoElement {
+        let (status, status_color) = if !self.streaming {
+            ("Stream ended".to_string(), colors.text_muted)
+        } else if self.paused {
+            (
+                format!("Paused ({} new)", self.logs.pending_len()),
+                colors.status_warning,
+            )
+        } else {
+            ("● Live".to_string(), colors.status_ok)
+        };
+
         div()
             .flex()
             .items_center()
@@ -67,28 +758,78 @@ impl LogView {
                         div()
                             .text_sm()
                             .text_color(colors.text_muted)
-                            .child(self.pod_name.clone()),
-                    ),
+                            .child(self.target.title()),
+                    )
+                    .child(div().text_xs().text_color(status_color).child(status))
+                    .children(self.export.status.as_ref().map(|status| {
+                        let (text, color) = match status {
+                            Ok(text) => (text.clone(), colors.text_muted),
+                            Err(text) => (text.clone(), colors.status_error),
+                        };
+                        div().text_xs().text_color(color).child(text)
+                    }))
+                    .child(div().text_xs().text_color(colors.text_muted).child(
+                        match self.logs.dropped() {
+                            0 => format!("{} lines", self.logs.len()),
+                            dropped => {
+                                format!("{} lines ({} older dropped)", self.logs.len(), dropped)
+                            }
+                        },
+                    )),
             )
             .child(
                 div()
-                    .id("close-logs")
-                    .px_2()
-                    .py_1()
-                    .text_sm()
-                    .text_color(colors.text_secondary)
-                    .hover({
-                        let cloned = colors.clone();
-                        move |style| style.text_color(cloned.text_primary)
-                    })
-                    .cursor(CursorStyle::PointingHand)
-                    .on_click(on_close)
-                    .child("Close"),
-            )
-    }
-
-    fn render_log_content(&self, colors: &ThemeColors) -> impl IntoElement {
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(
+                        div()
+                            .id("toggle-log-pause")
+                            .px_2()
+                            .py_1()
+                            .text_sm()
+                            .text_color(colors.text_secondary)
+                            .hover({
+                                let cloned = colors.clone();
+                                move |style| style.text_color(cloned.text_primary)
+                            })
+                            .cursor(CursorStyle::PointingHand)
+                            .on_click(on_toggle_pause)
+                            .child(if self.paused { "Resume" } else { "Pause" }),
+                    )
+                    .child(self.render_close_button(on_close, colors)),
+            )
+    }
+
+    fn render_close_button(
+        &self,
+        on_close: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
         div()
+            .id("close-logs")
+            .px_2()
+            .py_1()
+            .text_sm()
+            .text_color(colors.text_secondary)
+            .hover({
+                let cloned = colors.clone();
+                move |style| style.text_color(cloned.text_primary)
+            })
+            .cursor(CursorStyle::PointingHand)
+            .on_click(on_close)
+            .child("Close")
+    }
+
+    fn render_log_content(
+        &self,
+        scroll_handle: &UniformListScrollHandle,
+        current_line: Option<usize>,
+        column_view: bool,
+        on_action: impl Fn(LogAction, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        let content = div()
             .flex()
             .flex_col()
             .flex_1()
@@ -99,13 +840,215 @@ impl LogView {
             } else {
                 Hsla::from(colors.bg_app).opacity(0.5) // Dark mode: darker
             })
-            .child(
+            .font_family("'JetBrains Mono', 'Fira Code', monospace")
+            .text_xs()
+            .text_color(colors.text_secondary)
+            .line_height(relative(1.5));
+
+        if self.logs.is_empty() {
+            return content.child(
                 div()
-                    .font_family("'JetBrains Mono', 'Fira Code', monospace")
-                    .text_xs()
-                    .text_color(colors.text_secondary)
-                    .line_height(relative(1.5))
-                    .children(self.logs.iter().map(|line| div().child(line.clone()))),
-            )
+                    .text_color(colors.text_muted)
+                    .child(if self.streaming {
+                        "Waiting for logs..."
+                    } else {
+                        "No logs"
+                    }),
+            );
+        }
+
+        let buffer = self.logs.clone();
+        let show_prefix = self.has_many_sources();
+        let columns = self.columns.clone();
+        let inspected = self.inspected;
+        let row_colors = colors.clone();
+
+        // Only the rows in view are built, so buffer size doesn't affect frame time
+        let list = uniform_list("log-lines", self.row_count(), move |range, _window, _cx| {
+            range
+                .filter_map(|row| {
+                    let ix = buffer.row_line(row)?;
+                    let line = buffer.line(ix)?;
+                    let seq = buffer.seq(ix);
+                    let is_current = current_line == Some(ix);
+                    let row = if column_view {
+                        render_column_row(&line, show_prefix, &columns, is_current, &row_colors)
+                    } else {
+                        let highlights = buffer.highlight_ranges(&line.text);
+                        render_line(&line, show_prefix, highlights, is_current, &row_colors)
+                    };
+                    let on_action = on_action.clone();
+
+                    Some(
+                        row.id(("log-line", seq))
+                            .cursor(CursorStyle::PointingHand)
+                            .when(inspected == Some(seq), |this| {
+                                this.bg(row_colors.bg_element_active)
+                            })
+                            .on_click(move |_, win, cx| {
+                                on_action(LogAction::Inspect(seq), win, cx)
+                            }),
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+        .track_scroll(scroll_handle.clone())
+        .size_full();
+
+        content
+            .when(column_view, |this| {
+                this.child(render_column_header(show_prefix, &self.columns, colors))
+            })
+            .child(list)
     }
 }
+
+fn render_line(
+    line: &LogLine,
+    show_prefix: bool,
+    highlights: Vec<Range<usize>>,
+    is_current: bool,
+    colors: &ThemeColors,
+) -> Div {
+    let prefix = match &line.container {
+        Some(container) if show_prefix => Some(format!("{}/{} ", line.pod, container)),
+        None if show_prefix => Some(format!("{} ", line.pod)),
+        _ => None,
+    };
+
+    let match_bg = if is_current {
+        Hsla::from(colors.status_warning).opacity(0.6)
+    } else {
+        Hsla::from(colors.accent).opacity(0.35)
+    };
+    let highlights: Vec<_> = highlights
+        .into_iter()
+        .map(|range| {
+            (
+                range,
+                HighlightStyle {
+                    background_color: Some(match_bg),
+                    ..Default::default()
+                },
+            )
+        })
+        .collect();
+
+    // Rows must be one line tall for the uniform list
+    div()
+        .flex()
+        .whitespace_nowrap()
+        .overflow_hidden()
+        .children(prefix.map(|prefix| {
+            div()
+                .flex_none()
+                .text_color(source_color(&line.pod, line.container.as_deref()))
+                .child(prefix)
+        }))
+        .child(
+            div()
+                .when_some(LogLevel::detect(&line.text), |this, level| {
+                    this.text_color(level.color(colors))
+                })
+                .child(StyledText::new(line.text.clone()).with_highlights(highlights)),
+        )
+}
+
+fn column_cell(width: f32) -> Div {
+    div().flex_none().w(px(width)).pr_2().overflow_hidden()
+}
+
+fn render_column_header(show_prefix: bool, columns: &[String], colors: &ThemeColors) -> Div {
+    div()
+        .flex()
+        .whitespace_nowrap()
+        .overflow_hidden()
+        .pb_1()
+        .mb_1()
+        .border_b_1()
+        .border_color(colors.border)
+        .text_color(colors.text_muted)
+        .font_weight(FontWeight::SEMIBOLD)
+        .when(show_prefix, |this| {
+            this.child(column_cell(SOURCE_WIDTH).child("SOURCE"))
+        })
+        .child(column_cell(TIME_WIDTH).child("TIME"))
+        .child(column_cell(LEVEL_WIDTH).child("LEVEL"))
+        .child(div().flex_1().overflow_hidden().child("MESSAGE"))
+        .children(
+            columns
+                .iter()
+                .map(|column| column_cell(FIELD_WIDTH).child(column.to_uppercase())),
+        )
+}
+
+/// One line split into time, level, message and the selected fields. Lines
+/// that aren't structured show their raw text in the message column.
+fn render_column_row(
+    line: &LogLine,
+    show_prefix: bool,
+    columns: &[String],
+    is_current: bool,
+    colors: &ThemeColors,
+) -> Div {
+    let parsed = StructuredLog::parse(&line.text);
+    let field = |key: &str| {
+        parsed
+            .as_ref()
+            .and_then(|log| log.get(key))
+            .unwrap_or_default()
+            .to_string()
+    };
+    let level = match &parsed {
+        Some(log) => log.get("level").and_then(LogLevel::from_name),
+        None => LogLevel::detect(&line.text),
+    };
+    let message = match &parsed {
+        Some(_) => field("msg"),
+        None => line.text.clone(),
+    };
+
+    div()
+        .flex()
+        .whitespace_nowrap()
+        .overflow_hidden()
+        .when(is_current, |this| {
+            this.bg(Hsla::from(colors.status_warning).opacity(0.3))
+        })
+        .when(show_prefix, |this| {
+            this.child(
+                column_cell(SOURCE_WIDTH)
+                    .text_color(source_color(&line.pod, line.container.as_deref()))
+                    .child(line.pod.clone()),
+            )
+        })
+        .child(
+            column_cell(TIME_WIDTH)
+                .text_color(colors.text_muted)
+                .child(field("timestamp")),
+        )
+        .child(column_cell(LEVEL_WIDTH).when_some(level, |this, level| {
+            this.text_color(level.color(colors)).child(level.name())
+        }))
+        .child(
+            div()
+                .flex_1()
+                .overflow_hidden()
+                .text_color(colors.text_primary)
+                .child(message),
+        )
+        .children(
+            columns
+                .iter()
+                .map(|column| column_cell(FIELD_WIDTH).child(field(column))),
+        )
+}
+
+/// Stable per-source color so interleaved lines are easy to tell apart.
+fn source_color(pod: &str, container: Option<&str>) -> Hsla {
+    let mut hasher = DefaultHasher::new();
+    pod.hash(&mut hasher);
+    container.hash(&mut hasher);
+    let hue = (hasher.finish() % 360) as f32 / 360.0;
+    hsla(hue, 0.65, 0.6, 1.0)
+}
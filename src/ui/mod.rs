@@ -1,17 +1,42 @@
+pub mod chip;
 pub mod dashboard;
+pub mod delete_dialog;
 pub mod detail_view;
+pub mod drain_dialog;
+pub mod events;
 pub mod glass;
+pub mod log_buffer;
+pub mod log_export;
+pub mod log_parse;
 pub mod logs;
+pub mod port_forwards;
+pub mod prometheus;
 pub mod resource_list;
+pub mod rollout;
 pub mod sidebar;
 pub mod status_bar;
+pub mod terminal;
+pub mod text_field;
+pub mod usage_chart;
+pub mod yaml_diff;
+pub mod yaml_editor;
 
-pub use dashboard::DashboardView;
-pub use detail_view::DetailView;
+pub use dashboard::{DashboardView, EventFeed};
+pub use delete_dialog::{DeleteAction, DeleteDialog};
+pub use detail_view::{DetailAction, DetailProps, DetailView};
+pub use drain_dialog::{DrainAction, DrainDialog, NodeAction};
+pub use events::EventAction;
 pub use glass::GlassStyle;
-pub use logs::LogView;
+pub use logs::{LogAction, LogTarget, LogView};
+pub use port_forwards::{PortForwardAction, PortForwardEntry, PortForwardsView};
+pub use prometheus::{MetricsAction, PrometheusMetrics};
 pub use resource_list::ResourceListView;
-pub use sidebar::Sidebar;
+pub use rollout::{DetailTab, HistoryAction, RolloutHistory, WorkloadAction};
+pub use sidebar::{NamespacePicker, Sidebar};
+pub use terminal::{TerminalAction, TerminalView};
+pub use text_field::TextFieldEvent;
+pub use usage_chart::UsageHistory;
+pub use yaml_editor::{EditorEvent, YamlAction, YamlEditor};
 // pub use status_bar::StatusBar;
 
 use crate::kubernetes::ResourceKind;
@@ -21,4 +46,6 @@ pub enum ActiveView {
     Dashboard,
     Resources(ResourceKind),
     Logs(LogView), // Component struct, not View handle
+    Terminal(TerminalView),
+    PortForwards,
 }
@@ -0,0 +1,327 @@
+use crate::kubernetes::{PortForwardSpec, PortForwardStats, ResourceKind};
+use crate::theme::ThemeColors;
+use crate::ui::glass::{GlassExt, GlassStyle};
+use gpui::*;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+/// A running forward as shown in the panel.
+#[derive(Clone, Debug)]
+pub struct PortForwardEntry {
+    pub id: usize,
+    pub spec: PortForwardSpec,
+    pub stats: Arc<PortForwardStats>,
+    pub favorite: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PortForwardAction {
+    Stop(usize),
+    ToggleFavorite(usize),
+    /// Start a saved favorite that isn't running
+    Start(PortForwardSpec),
+    RemoveFavorite(PortForwardSpec),
+}
+
+pub struct PortForwardsView;
+
+impl PortForwardsView {
+    pub fn new(
+        entries: Vec<PortForwardEntry>,
+        idle_favorites: Vec<PortForwardSpec>,
+        glass_style: GlassStyle,
+        on_action: impl Fn(PortForwardAction, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        let is_empty = entries.is_empty() && idle_favorites.is_empty();
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .glass_panel(glass_style, colors)
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .h(px(48.0))
+                    .px_4()
+                    .border_b_1()
+                    .border_color(colors.border)
+                    .child(
+                        div()
+                            .text_lg()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(colors.text_primary)
+                            .child("Port Forwards"),
+                    ),
+            )
+            .child(
+                div()
+                    .id("port-forward-list")
+                    .flex()
+                    .flex_col()
+                    .flex_1()
+                    .gap_4()
+                    .p_4()
+                    .overflow_y_scroll()
+                    .when(is_empty, |this| {
+                        this.child(
+                            div().text_sm().text_color(colors.text_muted).child(
+                                "No port forwards. Start one from a pod or service's details.",
+                            ),
+                        )
+                    })
+                    .when(!entries.is_empty(), |this| {
+                        this.child(Self::render_active(entries, on_action.clone(), colors))
+                    })
+                    .when(!idle_favorites.is_empty(), |this| {
+                        this.child(Self::render_favorites(idle_favorites, on_action, colors))
+                    }),
+            )
+    }
+
+    fn render_active(
+        entries: Vec<PortForwardEntry>,
+        on_action: impl Fn(PortForwardAction, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .child(section_title("ACTIVE", colors))
+            .child(
+                div()
+                    .flex()
+                    .px_3()
+                    .text_xs()
+                    .text_color(colors.text_muted)
+                    .child(div().flex_1().child("Target"))
+                    .child(div().w(px(160.0)).child("Local"))
+                    .child(div().w(px(100.0)).child("Remote"))
+                    .child(div().w(px(70.0)).child("Conns"))
+                    .child(div().w(px(90.0)).child("In"))
+                    .child(div().w(px(90.0)).child("Out"))
+                    .child(div().w(px(110.0))),
+            )
+            .children(entries.into_iter().map(|entry| {
+                let stats = &entry.stats;
+                let (local, local_color) = match (stats.error(), stats.local_port()) {
+                    (Some(error), _) => (error, colors.status_error),
+                    (None, Some(port)) => (format!("localhost:{}", port), colors.text_primary),
+                    (None, None) => ("Starting…".to_string(), colors.text_muted),
+                };
+                let remote = match stats.pod() {
+                    Some(pod) if entry.spec.kind != ResourceKind::Pod => {
+                        format!("{} ({})", entry.spec.remote_port, pod)
+                    }
+                    _ => entry.spec.remote_port.to_string(),
+                };
+                let id = entry.id;
+                let on_stop = on_action.clone();
+                let on_favorite = on_action.clone();
+
+                div()
+                    .flex()
+                    .items_center()
+                    .px_3()
+                    .py_2()
+                    .rounded_md()
+                    .bg(colors.bg_element)
+                    .text_sm()
+                    .child(
+                        div()
+                            .flex_1()
+                            .flex()
+                            .flex_col()
+                            .overflow_hidden()
+                            .child(
+                                div()
+                                    .text_color(colors.text_primary)
+                                    .child(entry.spec.target()),
+                            )
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(colors.text_muted)
+                                    .child(entry.spec.namespace.clone()),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .w(px(160.0))
+                            .overflow_hidden()
+                            .text_color(local_color)
+                            .child(local),
+                    )
+                    .child(
+                        div()
+                            .w(px(100.0))
+                            .overflow_hidden()
+                            .text_color(colors.text_secondary)
+                            .child(remote),
+                    )
+                    .child(
+                        div()
+                            .w(px(70.0))
+                            .text_color(colors.text_secondary)
+                            .child(stats.connections.load(Ordering::Relaxed).to_string()),
+                    )
+                    .child(
+                        div()
+                            .w(px(90.0))
+                            .text_color(colors.text_secondary)
+                            .child(format_bytes(stats.bytes_in.load(Ordering::Relaxed))),
+                    )
+                    .child(
+                        div()
+                            .w(px(90.0))
+                            .text_color(colors.text_secondary)
+                            .child(format_bytes(stats.bytes_out.load(Ordering::Relaxed))),
+                    )
+                    .child(
+                        div()
+                            .w(px(110.0))
+                            .flex()
+                            .justify_end()
+                            .gap_2()
+                            .child(
+                                row_button(
+                                    ("port-forward-favorite", id),
+                                    if entry.favorite { "★" } else { "☆" },
+                                    colors,
+                                )
+                                .on_click(move |_, win, cx| {
+                                    on_favorite(PortForwardAction::ToggleFavorite(id), win, cx)
+                                }),
+                            )
+                            .child(
+                                row_button(("port-forward-stop", id), "Stop", colors).on_click(
+                                    move |_, win, cx| on_stop(PortForwardAction::Stop(id), win, cx),
+                                ),
+                            ),
+                    )
+            }))
+    }
+
+    fn render_favorites(
+        favorites: Vec<PortForwardSpec>,
+        on_action: impl Fn(PortForwardAction, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .child(section_title("FAVORITES", colors))
+            .children(favorites.into_iter().enumerate().map(|(ix, spec)| {
+                let on_start = on_action.clone();
+                let on_remove = on_action.clone();
+                let start_spec = spec.clone();
+                let remove_spec = spec.clone();
+
+                div()
+                    .flex()
+                    .items_center()
+                    .px_3()
+                    .py_2()
+                    .rounded_md()
+                    .bg(colors.bg_element)
+                    .text_sm()
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_color(colors.text_primary)
+                            .child(format!("{} ({})", spec.target(), spec.namespace)),
+                    )
+                    .child(
+                        div()
+                            .w(px(260.0))
+                            .text_color(colors.text_secondary)
+                            .child(format!(
+                                "localhost:{} → {}",
+                                spec.local_port, spec.remote_port
+                            )),
+                    )
+                    .child(
+                        div()
+                            .w(px(110.0))
+                            .flex()
+                            .justify_end()
+                            .gap_2()
+                            .child(
+                                row_button(("port-forward-start", ix), "Start", colors).on_click(
+                                    move |_, win, cx| {
+                                        on_start(
+                                            PortForwardAction::Start(start_spec.clone()),
+                                            win,
+                                            cx,
+                                        )
+                                    },
+                                ),
+                            )
+                            .child(
+                                row_button(("port-forward-remove", ix), "Remove", colors).on_click(
+                                    move |_, win, cx| {
+                                        on_remove(
+                                            PortForwardAction::RemoveFavorite(remove_spec.clone()),
+                                            win,
+                                            cx,
+                                        )
+                                    },
+                                ),
+                            ),
+                    )
+            }))
+    }
+}
+
+fn section_title(title: &'static str, colors: &ThemeColors) -> impl IntoElement {
+    div()
+        .text_xs()
+        .font_weight(FontWeight::SEMIBOLD)
+        .text_color(colors.text_muted)
+        .pb_1()
+        .child(title)
+}
+
+fn row_button(
+    id: impl Into<ElementId>,
+    label: &'static str,
+    colors: &ThemeColors,
+) -> Stateful<Div> {
+    div()
+        .id(id)
+        .px_2()
+        .py_0p5()
+        .rounded_md()
+        .text_xs()
+        .text_color(colors.text_secondary)
+        .cursor(CursorStyle::PointingHand)
+        .hover({
+            let cloned = colors.clone();
+            move |style| {
+                style
+                    .bg(cloned.bg_element_hover)
+                    .text_color(cloned.text_primary)
+            }
+        })
+        .child(label)
+}
+
+/// `1.2 MB`-style size
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
@@ -0,0 +1,232 @@
+use crate::kubernetes::{HistoryWindow, QuerySeries, QueryTemplate, QueryUnit};
+use crate::theme::ThemeColors;
+use crate::ui::chip::chip;
+use crate::ui::port_forwards::format_bytes;
+use crate::ui::resource_list::format_cores;
+use crate::ui::usage_chart::render_lines;
+use gpui::*;
+
+/// Lines drawn per chart; for workloads with more pods, the busiest.
+const MAX_SERIES: usize = 8;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MetricsAction {
+    SetWindow(HistoryWindow),
+    Refresh,
+}
+
+/// The Metrics tab: each query template's result for the selected object.
+#[derive(Clone, Debug, Default)]
+pub struct PrometheusMetrics {
+    pub window: HistoryWindow,
+    /// In settings order; `None` while the query runs
+    pub results: Vec<(QueryTemplate, Option<Result<Vec<QuerySeries>, String>>)>,
+}
+
+impl PrometheusMetrics {
+    pub fn new(window: HistoryWindow, templates: Vec<QueryTemplate>) -> Self {
+        Self {
+            window,
+            results: templates.into_iter().map(|t| (t, None)).collect(),
+        }
+    }
+
+    pub fn render(
+        &self,
+        on_action: impl Fn(MetricsAction, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        let button = |id: (&'static str, usize),
+                      label: &'static str,
+                      selected: bool,
+                      action: MetricsAction| {
+            let on_action = on_action.clone();
+            chip(id, label, selected, colors)
+                .on_click(move |_, win, cx| on_action(action.clone(), win, cx))
+        };
+
+        let controls = div()
+            .flex()
+            .justify_end()
+            .gap_1()
+            .children(
+                HistoryWindow::all()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(ix, window)| {
+                        button(
+                            ("metrics-window", ix),
+                            window.label(),
+                            window == self.window,
+                            MetricsAction::SetWindow(window),
+                        )
+                    }),
+            )
+            .child(button(
+                ("metrics-refresh", 0),
+                "Refresh",
+                false,
+                MetricsAction::Refresh,
+            ));
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .child(controls)
+            .when(self.results.is_empty(), |this| {
+                this.child(
+                    div().text_sm().text_color(colors.text_muted).child(
+                        "No queries configured. Add PromQL queries in Settings → Kubernetes.",
+                    ),
+                )
+            })
+            .children(
+                self.results
+                    .iter()
+                    .map(|(template, result)| self.render_query(template, result.as_ref(), colors)),
+            )
+    }
+
+    fn render_query(
+        &self,
+        template: &QueryTemplate,
+        result: Option<&Result<Vec<QuerySeries>, String>>,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        let title = div()
+            .text_sm()
+            .font_weight(FontWeight::SEMIBOLD)
+            .text_color(colors.text_primary)
+            .child(template.name.clone());
+        let message = |text: String, color: Rgba| {
+            div()
+                .h(px(140.0))
+                .flex()
+                .items_center()
+                .justify_center()
+                .rounded_md()
+                .bg(colors.bg_element)
+                .text_xs()
+                .text_color(color)
+                .child(text)
+        };
+
+        let body = match result {
+            None => message("Querying…".to_string(), colors.text_muted).into_any_element(),
+            Some(Err(error)) => message(error.clone(), colors.status_error).into_any_element(),
+            Some(Ok(series)) if series.iter().all(|s| s.points.is_empty()) => {
+                message("No data".to_string(), colors.text_muted).into_any_element()
+            }
+            Some(Ok(series)) => self
+                .render_series(series, template.unit, colors)
+                .into_any_element(),
+        };
+
+        div().flex().flex_col().gap_2().child(title).child(body)
+    }
+
+    fn render_series(
+        &self,
+        series: &[QuerySeries],
+        unit: QueryUnit,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        let palette = [
+            colors.accent,
+            colors.status_info,
+            colors.status_ok,
+            colors.status_warning,
+            colors.status_error,
+            colors.text_secondary,
+        ];
+        let latest = |s: &QuerySeries| s.points.last().map(|(_, v)| *v).unwrap_or(0.0);
+
+        let mut shown: Vec<&QuerySeries> = series.iter().filter(|s| !s.points.is_empty()).collect();
+        shown.sort_by(|a, b| latest(b).total_cmp(&latest(a)));
+        let hidden = shown.len().saturating_sub(MAX_SERIES);
+        shown.truncate(MAX_SERIES);
+
+        let max = shown
+            .iter()
+            .flat_map(|s| s.points.iter().map(|(_, v)| *v))
+            .fold(0.0, f64::max)
+            * 1.1;
+        let start = self.window.start();
+        let seconds = self.window.seconds() as f64;
+        let lines = shown
+            .iter()
+            .enumerate()
+            .map(|(ix, s)| {
+                let points = s
+                    .points
+                    .iter()
+                    .map(|(timestamp, value)| {
+                        let x = (timestamp - start) as f64 / seconds;
+                        let y = if max > 0.0 { value / max } else { 0.0 };
+                        (x.clamp(0.0, 1.0) as f32, y as f32)
+                    })
+                    .collect();
+                (points, palette[ix % palette.len()])
+            })
+            .collect();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .h(px(140.0))
+                    .rounded_md()
+                    .bg(colors.bg_element)
+                    .overflow_hidden()
+                    .child(render_lines(lines)),
+            )
+            .children(shown.iter().enumerate().map(|(ix, s)| {
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .text_xs()
+                    .child(
+                        div()
+                            .size(px(8.0))
+                            .flex_none()
+                            .rounded_sm()
+                            .bg(palette[ix % palette.len()]),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .overflow_hidden()
+                            .whitespace_nowrap()
+                            .text_color(colors.text_secondary)
+                            .child(s.label.clone()),
+                    )
+                    .child(
+                        div()
+                            .text_color(colors.text_muted)
+                            .child(format_value(unit, latest(s))),
+                    )
+            }))
+            .when(hidden > 0, |this| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(colors.text_muted)
+                        .child(format!("{} more not shown", hidden)),
+                )
+            })
+    }
+}
+
+pub fn format_value(unit: QueryUnit, value: f64) -> String {
+    match unit {
+        QueryUnit::Cores => format_cores(value),
+        QueryUnit::Bytes => format_bytes(value as u64),
+        QueryUnit::BytesPerSecond => format!("{}/s", format_bytes(value as u64)),
+        QueryUnit::Count if value.fract() == 0.0 => format!("{:.0}", value),
+        QueryUnit::Count => format!("{:.2}", value),
+    }
+}
@@ -1,19 +1,34 @@
-use crate::kubernetes::{ResourceItem, ResourceKind};
+use crate::kubernetes::{usage_reference, MetricsSnapshot, ResourceItem, ResourceKind, Usage};
 use crate::theme::ThemeColors;
 use crate::ui::glass::{GlassExt, GlassStyle};
+use crate::ui::port_forwards::format_bytes;
+use chrono::{DateTime, Local};
 use gpui::prelude::*;
 use gpui::*;
 
+const USAGE_COLUMN_WIDTH: f32 = 130.0;
+
 pub struct ResourceListView;
 
 impl ResourceListView {
     pub fn new(
         selected_kind: Option<ResourceKind>,
         resources: Vec<ResourceItem>,
+        last_refreshed: Option<DateTime<Local>>,
+        notice: Option<Result<String, String>>,
+        metrics: Option<&MetricsSnapshot>,
         glass_style: GlassStyle,
         on_select: impl Fn(ResourceItem, &mut Window, &mut App) + 'static + Clone,
         colors: &ThemeColors,
     ) -> impl IntoElement {
+        // Usage columns only where metrics-server reports usage
+        let metrics = metrics.filter(|_| {
+            matches!(
+                selected_kind,
+                Some(ResourceKind::Pod) | Some(ResourceKind::Node)
+            )
+        });
+
         div()
             .flex()
             .flex_col()
@@ -22,13 +37,22 @@ impl ResourceListView {
             .child(Self::render_header(
                 selected_kind.clone(),
                 resources.len(),
+                last_refreshed,
+                notice,
+                colors,
+            ))
+            .child(Self::render_table_header(
+                selected_kind.clone(),
+                metrics.is_some(),
                 colors,
             ))
-            .child(Self::render_table_header(selected_kind.clone(), colors))
             .child(div().flex().flex_col().flex_1().children(
                 resources.into_iter().enumerate().map({
                     let colors = colors.clone();
-                    move |(i, r)| Self::render_row(i, r, on_select.clone(), &colors)
+                    move |(i, r)| {
+                        let usage = metrics.map(|m| (m.usage(&r), usage_reference(&r)));
+                        Self::render_row(i, r, usage, on_select.clone(), &colors)
+                    }
                 }),
             ))
     }
@@ -36,6 +60,8 @@ impl ResourceListView {
     fn render_header(
         selected_kind: Option<ResourceKind>,
         count: usize,
+        last_refreshed: Option<DateTime<Local>>,
+        notice: Option<Result<String, String>>,
         colors: &ThemeColors,
     ) -> impl IntoElement {
         let title = selected_kind
@@ -73,7 +99,28 @@ impl ResourceListView {
             .child(
                 div()
                     .flex()
-                    .gap_2()
+                    .items_center()
+                    .gap_3()
+                    // Outcome of the last delete or other change made from the app
+                    .children(notice.map(|notice| {
+                        let (text, color) = match notice {
+                            Ok(text) => (text, colors.status_ok),
+                            Err(text) => (text, colors.status_error),
+                        };
+                        div()
+                            .max_w(px(360.0))
+                            .overflow_hidden()
+                            .whitespace_nowrap()
+                            .text_xs()
+                            .text_color(color)
+                            .child(text)
+                    }))
+                    .children(last_refreshed.map(|ts| {
+                        div()
+                            .text_xs()
+                            .text_color(colors.text_muted)
+                            .child(format!("Last refreshed {}", ts.format("%H:%M:%S")))
+                    }))
                     .child(Self::render_button("Refresh".to_string(), colors)),
             )
     }
@@ -93,9 +140,14 @@ impl ResourceListView {
 
     fn render_table_header(
         selected_kind: Option<ResourceKind>,
+        show_usage: bool,
         colors: &ThemeColors,
     ) -> impl IntoElement {
         let is_pod = matches!(selected_kind, Some(ResourceKind::Pod));
+        let extra_columns = selected_kind
+            .as_ref()
+            .map(|k| k.extra_columns())
+            .unwrap_or_default();
 
         div()
             .flex()
@@ -113,6 +165,15 @@ impl ResourceListView {
             .child(div().flex_1().child("NAME"))
             .child(div().w(px(150.0)).child("NAMESPACE"))
             .child(div().w(px(120.0)).child("STATUS"))
+            .children(
+                extra_columns
+                    .into_iter()
+                    .map(|col| div().w(px(120.0)).child(col)),
+            )
+            .when(show_usage, |this| {
+                this.child(div().w(px(USAGE_COLUMN_WIDTH)).child("CPU"))
+                    .child(div().w(px(USAGE_COLUMN_WIDTH)).child("MEMORY"))
+            })
             .child(if is_pod {
                 div().w(px(80.0)).child("RESTARTS")
             } else {
@@ -124,6 +185,7 @@ impl ResourceListView {
     fn render_row(
         index: usize,
         resource: ResourceItem,
+        usage: Option<(Option<Usage>, Option<Usage>)>,
         on_select: impl Fn(ResourceItem, &mut Window, &mut App) + 'static + Clone,
         colors: &ThemeColors,
     ) -> impl IntoElement {
@@ -170,6 +232,26 @@ impl ResourceListView {
                     .w(px(120.0))
                     .child(Self::render_status(resource.status.clone(), colors)),
             )
+            .children(
+                resource
+                    .columns
+                    .iter()
+                    .map(|value| div().w(px(120.0)).text_ellipsis().child(value.clone())),
+            )
+            .when_some(usage, |this, (usage, reference)| {
+                this.child(Self::render_usage(
+                    usage.map(|u| u.cpu),
+                    reference.map(|r| r.cpu),
+                    format_cores,
+                    colors,
+                ))
+                .child(Self::render_usage(
+                    usage.map(|u| u.memory),
+                    reference.map(|r| r.memory),
+                    |bytes| format_bytes(bytes as u64),
+                    colors,
+                ))
+            })
             .child(if resource.kind == ResourceKind::Pod {
                 div()
                     .w(px(80.0))
@@ -180,14 +262,78 @@ impl ResourceListView {
             .child(div().w(px(80.0)).child(resource.age.clone()))
     }
 
+    /// Usage with a bar against the pod's requests or the node's allocatable;
+    /// no bar when there is nothing to measure against.
+    fn render_usage(
+        usage: Option<f64>,
+        reference: Option<f64>,
+        format: impl Fn(f64) -> String,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        let Some(usage) = usage else {
+            return div()
+                .w(px(USAGE_COLUMN_WIDTH))
+                .text_color(colors.text_muted)
+                .child("–");
+        };
+        let fraction = reference.filter(|r| *r > 0.0).map(|r| usage / r);
+        let color = match fraction {
+            Some(f) if f >= 1.0 => colors.status_error,
+            Some(f) if f >= 0.8 => colors.status_warning,
+            _ => colors.status_ok,
+        };
+
+        div()
+            .w(px(USAGE_COLUMN_WIDTH))
+            .pr_3()
+            .flex()
+            .flex_col()
+            .gap_0p5()
+            .child(div().text_xs().child(match fraction {
+                Some(f) => format!("{} ({:.0}%)", format(usage), f * 100.0),
+                None => format(usage),
+            }))
+            .when_some(fraction, |this, fraction| {
+                this.child(
+                    div()
+                        .h(px(3.0))
+                        .w_full()
+                        .rounded_sm()
+                        .bg(colors.bg_element)
+                        .child(
+                            div()
+                                .h_full()
+                                .w(relative(fraction.clamp(0.0, 1.0) as f32))
+                                .rounded_sm()
+                                .bg(color),
+                        ),
+                )
+            })
+    }
+
     fn render_status(status: String, colors: &ThemeColors) -> impl IntoElement {
-        let color = match status.as_str() {
-            "Running" | "Ready" | "Succeeded" => colors.status_ok,
-            "Pending" | "ContainerCreating" => colors.status_warning,
-            "Failed" | "Error" | "CrashLoopBackOff" => colors.status_error,
+        // Cordoned nodes carry a suffix; NotReady still takes precedence
+        let (state, cordoned) = match status.strip_suffix(",SchedulingDisabled") {
+            Some(state) => (state, true),
+            None => (status.as_str(), false),
+        };
+        let color = match state {
+            "Failed" | "Error" | "CrashLoopBackOff" | "Lost" | "NotReady" => colors.status_error,
+            _ if cordoned => colors.status_warning,
+            "Running" | "Ready" | "Succeeded" | "Bound" => colors.status_ok,
+            "Pending" | "ContainerCreating" | "Released" => colors.status_warning,
             _ => colors.text_muted,
         };
 
         div().text_color(color).child(status)
     }
 }
+
+/// CPU as `kubectl top` prints it: millicores below one core.
+pub fn format_cores(cores: f64) -> String {
+    if cores < 1.0 {
+        format!("{:.0}m", cores * 1000.0)
+    } else {
+        format!("{:.2}", cores)
+    }
+}
@@ -0,0 +1,457 @@
+use crate::kubernetes::{ResourceItem, ResourceKind, Revision, RolloutStatus};
+use crate::theme::ThemeColors;
+use crate::ui::chip::chip;
+use crate::ui::yaml_diff::YamlDiff;
+use gpui::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum WorkloadAction {
+    /// Move the replica stepper by this many replicas
+    StepReplicas(i32),
+    Scale(i32),
+    Restart,
+    SetPaused(bool),
+}
+
+/// Scale stepper, rollout progress and restart/pause buttons for
+/// Deployments, StatefulSets and ReplicaSets.
+pub struct RolloutControls;
+
+impl RolloutControls {
+    /// `replica_target` is the stepper's value when it differs from the spec.
+    pub fn new(
+        resource: &ResourceItem,
+        status: RolloutStatus,
+        replica_target: Option<i32>,
+        on_action: impl Fn(WorkloadAction, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        let target = replica_target.unwrap_or(status.desired);
+        let can_restart = matches!(
+            resource.kind,
+            ResourceKind::Deployment | ResourceKind::StatefulSet
+        );
+        let is_deployment = resource.kind == ResourceKind::Deployment;
+
+        let (state, state_color) = if status.paused {
+            ("Paused".to_string(), colors.status_warning)
+        } else if status.is_complete() {
+            ("Up to date".to_string(), colors.status_ok)
+        } else {
+            (
+                status
+                    .message
+                    .clone()
+                    .unwrap_or_else(|| "Rolling out…".to_string()),
+                colors.status_info,
+            )
+        };
+        let ready_fraction = if status.desired > 0 {
+            (status.ready as f32 / status.desired as f32).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+
+        let button = |id: &'static str, label: String, action: WorkloadAction| {
+            let on_action = on_action.clone();
+            div()
+                .id(id)
+                .px_3()
+                .py_1()
+                .bg(colors.bg_element)
+                .rounded_md()
+                .text_sm()
+                .text_color(colors.text_primary)
+                .cursor(CursorStyle::PointingHand)
+                .hover({
+                    let cloned = colors.clone();
+                    move |style| style.bg(cloned.bg_element_hover)
+                })
+                .on_click(move |_, win, cx| on_action(action.clone(), win, cx))
+                .child(label)
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(colors.text_primary)
+                    .pb_2()
+                    .border_b_1()
+                    .border_color(colors.border)
+                    .child("Rollout"),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_4()
+                    .text_sm()
+                    .text_color(colors.text_secondary)
+                    .child(format!("Ready {}/{}", status.ready, status.desired))
+                    .child(format!("Updated {}/{}", status.updated, status.desired))
+                    .child(format!("Available {}/{}", status.available, status.desired)),
+            )
+            .child(
+                div()
+                    .h(px(4.0))
+                    .w_full()
+                    .rounded_sm()
+                    .bg(colors.bg_element)
+                    .child(
+                        div()
+                            .h_full()
+                            .w(relative(ready_fraction))
+                            .rounded_sm()
+                            .bg(state_color),
+                    ),
+            )
+            .child(div().text_xs().text_color(state_color).child(state))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(colors.text_muted)
+                                    .child("Replicas"),
+                            )
+                            .child(button(
+                                "replicas-down",
+                                "−".to_string(),
+                                WorkloadAction::StepReplicas(-1),
+                            ))
+                            .child(
+                                div()
+                                    .min_w(px(28.0))
+                                    .flex()
+                                    .justify_center()
+                                    .text_sm()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(colors.text_primary)
+                                    .child(target.to_string()),
+                            )
+                            .child(button(
+                                "replicas-up",
+                                "+".to_string(),
+                                WorkloadAction::StepReplicas(1),
+                            ))
+                            .when(target != status.desired, |this| {
+                                this.child(button(
+                                    "replicas-scale",
+                                    format!("Scale {} → {}", status.desired, target),
+                                    WorkloadAction::Scale(target),
+                                ))
+                            }),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .gap_2()
+                            .when(can_restart, |this| {
+                                this.child(button(
+                                    "rollout-restart",
+                                    "Rollout restart".to_string(),
+                                    WorkloadAction::Restart,
+                                ))
+                            })
+                            .when(is_deployment, |this| {
+                                this.child(button(
+                                    "rollout-pause",
+                                    if status.paused { "Resume" } else { "Pause" }.to_string(),
+                                    WorkloadAction::SetPaused(!status.paused),
+                                ))
+                            }),
+                    ),
+            )
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DetailTab {
+    #[default]
+    Overview,
+    /// Rollout history; only offered for kinds with `has_history`
+    History,
+    /// Prometheus queries; only offered while Prometheus is enabled
+    Metrics,
+}
+
+impl DetailTab {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Overview => "Overview",
+            Self::History => "History",
+            Self::Metrics => "Metrics",
+        }
+    }
+}
+
+/// Kinds whose past pod templates are kept and can be rolled back to.
+pub fn has_history(kind: &ResourceKind) -> bool {
+    matches!(
+        kind,
+        ResourceKind::Deployment | ResourceKind::StatefulSet | ResourceKind::DaemonSet
+    )
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum HistoryAction {
+    /// Older side of the template diff
+    SetFrom(i64),
+    /// Newer side of the template diff
+    SetTo(i64),
+    /// Ask to roll back; the row then asks for confirmation
+    Rollback(i64),
+    ConfirmRollback(i64),
+    CancelRollback,
+    Refresh,
+}
+
+/// History tab state for one workload.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RolloutHistory {
+    /// Newest first; `None` while loading
+    pub revisions: Option<Result<Vec<Revision>, String>>,
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+    /// Template diff between `from` and `to`
+    pub diff: Option<YamlDiff>,
+    /// Revision whose rollback is waiting for confirmation
+    pub confirm_rollback: Option<i64>,
+}
+
+impl RolloutHistory {
+    /// Stores a fresh listing; the diff defaults to the previous revision
+    /// against the current one.
+    pub fn set_revisions(&mut self, result: Result<Vec<Revision>, String>) {
+        self.confirm_rollback = None;
+        if let Ok(revisions) = &result {
+            let current = revisions.iter().position(|r| r.current);
+            self.to = current.map(|ix| revisions[ix].revision);
+            self.from = current
+                .and_then(|ix| revisions.get(ix + 1))
+                .map(|r| r.revision);
+        }
+        self.revisions = Some(result);
+        self.update_diff();
+    }
+
+    pub fn revision(&self, revision: i64) -> Option<&Revision> {
+        self.revisions
+            .as_ref()?
+            .as_ref()
+            .ok()?
+            .iter()
+            .find(|r| r.revision == revision)
+    }
+
+    pub fn set_from(&mut self, revision: i64) {
+        self.from = Some(revision);
+        self.update_diff();
+    }
+
+    pub fn set_to(&mut self, revision: i64) {
+        self.to = Some(revision);
+        self.update_diff();
+    }
+
+    fn update_diff(&mut self) {
+        let template_yaml = |revision: Option<i64>| {
+            let revision = self.revision(revision?)?;
+            serde_yaml::to_string(&revision.template).ok()
+        };
+        self.diff = match (template_yaml(self.from), template_yaml(self.to)) {
+            (Some(from), Some(to)) => Some(YamlDiff::new(&from, &to)),
+            _ => None,
+        };
+    }
+
+    pub fn render(
+        &self,
+        on_action: impl Fn(HistoryAction, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        let button =
+            |id: (&'static str, usize), label: String, selected: bool, action: HistoryAction| {
+                let on_action = on_action.clone();
+                chip(id, label, selected, colors)
+                    .on_click(move |_, win, cx| on_action(action.clone(), win, cx))
+            };
+
+        let header = div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .pb_2()
+            .border_b_1()
+            .border_color(colors.border)
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(colors.text_primary)
+                    .child("Revisions"),
+            )
+            .child(button(
+                ("history-refresh", 0),
+                "Refresh".to_string(),
+                false,
+                HistoryAction::Refresh,
+            ));
+
+        let body = match &self.revisions {
+            None => div()
+                .text_sm()
+                .text_color(colors.text_muted)
+                .child("Loading history…"),
+            Some(Err(error)) => div()
+                .text_sm()
+                .text_color(colors.status_error)
+                .child(error.clone()),
+            Some(Ok(revisions)) if revisions.is_empty() => div()
+                .text_sm()
+                .text_color(colors.text_muted)
+                .child("No revisions recorded."),
+            Some(Ok(revisions)) => {
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .children(revisions.iter().enumerate().map(|(ix, revision)| {
+                        let number = revision.revision;
+                        let confirming = self.confirm_rollback == Some(number);
+
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .px_3()
+                            .py_2()
+                            .rounded_md()
+                            .bg(colors.bg_element)
+                            .text_sm()
+                            .child(
+                                div()
+                                    .flex()
+                                    .items_center()
+                                    .justify_between()
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .items_baseline()
+                                            .gap_2()
+                                            .child(
+                                                div()
+                                                    .font_weight(FontWeight::SEMIBOLD)
+                                                    .text_color(colors.text_primary)
+                                                    .child(format!("#{}", number)),
+                                            )
+                                            .when(revision.current, |this| {
+                                                this.child(
+                                                    div()
+                                                        .text_xs()
+                                                        .text_color(colors.status_ok)
+                                                        .child("current"),
+                                                )
+                                            })
+                                            .child(
+                                                div()
+                                                    .text_xs()
+                                                    .text_color(colors.text_muted)
+                                                    .child(format!(
+                                                        "{} · {}",
+                                                        revision.name, revision.age
+                                                    )),
+                                            ),
+                                    )
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .gap_1()
+                                            .child(button(
+                                                ("history-from", ix),
+                                                "From".to_string(),
+                                                self.from == Some(number),
+                                                HistoryAction::SetFrom(number),
+                                            ))
+                                            .child(button(
+                                                ("history-to", ix),
+                                                "To".to_string(),
+                                                self.to == Some(number),
+                                                HistoryAction::SetTo(number),
+                                            ))
+                                            .when(!revision.current && !confirming, |this| {
+                                                this.child(button(
+                                                    ("history-rollback", ix),
+                                                    "Roll back to this revision".to_string(),
+                                                    false,
+                                                    HistoryAction::Rollback(number),
+                                                ))
+                                            })
+                                            .when(confirming, |this| {
+                                                this.child(button(
+                                                    ("history-cancel", ix),
+                                                    "Cancel".to_string(),
+                                                    false,
+                                                    HistoryAction::CancelRollback,
+                                                ))
+                                                .child(button(
+                                                    ("history-confirm", ix),
+                                                    format!("Confirm roll back to #{}", number),
+                                                    true,
+                                                    HistoryAction::ConfirmRollback(number),
+                                                ))
+                                            }),
+                                    ),
+                            )
+                            .child(
+                                div().text_xs().text_color(colors.text_secondary).child(
+                                    revision
+                                        .change_cause
+                                        .clone()
+                                        .unwrap_or_else(|| "No change cause recorded".to_string()),
+                                ),
+                            )
+                            .children(revision.images.iter().map(|image| {
+                                div()
+                                    .text_xs()
+                                    .text_color(colors.text_muted)
+                                    .child(image.clone())
+                            }))
+                    }))
+            }
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(header)
+            .child(body)
+            .when_some(self.diff.as_ref(), |this, diff| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .text_color(colors.text_primary)
+                        .child(format!(
+                            "Pod template: #{} → #{}",
+                            self.from.unwrap_or_default(),
+                            self.to.unwrap_or_default()
+                        )),
+                )
+                .child(diff.render(colors))
+            })
+    }
+}
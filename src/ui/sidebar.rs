@@ -9,6 +9,26 @@ use std::collections::HashMap;
 
 pub struct Sidebar;
 
+/// State the namespace picker renders from
+#[derive(Clone, Debug, Default)]
+pub struct NamespacePicker {
+    /// Empty means all namespaces
+    pub selected: Vec<String>,
+    pub available: Vec<String>,
+    pub recent: Vec<String>,
+    pub is_open: bool,
+}
+
+impl NamespacePicker {
+    pub fn label(&self) -> String {
+        match self.selected.as_slice() {
+            [] => "All namespaces".to_string(),
+            [only] => only.clone(),
+            [first, rest @ ..] => format!("{} +{}", first, rest.len()),
+        }
+    }
+}
+
 impl Sidebar {
     pub fn new(
         glass_style: GlassStyle,
@@ -18,16 +38,22 @@ impl Sidebar {
         current_context: String,
         available_contexts: Vec<String>,
         show_context_menu: bool,
+        custom_kinds: Vec<ResourceKind>,
+        namespace_picker: NamespacePicker,
         on_select: impl Fn(ActiveView, &mut Window, &mut App) + 'static + Clone,
         on_toggle_context_menu: impl Fn(&mut Window, &mut App) + 'static + Clone,
         on_switch_context: impl Fn(String, &mut Window, &mut App) + 'static + Clone,
+        on_toggle_namespace_menu: impl Fn(&mut Window, &mut App) + 'static,
+        on_select_namespace: impl Fn(Option<String>, bool, &mut Window, &mut App) + 'static + Clone,
     ) -> impl IntoElement {
         let categories = Self::categorize_resources();
+        let custom_groups = Self::group_custom_resources(custom_kinds);
         let show_icons = settings.show_sidebar_icons;
         let active_view = active_view.clone();
 
         // Clone for callbacks
         let on_select_overview = on_select.clone();
+        let on_select_port_forwards = on_select.clone();
         let on_toggle_menu = on_toggle_context_menu.clone();
 
         // Get theme colors
@@ -138,46 +164,103 @@ impl Sidebar {
             } else {
                 div()
             })
+            // Namespace Selector (only if not collapsed)
+            .children(if !is_collapsed {
+                Some(Self::render_namespace_picker(
+                    namespace_picker,
+                    on_toggle_namespace_menu,
+                    on_select_namespace,
+                    &colors,
+                ))
+            } else {
+                None
+            })
             // Overview
-            .child(div().flex().flex_col().gap_px().child({
-                let is_active = matches!(active_view, ActiveView::Dashboard);
-
-                // Clone colors
-                let bg_element_active = colors.bg_element_active;
-                let text_inverse = colors.text_inverse;
-                let text_secondary = colors.text_secondary;
-                let ghost_element_hover = colors.ghost_element_hover;
-                let text_primary = colors.text_primary;
-
-                let mut item = div()
-                    .id("overview")
+            .child(
+                div()
                     .flex()
-                    .items_center()
-                    .px_3()
-                    .py_1()
-                    .mx_1()
-                    .gap_2()
-                    .text_sm()
-                    .rounded_md()
-                    .cursor(CursorStyle::PointingHand);
-
-                if is_active {
-                    item = item.bg(bg_element_active).text_color(text_inverse);
-                } else {
-                    item = item
-                        .text_color(text_secondary)
-                        .hover(move |style| style.bg(ghost_element_hover).text_color(text_primary));
-                }
-
-                if is_collapsed {
-                    item = item.justify_center().px_0().mx_0();
-                    item.child("📊")
-                } else {
-                    item.child(if show_icons { "📊" } else { "" })
-                        .child("Overview")
-                }
-                .on_click(move |_, win, app| on_select_overview(ActiveView::Dashboard, win, app))
-            }))
+                    .flex_col()
+                    .gap_px()
+                    .child({
+                        let is_active = matches!(active_view, ActiveView::Dashboard);
+
+                        // Clone colors
+                        let bg_element_active = colors.bg_element_active;
+                        let text_inverse = colors.text_inverse;
+                        let text_secondary = colors.text_secondary;
+                        let ghost_element_hover = colors.ghost_element_hover;
+                        let text_primary = colors.text_primary;
+
+                        let mut item = div()
+                            .id("overview")
+                            .flex()
+                            .items_center()
+                            .px_3()
+                            .py_1()
+                            .mx_1()
+                            .gap_2()
+                            .text_sm()
+                            .rounded_md()
+                            .cursor(CursorStyle::PointingHand);
+
+                        if is_active {
+                            item = item.bg(bg_element_active).text_color(text_inverse);
+                        } else {
+                            item = item.text_color(text_secondary).hover(move |style| {
+                                style.bg(ghost_element_hover).text_color(text_primary)
+                            });
+                        }
+
+                        if is_collapsed {
+                            item = item.justify_center().px_0().mx_0();
+                            item.child("📊")
+                        } else {
+                            item.child(if show_icons { "📊" } else { "" })
+                                .child("Overview")
+                        }
+                        .on_click(move |_, win, app| {
+                            on_select_overview(ActiveView::Dashboard, win, app)
+                        })
+                    })
+                    .child({
+                        let is_active = matches!(active_view, ActiveView::PortForwards);
+
+                        let mut item = div()
+                            .id("port-forwards")
+                            .flex()
+                            .items_center()
+                            .px_3()
+                            .py_1()
+                            .mx_1()
+                            .gap_2()
+                            .text_sm()
+                            .rounded_md()
+                            .cursor(CursorStyle::PointingHand);
+
+                        if is_active {
+                            item = item
+                                .bg(colors.bg_element_active)
+                                .text_color(colors.text_inverse);
+                        } else {
+                            let ghost_element_hover = colors.ghost_element_hover;
+                            let text_primary = colors.text_primary;
+                            item = item.text_color(colors.text_secondary).hover(move |style| {
+                                style.bg(ghost_element_hover).text_color(text_primary)
+                            });
+                        }
+
+                        if is_collapsed {
+                            item = item.justify_center().px_0().mx_0();
+                            item.child("🔌")
+                        } else {
+                            item.child(if show_icons { "🔌" } else { "" })
+                                .child("Port Forwards")
+                        }
+                        .on_click(move |_, win, app| {
+                            on_select_port_forwards(ActiveView::PortForwards, win, app)
+                        })
+                    }),
+            )
             .child(if !is_collapsed {
                 div()
                     .text_xs()
@@ -192,12 +275,44 @@ impl Sidebar {
             })
             .child(
                 div()
+                    .id("sidebar-resources")
                     .flex()
                     .flex_col()
+                    .flex_1()
                     .gap_3()
-                    .children(categories.into_iter().map(move |(category, resources)| {
+                    .overflow_y_scroll()
+                    .children(categories.into_iter().map({
+                        let active_view = active_view.clone();
+                        let on_select = on_select.clone();
+                        let colors = colors.clone();
+                        move |(category, resources)| {
+                            Self::render_category(
+                                category.to_string(),
+                                resources,
+                                show_icons,
+                                &active_view,
+                                is_collapsed,
+                                on_select.clone(),
+                                &colors,
+                            )
+                        }
+                    }))
+                    .children(if custom_groups.is_empty() || is_collapsed {
+                        None
+                    } else {
+                        Some(
+                            div()
+                                .text_xs()
+                                .font_weight(FontWeight::SEMIBOLD)
+                                .text_color(colors.text_muted)
+                                .px_3()
+                                .pt_2()
+                                .child("CUSTOM RESOURCES"),
+                        )
+                    })
+                    .children(custom_groups.into_iter().map(move |(group, resources)| {
                         Self::render_category(
-                            category.to_string(),
+                            group,
                             resources,
                             show_icons,
                             &active_view,
@@ -209,6 +324,134 @@ impl Sidebar {
             )
     }
 
+    fn render_namespace_picker(
+        picker: NamespacePicker,
+        on_toggle_menu: impl Fn(&mut Window, &mut App) + 'static,
+        on_select_namespace: impl Fn(Option<String>, bool, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        let bg_element_hover = colors.bg_element_hover;
+        let text_muted = colors.text_muted;
+
+        // Click selects a single namespace; Cmd/Ctrl-click adds or removes it
+        let menu_item = {
+            let on_select_namespace = on_select_namespace.clone();
+            let selected = picker.selected.clone();
+            move |id: String, namespace: Option<String>| {
+                let on_select = on_select_namespace.clone();
+                let is_selected = match &namespace {
+                    Some(ns) => selected.contains(ns),
+                    None => selected.is_empty(),
+                };
+                let label = namespace
+                    .clone()
+                    .unwrap_or_else(|| "All namespaces".to_string());
+
+                div()
+                    .id(id)
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .px_2()
+                    .py_1()
+                    .rounded_sm()
+                    .text_sm()
+                    .cursor(CursorStyle::PointingHand)
+                    .hover(move |style| style.bg(bg_element_hover))
+                    .child(div().w(px(12.0)).child(if is_selected { "✓" } else { "" }))
+                    .child(label)
+                    .on_mouse_down(MouseButton::Left, move |event, win, app| {
+                        on_select(namespace.clone(), event.modifiers.secondary(), win, app)
+                    })
+            }
+        };
+
+        let section_label = move |label: &'static str| {
+            div()
+                .text_xs()
+                .font_weight(FontWeight::SEMIBOLD)
+                .text_color(text_muted)
+                .px_2()
+                .pt_2()
+                .child(label)
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .px_3()
+            .gap_1()
+            .child(
+                div()
+                    .id("namespace-selector")
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .px_2()
+                    .py_1()
+                    .rounded_md()
+                    .bg(colors.bg_element)
+                    .cursor(CursorStyle::PointingHand)
+                    .hover(move |style| style.bg(bg_element_hover))
+                    .child(
+                        div().flex().items_center().gap_2().child("🗂").child(
+                            div()
+                                .text_sm()
+                                .text_ellipsis()
+                                .text_color(colors.text_primary)
+                                .child(picker.label()),
+                        ),
+                    )
+                    .child(if picker.is_open { "▲" } else { "▼" })
+                    .on_click(move |_, win, app| on_toggle_menu(win, app)),
+            )
+            .children(if picker.is_open {
+                Some(
+                    div()
+                        .id("namespace-menu")
+                        .flex()
+                        .flex_col()
+                        .max_h(px(320.0))
+                        .overflow_y_scroll()
+                        .bg(colors.bg_panel)
+                        .rounded_md()
+                        .border_1()
+                        .border_color(colors.border)
+                        .p_1()
+                        .mt_1()
+                        .child(menu_item("ns-all".to_string(), None))
+                        .children(if picker.recent.is_empty() {
+                            None
+                        } else {
+                            Some(section_label("RECENT"))
+                        })
+                        .children(
+                            picker
+                                .recent
+                                .iter()
+                                .map(|ns| menu_item(format!("ns-recent-{}", ns), Some(ns.clone()))),
+                        )
+                        .child(section_label("NAMESPACES"))
+                        .children(
+                            picker
+                                .available
+                                .iter()
+                                .map(|ns| menu_item(format!("ns-{}", ns), Some(ns.clone()))),
+                        )
+                        .child(
+                            div()
+                                .text_xs()
+                                .text_color(text_muted)
+                                .px_2()
+                                .pt_2()
+                                .child("Cmd/Ctrl-click to select multiple"),
+                        ),
+                )
+            } else {
+                None
+            })
+    }
+
     fn categorize_resources() -> Vec<(String, Vec<ResourceKind>)> {
         let mut categories: HashMap<String, Vec<ResourceKind>> = HashMap::new();
 
@@ -232,6 +475,21 @@ impl Sidebar {
         result
     }
 
+    /// Groups discovered custom kinds by API group, in discovery order.
+    fn group_custom_resources(custom_kinds: Vec<ResourceKind>) -> Vec<(String, Vec<ResourceKind>)> {
+        let mut groups: Vec<(String, Vec<ResourceKind>)> = Vec::new();
+
+        for kind in custom_kinds {
+            let group = kind.category().to_string();
+            match groups.iter_mut().find(|(name, _)| *name == group) {
+                Some((_, kinds)) => kinds.push(kind),
+                None => groups.push((group, vec![kind])),
+            }
+        }
+
+        groups
+    }
+
     fn render_category(
         category: String,
         resources: Vec<ResourceKind>,
@@ -252,7 +510,12 @@ impl Sidebar {
                     .text_color(colors.text_muted)
                     .px_3()
                     .pb_1()
-                    .child(category.to_uppercase())
+                    .child(if category.contains('.') {
+                        // API groups are case-sensitive domain names
+                        category
+                    } else {
+                        category.to_uppercase()
+                    })
             } else {
                 div().pb_1()
             })
@@ -293,6 +556,7 @@ impl Sidebar {
             ResourceKind::PersistentVolume => "💿",
             ResourceKind::PersistentVolumeClaim => "📑",
             ResourceKind::Node => "🖥️",
+            ResourceKind::Custom(_) => "🧩",
         };
 
         let icon = if show_icons || is_collapsed {
@@ -309,8 +573,13 @@ impl Sidebar {
 
         let kind_clone = kind.clone();
 
+        let item_id = match &kind {
+            ResourceKind::Custom(custom) => format!("{}.{}", custom.plural, custom.group),
+            _ => display_name.clone(),
+        };
+
         let mut item = div()
-            .id(display_name.clone())
+            .id(item_id)
             .flex()
             .items_center()
             .px_3()
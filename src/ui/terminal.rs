@@ -0,0 +1,597 @@
+use crate::kubernetes::ContainerInfo;
+use crate::theme::ThemeColors;
+use crate::ui::chip::chip;
+use crate::ui::glass::{GlassExt, GlassStyle};
+use crate::ui::text_field::TextField;
+use gpui::*;
+use std::cell::RefCell;
+use std::fmt;
+use std::ops::Range;
+use std::rc::Rc;
+
+const TERMINAL_FONT: &str = "JetBrains Mono";
+const FONT_SIZE: f32 = 13.0;
+const LINE_HEIGHT: f32 = 18.0;
+const SCROLLBACK_LINES: usize = 5000;
+
+/// Which shell to start in the container.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ShellChoice {
+    /// bash, falling back to sh when the image has no bash
+    #[default]
+    Auto,
+    Bash,
+    Sh,
+    Custom,
+}
+
+impl ShellChoice {
+    pub const ALL: [ShellChoice; 4] = [Self::Auto, Self::Bash, Self::Sh, Self::Custom];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Auto => "Auto",
+            Self::Bash => "bash",
+            Self::Sh => "sh",
+            Self::Custom => "Custom",
+        }
+    }
+
+    /// Commands to try, in order.
+    pub fn commands(&self, custom: &str) -> Vec<Vec<String>> {
+        let argv = |s: &str| s.split_whitespace().map(str::to_string).collect::<Vec<_>>();
+        match self {
+            Self::Auto => vec![argv("bash"), argv("sh")],
+            Self::Bash => vec![argv("bash")],
+            Self::Sh => vec![argv("sh")],
+            Self::Custom if custom.trim().is_empty() => vec![argv("sh")],
+            Self::Custom => vec![argv(custom)],
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TerminalAction {
+    Focus,
+    FocusCustomCommand,
+    SelectShell(ShellChoice),
+    SelectContainer(String),
+    Reconnect,
+    Copy,
+    Paste,
+}
+
+/// VT100 screen state shared with the render closures, like `LogBuffer`.
+#[derive(Clone)]
+pub struct TerminalScreen {
+    parser: Rc<RefCell<vt100::Parser>>,
+}
+
+impl TerminalScreen {
+    pub fn new(rows: u16, cols: u16) -> Self {
+        Self {
+            parser: Rc::new(RefCell::new(vt100::Parser::new(
+                rows,
+                cols,
+                SCROLLBACK_LINES,
+            ))),
+        }
+    }
+
+    pub fn process(&self, bytes: &[u8]) {
+        self.parser.borrow_mut().process(bytes);
+    }
+
+    /// (rows, cols)
+    pub fn size(&self) -> (u16, u16) {
+        self.parser.borrow().screen().size()
+    }
+
+    pub fn set_size(&self, rows: u16, cols: u16) {
+        self.parser.borrow_mut().set_size(rows, cols);
+    }
+
+    /// Visible text, for copying.
+    pub fn contents(&self) -> String {
+        self.parser.borrow().screen().contents()
+    }
+
+    pub fn application_cursor(&self) -> bool {
+        self.parser.borrow().screen().application_cursor()
+    }
+
+    pub fn bracketed_paste(&self) -> bool {
+        self.parser.borrow().screen().bracketed_paste()
+    }
+
+    /// Moves the view into (positive) or out of the scrollback.
+    pub fn scroll(&self, lines: i32) {
+        let mut parser = self.parser.borrow_mut();
+        let offset = parser.screen().scrollback() as i32 + lines;
+        parser.set_scrollback(offset.max(0) as usize);
+    }
+
+    /// Jumps back to the live screen, as typing does in most terminals.
+    pub fn scroll_to_bottom(&self) {
+        self.parser.borrow_mut().set_scrollback(0);
+    }
+}
+
+impl PartialEq for TerminalScreen {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.parser, &other.parser)
+    }
+}
+
+impl fmt::Debug for TerminalScreen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TerminalScreen")
+            .field("size", &self.size())
+            .finish()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TerminalView {
+    pub pod: String,
+    pub namespace: String,
+    pub containers: Vec<ContainerInfo>,
+    pub container: Option<String>,
+    pub shell: ShellChoice,
+    pub custom_command: TextField,
+    pub screen: TerminalScreen,
+    /// Key presses go to the session
+    pub focused: bool,
+    pub connected: bool,
+    /// Running command, or why the session ended
+    pub status: Option<String>,
+    pub glass_style: GlassStyle,
+}
+
+impl TerminalView {
+    pub fn new(
+        pod: String,
+        namespace: String,
+        containers: Vec<ContainerInfo>,
+        container: Option<String>,
+        glass_style: GlassStyle,
+    ) -> Self {
+        Self {
+            pod,
+            namespace,
+            containers,
+            container,
+            shell: ShellChoice::default(),
+            custom_command: TextField::default(),
+            screen: TerminalScreen::new(24, 80),
+            focused: true,
+            connected: false,
+            status: None,
+            glass_style,
+        }
+    }
+
+    /// Clears the screen for a new session, keeping its size.
+    pub fn reset(&mut self) {
+        let (rows, cols) = self.screen.size();
+        self.screen = TerminalScreen::new(rows, cols);
+        self.connected = true;
+        self.status = None;
+    }
+
+    /// `on_resize` gets the (rows, cols) that fit the screen area each frame.
+    pub fn render(
+        &self,
+        on_close: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+        on_action: impl Fn(TerminalAction, &mut Window, &mut App) + 'static + Clone,
+        on_resize: impl Fn(u16, u16, &mut Window, &mut App) + 'static,
+        on_scroll: impl Fn(i32, &mut Window, &mut App) + 'static,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .glass_panel(self.glass_style, colors)
+            .child(self.render_header(on_close, on_action.clone(), colors))
+            .child(self.render_toolbar(on_action.clone(), colors))
+            .child(self.render_screen(on_action, on_resize, on_scroll, colors))
+    }
+
+    fn render_header(
+        &self,
+        on_close: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+        on_action: impl Fn(TerminalAction, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        let (status, status_color) = if self.connected {
+            (
+                format!("● {}", self.status.as_deref().unwrap_or("Connecting…")),
+                colors.status_ok,
+            )
+        } else {
+            (
+                self.status
+                    .clone()
+                    .unwrap_or_else(|| "Disconnected".to_string()),
+                colors.text_muted,
+            )
+        };
+        let action = |action: TerminalAction| {
+            let on_action = on_action.clone();
+            move |_: &ClickEvent, win: &mut Window, cx: &mut App| on_action(action.clone(), win, cx)
+        };
+
+        div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .h(px(48.0))
+            .px_4()
+            .border_b_1()
+            .border_color(colors.border)
+            .child(
+                div()
+                    .flex()
+                    .items_baseline()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_lg()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(colors.text_primary)
+                            .child("Shell"),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(colors.text_muted)
+                            .child(format!("{}/{}", self.namespace, self.pod)),
+                    )
+                    .child(div().text_xs().text_color(status_color).child(status)),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(
+                        header_button("terminal-copy", "Copy", colors)
+                            .on_click(action(TerminalAction::Copy)),
+                    )
+                    .child(
+                        header_button("terminal-paste", "Paste", colors)
+                            .on_click(action(TerminalAction::Paste)),
+                    )
+                    .child(
+                        header_button("terminal-reconnect", "Reconnect", colors)
+                            .on_click(action(TerminalAction::Reconnect)),
+                    )
+                    .child(header_button("close-terminal", "Close", colors).on_click(on_close)),
+            )
+    }
+
+    fn render_toolbar(
+        &self,
+        on_action: impl Fn(TerminalAction, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        let containers = self
+            .containers
+            .iter()
+            .filter(|container| !container.init)
+            .map(|container| {
+                let on_action = on_action.clone();
+                let name = container.name.clone();
+                chip(
+                    format!("terminal-container-{}", container.name),
+                    container.name.clone(),
+                    self.container.as_deref() == Some(container.name.as_str()),
+                    colors,
+                )
+                .on_click(move |_, win, cx| {
+                    on_action(TerminalAction::SelectContainer(name.clone()), win, cx)
+                })
+            });
+
+        let shells = ShellChoice::ALL.into_iter().map(|shell| {
+            let on_action = on_action.clone();
+            chip(
+                format!("terminal-shell-{}", shell.label()),
+                shell.label().to_string(),
+                self.shell == shell,
+                colors,
+            )
+            .on_click(move |_, win, cx| on_action(TerminalAction::SelectShell(shell), win, cx))
+        });
+
+        let on_focus_custom = on_action.clone();
+
+        div()
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .gap_2()
+            .px_4()
+            .py_2()
+            .border_b_1()
+            .border_color(colors.border)
+            .children(containers)
+            .child(div().w(px(1.0)).h(px(16.0)).bg(colors.border))
+            .children(shells)
+            .when(self.shell == ShellChoice::Custom, |this| {
+                this.child(
+                    self.custom_command
+                        .render("terminal-custom-command", "/bin/zsh -l", colors)
+                        .on_click(move |_, win, cx| {
+                            on_focus_custom(TerminalAction::FocusCustomCommand, win, cx)
+                        }),
+                )
+            })
+    }
+
+    fn render_screen(
+        &self,
+        on_action: impl Fn(TerminalAction, &mut Window, &mut App) + 'static + Clone,
+        on_resize: impl Fn(u16, u16, &mut Window, &mut App) + 'static,
+        on_scroll: impl Fn(i32, &mut Window, &mut App) + 'static,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        let rows = {
+            let parser = self.screen.parser.borrow();
+            render_rows(parser.screen(), self.focused && self.connected, colors)
+        };
+
+        div()
+            .id("terminal-screen")
+            .relative()
+            .flex()
+            .flex_col()
+            .flex_1()
+            .overflow_hidden()
+            .p_2()
+            .bg(Hsla::from(colors.bg_app).opacity(0.6))
+            .border_1()
+            .border_color(if self.focused {
+                Hsla::from(colors.accent).opacity(0.5)
+            } else {
+                Hsla::transparent_black()
+            })
+            .font_family(TERMINAL_FONT)
+            .text_size(px(FONT_SIZE))
+            .line_height(px(LINE_HEIGHT))
+            .text_color(colors.text_primary)
+            .cursor(CursorStyle::IBeam)
+            .on_click(move |_, win, cx| on_action(TerminalAction::Focus, win, cx))
+            .on_scroll_wheel(move |event, win, cx| {
+                let lines = event.delta.pixel_delta(px(LINE_HEIGHT)).y / px(LINE_HEIGHT);
+                if lines.abs() >= 1.0 {
+                    on_scroll(lines as i32, win, cx);
+                }
+            })
+            // Measures the area each frame so the PTY follows the pane's size
+            .child(
+                canvas(
+                    move |bounds, window, cx| {
+                        let font_id = window.text_system().resolve_font(&font(TERMINAL_FONT));
+                        let cell_width = window
+                            .text_system()
+                            .advance(font_id, px(FONT_SIZE), 'm')
+                            .map(|size| size.width)
+                            .unwrap_or(px(FONT_SIZE * 0.6));
+                        let cols = (bounds.size.width / cell_width).floor().max(1.0) as u16;
+                        let rows = (bounds.size.height / px(LINE_HEIGHT)).floor().max(1.0) as u16;
+                        on_resize(rows, cols, window, cx);
+                    },
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .size_full(),
+            )
+            .children(rows)
+    }
+}
+
+/// One styled text element per screen row.
+fn render_rows(screen: &vt100::Screen, show_cursor: bool, colors: &ThemeColors) -> Vec<Div> {
+    let (rows, cols) = screen.size();
+    let cursor = (!screen.hide_cursor() && screen.scrollback() == 0 && show_cursor)
+        .then(|| screen.cursor_position());
+
+    (0..rows)
+        .map(|row| {
+            let mut text = String::new();
+            let mut runs: Vec<(Range<usize>, CellStyle)> = Vec::new();
+
+            for col in 0..cols {
+                let Some(cell) = screen.cell(row, col) else {
+                    continue;
+                };
+                if cell.is_wide_continuation() {
+                    continue;
+                }
+
+                let start = text.len();
+                if cell.has_contents() {
+                    text.push_str(&cell.contents());
+                } else {
+                    text.push(' ');
+                }
+
+                let style = CellStyle::of(cell, cursor == Some((row, col)));
+                match runs.last_mut() {
+                    Some((range, last)) if range.end == start && *last == style => {
+                        range.end = text.len();
+                    }
+                    _ => runs.push((start..text.len(), style)),
+                }
+            }
+
+            let highlights = runs
+                .into_iter()
+                .filter(|(_, style)| !style.is_plain())
+                .map(|(range, style)| (range, style.highlight(colors)))
+                .collect::<Vec<_>>();
+
+            div()
+                .h(px(LINE_HEIGHT))
+                .whitespace_nowrap()
+                .child(StyledText::new(text).with_highlights(highlights))
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+struct CellStyle {
+    fg: vt100::Color,
+    bg: vt100::Color,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    inverse: bool,
+}
+
+impl CellStyle {
+    fn of(cell: &vt100::Cell, is_cursor: bool) -> Self {
+        Self {
+            fg: cell.fgcolor(),
+            bg: cell.bgcolor(),
+            bold: cell.bold(),
+            italic: cell.italic(),
+            underline: cell.underline(),
+            // The cursor is drawn as an inverted cell
+            inverse: cell.inverse() != is_cursor,
+        }
+    }
+
+    fn is_plain(&self) -> bool {
+        self.fg == vt100::Color::Default
+            && self.bg == vt100::Color::Default
+            && !self.bold
+            && !self.italic
+            && !self.underline
+            && !self.inverse
+    }
+
+    fn highlight(&self, colors: &ThemeColors) -> HighlightStyle {
+        let mut fg = match self.fg {
+            vt100::Color::Default => None,
+            color => Some(ansi_color(color)),
+        };
+        let mut bg = match self.bg {
+            vt100::Color::Default => None,
+            color => Some(ansi_color(color)),
+        };
+        if self.inverse {
+            let new_fg = bg.unwrap_or_else(|| Hsla::from(colors.bg_app));
+            let new_bg = fg.unwrap_or_else(|| Hsla::from(colors.text_primary));
+            fg = Some(new_fg);
+            bg = Some(new_bg);
+        }
+
+        HighlightStyle {
+            color: fg,
+            background_color: bg,
+            font_weight: self.bold.then_some(FontWeight::BOLD),
+            font_style: self.italic.then_some(FontStyle::Italic),
+            underline: self.underline.then_some(UnderlineStyle {
+                thickness: px(1.0),
+                color: None,
+                wavy: false,
+            }),
+            ..Default::default()
+        }
+    }
+}
+
+/// xterm's 256-color palette.
+fn ansi_color(color: vt100::Color) -> Hsla {
+    const BASE: [u32; 16] = [
+        0x000000, 0xcd3131, 0x0dbc79, 0xe5e510, 0x2472c8, 0xbc3fbc, 0x11a8cd, 0xe5e5e5, 0x666666,
+        0xf14c4c, 0x23d18b, 0xf5f543, 0x3b8eea, 0xd670d6, 0x29b8db, 0xffffff,
+    ];
+
+    let hex = match color {
+        vt100::Color::Default => 0xe5e5e5,
+        vt100::Color::Rgb(r, g, b) => (r as u32) << 16 | (g as u32) << 8 | b as u32,
+        vt100::Color::Idx(ix @ 0..=15) => BASE[ix as usize],
+        vt100::Color::Idx(ix @ 16..=231) => {
+            let ix = ix as u32 - 16;
+            let level = |v: u32| if v == 0 { 0 } else { 55 + v * 40 };
+            level(ix / 36) << 16 | level((ix / 6) % 6) << 8 | level(ix % 6)
+        }
+        vt100::Color::Idx(ix) => {
+            let gray = 8 + (ix as u32 - 232) * 10;
+            gray << 16 | gray << 8 | gray
+        }
+    };
+    rgb(hex).into()
+}
+
+/// Bytes a terminal sends for `keystroke`, or `None` if it sends nothing.
+pub fn encode_key(keystroke: &Keystroke, application_cursor: bool) -> Option<Vec<u8>> {
+    let modifiers = &keystroke.modifiers;
+    let cursor = |code: char| {
+        if application_cursor {
+            format!("\x1bO{}", code)
+        } else {
+            format!("\x1b[{}", code)
+        }
+    };
+
+    let sequence = match keystroke.key.as_str() {
+        "enter" => "\r".to_string(),
+        "backspace" => "\x7f".to_string(),
+        "tab" if modifiers.shift => "\x1b[Z".to_string(),
+        "tab" => "\t".to_string(),
+        "escape" => "\x1b".to_string(),
+        "up" => cursor('A'),
+        "down" => cursor('B'),
+        "right" => cursor('C'),
+        "left" => cursor('D'),
+        "home" => "\x1b[H".to_string(),
+        "end" => "\x1b[F".to_string(),
+        "pageup" => "\x1b[5~".to_string(),
+        "pagedown" => "\x1b[6~".to_string(),
+        "insert" => "\x1b[2~".to_string(),
+        "delete" => "\x1b[3~".to_string(),
+        "space" if modifiers.control => return Some(vec![0]),
+        key if modifiers.control && key.len() == 1 => {
+            return match key.as_bytes()[0] {
+                c @ b'a'..=b'z' => Some(vec![c - b'a' + 1]),
+                b'@' => Some(vec![0]),
+                b'[' => Some(vec![0x1b]),
+                b'\\' => Some(vec![0x1c]),
+                b']' => Some(vec![0x1d]),
+                _ => None,
+            };
+        }
+        _ => match &keystroke.key_char {
+            Some(ch) if !modifiers.platform => {
+                if modifiers.alt {
+                    format!("\x1b{}", ch)
+                } else {
+                    ch.clone()
+                }
+            }
+            _ => return None,
+        },
+    };
+
+    Some(sequence.into_bytes())
+}
+
+fn header_button(id: &'static str, label: &'static str, colors: &ThemeColors) -> Stateful<Div> {
+    div()
+        .id(id)
+        .px_2()
+        .py_1()
+        .text_sm()
+        .text_color(colors.text_secondary)
+        .hover({
+            let cloned = colors.clone();
+            move |style| style.text_color(cloned.text_primary)
+        })
+        .cursor(CursorStyle::PointingHand)
+        .child(label)
+}
@@ -0,0 +1,115 @@
+use crate::theme::ThemeColors;
+use gpui::*;
+
+/// Single-line text input state. Keys are routed here by the owning view
+/// while the field has focus; editing happens at the end of the text.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextField {
+    pub text: String,
+    pub focused: bool,
+}
+
+/// What a key press did to the field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextFieldEvent {
+    Changed,
+    Submit {
+        shift: bool,
+    },
+    Cancel,
+    /// Cmd/Ctrl-V; the caller reads the clipboard and calls `insert`
+    Paste,
+    Ignored,
+}
+
+impl TextField {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            focused: false,
+        }
+    }
+
+    pub fn insert(&mut self, text: &str) {
+        // Single-line field: drop anything after the first newline
+        self.text.push_str(text.lines().next().unwrap_or_default());
+    }
+
+    pub fn handle_key(&mut self, keystroke: &Keystroke) -> TextFieldEvent {
+        let modifiers = &keystroke.modifiers;
+
+        match keystroke.key.as_str() {
+            "enter" => {
+                return TextFieldEvent::Submit {
+                    shift: modifiers.shift,
+                }
+            }
+            "escape" => return TextFieldEvent::Cancel,
+            "backspace" => {
+                if modifiers.secondary() {
+                    self.text.clear();
+                } else {
+                    self.text.pop();
+                }
+                return TextFieldEvent::Changed;
+            }
+            "v" if modifiers.secondary() => return TextFieldEvent::Paste,
+            _ => {}
+        }
+
+        if modifiers.control || modifiers.platform {
+            return TextFieldEvent::Ignored;
+        }
+
+        match &keystroke.key_char {
+            Some(ch) => {
+                self.text.push_str(ch);
+                TextFieldEvent::Changed
+            }
+            None => TextFieldEvent::Ignored,
+        }
+    }
+
+    pub fn render(
+        &self,
+        id: impl Into<ElementId>,
+        placeholder: &str,
+        colors: &ThemeColors,
+    ) -> Stateful<Div> {
+        let is_empty = self.text.is_empty();
+
+        div()
+            .id(id)
+            .flex()
+            .items_center()
+            .min_w(px(160.0))
+            .h(px(24.0))
+            .px_2()
+            .rounded_md()
+            .bg(colors.bg_element)
+            .border_1()
+            .border_color(if self.focused {
+                colors.accent
+            } else {
+                colors.border
+            })
+            .text_xs()
+            .cursor(CursorStyle::IBeam)
+            .child(
+                div()
+                    .text_color(if is_empty {
+                        colors.text_muted
+                    } else {
+                        colors.text_primary
+                    })
+                    .child(if is_empty && !self.focused {
+                        placeholder.to_string()
+                    } else {
+                        self.text.clone()
+                    }),
+            )
+            .when(self.focused, |this| {
+                this.child(div().w(px(1.0)).h(px(14.0)).bg(colors.accent))
+            })
+    }
+}
@@ -0,0 +1,218 @@
+use crate::kubernetes::{HistoryWindow, Sample, Usage};
+use crate::theme::ThemeColors;
+use crate::ui::chip::chip;
+use crate::ui::port_forwards::format_bytes;
+use crate::ui::resource_list::format_cores;
+use gpui::*;
+
+/// Recorded usage of a node, a pod, or the namespaces the dashboard covers.
+#[derive(Clone, Debug, Default)]
+pub struct UsageHistory {
+    /// Oldest first, all within `window`
+    pub samples: Vec<Sample>,
+    pub window: HistoryWindow,
+    /// Requests or allocatable, drawn as a line across the charts
+    pub reference: Option<Usage>,
+}
+
+impl UsageHistory {
+    /// CPU and memory charts under a window picker.
+    pub fn render(
+        &self,
+        id: &'static str,
+        on_window: impl Fn(HistoryWindow, &mut Window, &mut App) + 'static + Clone,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        let chips = HistoryWindow::all()
+            .into_iter()
+            .enumerate()
+            .map(|(ix, window)| {
+                let on_window = on_window.clone();
+                chip((id, ix), window.label(), window == self.window, colors)
+                    .on_click(move |_, win, cx| on_window(window, win, cx))
+            })
+            .collect::<Vec<_>>();
+
+        div()
+            .flex()
+            .flex_col()
+            .flex_1()
+            .gap_3()
+            .child(div().flex().justify_end().gap_1().children(chips))
+            .child(self.render_chart("CPU", |u| u.cpu, format_cores, colors.accent, colors))
+            .child(self.render_chart(
+                "Memory",
+                |u| u.memory,
+                |bytes| format_bytes(bytes as u64),
+                colors.status_info,
+                colors,
+            ))
+    }
+
+    fn render_chart(
+        &self,
+        title: &'static str,
+        value: fn(&Usage) -> f64,
+        format: impl Fn(f64) -> String,
+        color: Rgba,
+        colors: &ThemeColors,
+    ) -> impl IntoElement {
+        let reference = self.reference.as_ref().map(value).filter(|r| *r > 0.0);
+        let peak = self
+            .samples
+            .iter()
+            .map(|s| value(&s.usage))
+            .fold(0.0, f64::max);
+        // Headroom above the peak, or the reference if that is higher
+        let max = peak.max(reference.unwrap_or(0.0)) * 1.1;
+
+        let start = self.window.start();
+        let seconds = self.window.seconds() as f64;
+        let points: Vec<(f32, f32)> = self
+            .samples
+            .iter()
+            .map(|s| {
+                let x = (s.timestamp - start) as f64 / seconds;
+                let y = if max > 0.0 {
+                    value(&s.usage) / max
+                } else {
+                    0.0
+                };
+                (x.clamp(0.0, 1.0) as f32, y as f32)
+            })
+            .collect();
+
+        let current = self.samples.last().map(|s| value(&s.usage));
+        let summary = match (current, reference) {
+            (Some(current), Some(reference)) => {
+                format!("{} of {}", format(current), format(reference))
+            }
+            (Some(current), None) => format(current),
+            (None, _) => "–".to_string(),
+        };
+
+        let plot = if points.len() < 2 {
+            div()
+                .size_full()
+                .flex()
+                .items_center()
+                .justify_center()
+                .text_xs()
+                .text_color(colors.text_muted)
+                .child("Collecting samples…")
+                .into_any_element()
+        } else {
+            render_plot(
+                points,
+                reference.map(|r| (r / max) as f32),
+                color,
+                colors.text_muted,
+            )
+            .into_any_element()
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .flex_1()
+            .gap_1()
+            .child(
+                div()
+                    .flex()
+                    .justify_between()
+                    .text_xs()
+                    .child(div().text_color(colors.text_secondary).child(title))
+                    .child(div().text_color(colors.text_muted).child(summary)),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .min_h(px(64.0))
+                    .rounded_md()
+                    .bg(colors.bg_element)
+                    .overflow_hidden()
+                    .child(plot),
+            )
+    }
+}
+
+/// Area chart of `points`, both coordinates as fractions of the plot.
+fn render_plot(
+    points: Vec<(f32, f32)>,
+    reference: Option<f32>,
+    color: Rgba,
+    reference_color: Rgba,
+) -> impl IntoElement {
+    canvas(
+        |_, _, _| {},
+        move |bounds, _, window, _| {
+            let at = |x: f32, y: f32| {
+                point(
+                    bounds.left() + bounds.size.width * x,
+                    bounds.bottom() - bounds.size.height * y,
+                )
+            };
+
+            if let Some(y) = reference {
+                let mut line = PathBuilder::stroke(px(1.0));
+                line.move_to(at(0.0, y));
+                line.line_to(at(1.0, y));
+                if let Ok(path) = line.build() {
+                    window.paint_path(path, Hsla::from(reference_color).opacity(0.6));
+                }
+            }
+
+            let (Some(first), Some(last)) = (points.first(), points.last()) else {
+                return;
+            };
+
+            let mut area = PathBuilder::fill();
+            area.move_to(at(first.0, 0.0));
+            for (x, y) in &points {
+                area.line_to(at(*x, *y));
+            }
+            area.line_to(at(last.0, 0.0));
+            area.close();
+            if let Ok(path) = area.build() {
+                window.paint_path(path, Hsla::from(color).opacity(0.2));
+            }
+
+            stroke_line(window, bounds, &points, Hsla::from(color));
+        },
+    )
+    .size_full()
+}
+
+/// One line per series, coordinates as fractions of the plot.
+pub fn render_lines(lines: Vec<(Vec<(f32, f32)>, Rgba)>) -> impl IntoElement {
+    canvas(
+        |_, _, _| {},
+        move |bounds, _, window, _| {
+            for (points, color) in &lines {
+                stroke_line(window, bounds, points, Hsla::from(*color));
+            }
+        },
+    )
+    .size_full()
+}
+
+fn stroke_line(window: &mut Window, bounds: Bounds<Pixels>, points: &[(f32, f32)], color: Hsla) {
+    let at = |x: f32, y: f32| {
+        point(
+            bounds.left() + bounds.size.width * x,
+            bounds.bottom() - bounds.size.height * y,
+        )
+    };
+    let Some(first) = points.first() else {
+        return;
+    };
+
+    let mut line = PathBuilder::stroke(px(1.5));
+    line.move_to(at(first.0, first.1));
+    for (x, y) in &points[1..] {
+        line.line_to(at(*x, *y));
+    }
+    if let Ok(path) = line.build() {
+        window.paint_path(path, color);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::theme::ThemeColors;
+use crate::ui::yaml_editor::YAML_FONT;
+use gpui::*;
+use similar::{ChangeTag, TextDiff};
+
+/// Unchanged lines kept around each change.
+const CONTEXT_LINES: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffLineKind {
+    Context,
+    Added,
+    Removed,
+    /// Gap between two hunks
+    Separator,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    /// Line numbers in the old and new text
+    pub old_line: Option<usize>,
+    pub new_line: Option<usize>,
+    pub text: String,
+}
+
+/// Unified line diff between two YAML documents.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct YamlDiff {
+    pub lines: Vec<DiffLine>,
+    pub added: usize,
+    pub removed: usize,
+}
+
+impl YamlDiff {
+    pub fn new(old: &str, new: &str) -> Self {
+        let diff = TextDiff::from_lines(old, new);
+        let mut out = Self::default();
+
+        for (ix, group) in diff.grouped_ops(CONTEXT_LINES).iter().enumerate() {
+            if ix > 0 {
+                out.lines.push(DiffLine {
+                    kind: DiffLineKind::Separator,
+                    old_line: None,
+                    new_line: None,
+                    text: "…".to_string(),
+                });
+            }
+            for op in group {
+                for change in diff.iter_changes(op) {
+                    let kind = match change.tag() {
+                        ChangeTag::Equal => DiffLineKind::Context,
+                        ChangeTag::Insert => {
+                            out.added += 1;
+                            DiffLineKind::Added
+                        }
+                        ChangeTag::Delete => {
+                            out.removed += 1;
+                            DiffLineKind::Removed
+                        }
+                    };
+                    out.lines.push(DiffLine {
+                        kind,
+                        old_line: change.old_index().map(|ix| ix + 1),
+                        new_line: change.new_index().map(|ix| ix + 1),
+                        text: change.value().trim_end_matches('\n').to_string(),
+                    });
+                }
+            }
+        }
+
+        out
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added == 0 && self.removed == 0
+    }
+
+    pub fn render(&self, colors: &ThemeColors) -> impl IntoElement {
+        let number = |n: Option<usize>| {
+            div()
+                .flex()
+                .flex_none()
+                .justify_end()
+                .w(px(36.0))
+                .pr_2()
+                .text_color(colors.text_muted)
+                .child(n.map(|n| n.to_string()).unwrap_or_default())
+        };
+
+        div()
+            .id("yaml-diff")
+            .flex()
+            .flex_col()
+            .p_3()
+            .bg(if Hsla::from(colors.bg_app).l > 0.5 {
+                Hsla::from(colors.bg_element_active).opacity(0.1)
+            } else {
+                Hsla::from(colors.bg_sidebar)
+            })
+            .rounded_md()
+            .text_xs()
+            .font_family(YAML_FONT)
+            .text_color(colors.text_secondary)
+            .max_h(px(500.0))
+            .overflow_y_scroll()
+            .line_height(relative(1.6))
+            .when(self.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_color(colors.text_muted)
+                        .child("No changes: the server would store the object as it is."),
+                )
+            })
+            .children(self.lines.iter().map(|line| {
+                let (marker, bg, color) = match line.kind {
+                    DiffLineKind::Added => (
+                        "+",
+                        Some(Hsla::from(colors.status_ok).opacity(0.15)),
+                        colors.text_primary,
+                    ),
+                    DiffLineKind::Removed => (
+                        "-",
+                        Some(Hsla::from(colors.status_error).opacity(0.15)),
+                        colors.text_primary,
+                    ),
+                    DiffLineKind::Context => (" ", None, colors.text_secondary),
+                    DiffLineKind::Separator => (" ", None, colors.text_muted),
+                };
+
+                div()
+                    .flex()
+                    .when_some(bg, |this, bg| this.bg(bg))
+                    .text_color(color)
+                    .child(number(line.old_line))
+                    .child(number(line.new_line))
+                    .child(div().flex_none().w(px(14.0)).child(marker))
+                    .child(div().flex_1().whitespace_nowrap().child(line.text.clone()))
+            }))
+    }
+}
@@ -0,0 +1,660 @@
+use crate::kubernetes::{FieldError, SaveMode};
+use crate::settings::EditorSettings;
+use crate::theme::ThemeColors;
+use crate::ui::yaml_diff::YamlDiff;
+use gpui::*;
+
+pub const YAML_FONT: &str =
+    "'JetBrains Mono', 'Fira Code', 'SF Mono', Menlo, Monaco, 'Courier New', monospace";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum YamlAction {
+    Edit,
+    /// Discard the edits and go back to the read-only view
+    Cancel,
+    Focus,
+    /// Put the cursor at the end of a line
+    ClickLine(usize),
+    /// Dry-run a save and show its diff for review
+    Save(SaveMode),
+    /// Repeat the last save, overriding a conflict
+    ForceSave,
+    /// Make the reviewed save for real
+    ConfirmSave,
+    /// Leave the diff and keep editing
+    BackToEdit,
+    /// Start over from the object currently in the cluster
+    Reload,
+}
+
+/// What a key press did to the editor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditorEvent {
+    Changed,
+    Moved,
+    /// Cmd/Ctrl-S
+    Save,
+    /// Enter while reviewing a diff
+    Confirm,
+    Cancel,
+    /// Cmd/Ctrl-V; the caller reads the clipboard and calls `insert`
+    Paste,
+    Ignored,
+}
+
+/// A problem to show in the editor; `line` is `None` when it can't be placed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// A save that passed the server-side dry run, waiting for confirmation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PendingSave {
+    pub mode: SaveMode,
+    pub force: bool,
+    /// Live object against the dry-run result
+    pub diff: YamlDiff,
+}
+
+/// Multi-line YAML buffer for the details panel. Like `TextField`, keys are
+/// routed here by the app while it has focus.
+#[derive(Clone, Debug, PartialEq)]
+pub struct YamlEditor {
+    lines: Vec<String>,
+    /// Line and byte offset
+    cursor: (usize, usize),
+    pub focused: bool,
+    pub dirty: bool,
+    /// A save is in flight
+    pub saving: bool,
+    /// How the last save was made; reused by a forced retry
+    pub mode: SaveMode,
+    pub diagnostics: Vec<Diagnostic>,
+    /// The last save hit a `resourceVersion` or field-ownership conflict
+    pub conflict: bool,
+    /// Shown instead of the buffer while set; edits are blocked until the
+    /// save is confirmed or abandoned
+    pub review: Option<PendingSave>,
+    pub status: Option<Result<String, String>>,
+}
+
+impl YamlEditor {
+    pub fn new(text: &str) -> Self {
+        let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        Self {
+            lines,
+            cursor: (0, 0),
+            focused: true,
+            dirty: false,
+            saving: false,
+            mode: SaveMode::default(),
+            diagnostics: Vec::new(),
+            conflict: false,
+            review: None,
+            status: None,
+        }
+    }
+
+    pub fn from_object(object: &serde_json::Value) -> Self {
+        Self::new(&editable_yaml(object))
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    pub fn cursor(&self) -> (usize, usize) {
+        self.cursor
+    }
+
+    pub fn text(&self) -> String {
+        let mut text = self.lines.join("\n");
+        text.push('\n');
+        text
+    }
+
+    pub fn move_to_line_end(&mut self, row: usize) {
+        let row = row.min(self.lines.len() - 1);
+        self.cursor = (row, self.lines[row].len());
+    }
+
+    /// Inserts pasted text at the cursor, keeping its line breaks.
+    pub fn insert(&mut self, text: &str) {
+        let text = text.replace("\r\n", "\n");
+        let mut parts = text.split('\n');
+        if let Some(first) = parts.next() {
+            self.insert_str(first);
+        }
+        for part in parts {
+            self.split_line(String::new());
+            self.insert_str(part);
+        }
+        self.mark_changed();
+    }
+
+    /// Parses the buffer locally so syntax errors are caught before a round trip.
+    pub fn validate(&self) -> Option<Diagnostic> {
+        let error = serde_yaml::from_str::<serde_yaml::Value>(&self.text()).err()?;
+        Some(Diagnostic {
+            line: error.location().map(|loc| loc.line().saturating_sub(1)),
+            message: error.to_string(),
+        })
+    }
+
+    /// Places each API validation error on the line of its field path.
+    pub fn show_field_errors(&mut self, message: &str, fields: &[FieldError]) {
+        self.diagnostics = fields
+            .iter()
+            .map(|field| Diagnostic {
+                line: locate_path(&self.lines, &field.path),
+                message: format!("{}: {}", field.path, field.message),
+            })
+            .collect();
+        if self.diagnostics.is_empty() {
+            self.diagnostics.push(Diagnostic {
+                line: None,
+                message: message.to_string(),
+            });
+        }
+    }
+
+    pub fn handle_key(&mut self, keystroke: &Keystroke, settings: &EditorSettings) -> EditorEvent {
+        let modifiers = &keystroke.modifiers;
+
+        if self.review.is_some() {
+            return match keystroke.key.as_str() {
+                "enter" => EditorEvent::Confirm,
+                "escape" => EditorEvent::Cancel,
+                _ => EditorEvent::Ignored,
+            };
+        }
+
+        match keystroke.key.as_str() {
+            "s" if modifiers.secondary() => return EditorEvent::Save,
+            "v" if modifiers.secondary() => return EditorEvent::Paste,
+            "escape" => return EditorEvent::Cancel,
+            "enter" => {
+                let indent = self.next_line_indent(settings);
+                self.split_line(indent);
+            }
+            "tab" if modifiers.shift => self.dedent(settings),
+            "tab" => self.insert_str(&indent_unit(settings)),
+            "backspace" => self.backspace(settings),
+            "delete" => self.delete(),
+            "left" => {
+                self.move_left();
+                return EditorEvent::Moved;
+            }
+            "right" => {
+                self.move_right();
+                return EditorEvent::Moved;
+            }
+            "up" => {
+                self.move_vertical(-1);
+                return EditorEvent::Moved;
+            }
+            "down" => {
+                self.move_vertical(1);
+                return EditorEvent::Moved;
+            }
+            "home" => {
+                self.cursor.1 = 0;
+                return EditorEvent::Moved;
+            }
+            "end" => {
+                self.cursor.1 = self.lines[self.cursor.0].len();
+                return EditorEvent::Moved;
+            }
+            _ => {
+                if modifiers.control || modifiers.platform {
+                    return EditorEvent::Ignored;
+                }
+                match &keystroke.key_char {
+                    Some(ch) => self.insert_str(ch),
+                    None => return EditorEvent::Ignored,
+                }
+            }
+        }
+
+        self.mark_changed();
+        EditorEvent::Changed
+    }
+
+    fn mark_changed(&mut self) {
+        self.dirty = true;
+        self.status = None;
+    }
+
+    fn insert_str(&mut self, text: &str) {
+        let (row, col) = self.cursor;
+        self.lines[row].insert_str(col, text);
+        self.cursor.1 += text.len();
+    }
+
+    /// Breaks the line at the cursor; the new line starts with `indent`.
+    fn split_line(&mut self, indent: String) {
+        let (row, col) = self.cursor;
+        let rest = self.lines[row].split_off(col);
+        self.cursor = (row + 1, indent.len());
+        self.lines.insert(row + 1, indent + &rest);
+    }
+
+    /// Keeps the current indentation, one level deeper after a `key:`.
+    fn next_line_indent(&self, settings: &EditorSettings) -> String {
+        let (row, col) = self.cursor;
+        let before = &self.lines[row][..col];
+        let mut indent: String = before
+            .chars()
+            .take_while(|c| *c == ' ' || *c == '\t')
+            .collect();
+        if before.trim_end().ends_with(':') {
+            indent.push_str(&indent_unit(settings));
+        }
+        indent
+    }
+
+    fn backspace(&mut self, settings: &EditorSettings) {
+        let (row, col) = self.cursor;
+        if col == 0 {
+            if row > 0 {
+                let line = self.lines.remove(row);
+                let prev = &mut self.lines[row - 1];
+                self.cursor = (row - 1, prev.len());
+                prev.push_str(&line);
+            }
+            return;
+        }
+
+        let before = &self.lines[row][..col];
+        // In leading spaces, remove back to the previous tab stop
+        let start = if settings.use_spaces && before.chars().all(|c| c == ' ') {
+            let tab = settings.tab_size.max(1);
+            (col - 1) / tab * tab
+        } else {
+            prev_boundary(before, col)
+        };
+        self.lines[row].replace_range(start..col, "");
+        self.cursor.1 = start;
+    }
+
+    fn delete(&mut self) {
+        let (row, col) = self.cursor;
+        let line = &self.lines[row];
+        if col < line.len() {
+            let end = next_boundary(line, col);
+            self.lines[row].replace_range(col..end, "");
+        } else if row + 1 < self.lines.len() {
+            let next = self.lines.remove(row + 1);
+            self.lines[row].push_str(&next);
+        }
+    }
+
+    fn dedent(&mut self, settings: &EditorSettings) {
+        let row = self.cursor.0;
+        let line = &self.lines[row];
+        let remove = if line.starts_with('\t') {
+            1
+        } else {
+            line.chars()
+                .take(settings.tab_size.max(1))
+                .take_while(|c| *c == ' ')
+                .count()
+        };
+        self.lines[row].replace_range(..remove, "");
+        self.cursor.1 = self.cursor.1.saturating_sub(remove);
+    }
+
+    fn move_left(&mut self) {
+        let (row, col) = self.cursor;
+        if col > 0 {
+            self.cursor.1 = prev_boundary(&self.lines[row], col);
+        } else if row > 0 {
+            self.cursor = (row - 1, self.lines[row - 1].len());
+        }
+    }
+
+    fn move_right(&mut self) {
+        let (row, col) = self.cursor;
+        if col < self.lines[row].len() {
+            self.cursor.1 = next_boundary(&self.lines[row], col);
+        } else if row + 1 < self.lines.len() {
+            self.cursor = (row + 1, 0);
+        }
+    }
+
+    /// Moves up or down a line, keeping the character column where possible.
+    fn move_vertical(&mut self, delta: isize) {
+        let (row, col) = self.cursor;
+        let Some(target) = row
+            .checked_add_signed(delta)
+            .filter(|r| *r < self.lines.len())
+        else {
+            return;
+        };
+        let chars = self.lines[row][..col].chars().count();
+        let line = &self.lines[target];
+        let offset = line
+            .char_indices()
+            .nth(chars)
+            .map(|(ix, _)| ix)
+            .unwrap_or(line.len());
+        self.cursor = (target, offset);
+    }
+}
+
+fn indent_unit(settings: &EditorSettings) -> String {
+    if settings.use_spaces {
+        " ".repeat(settings.tab_size.max(1))
+    } else {
+        "\t".to_string()
+    }
+}
+
+fn prev_boundary(text: &str, col: usize) -> usize {
+    text[..col]
+        .char_indices()
+        .next_back()
+        .map(|(ix, _)| ix)
+        .unwrap_or(0)
+}
+
+fn next_boundary(text: &str, col: usize) -> usize {
+    text[col..]
+        .chars()
+        .next()
+        .map(|c| col + c.len_utf8())
+        .unwrap_or(col)
+}
+
+/// YAML for editing: the full object without `metadata.managedFields`, which
+/// is server-owned bookkeeping and only gets in the way.
+pub fn editable_yaml(object: &serde_json::Value) -> String {
+    let mut object = object.clone();
+    if let Some(metadata) = object.get_mut("metadata").and_then(|m| m.as_object_mut()) {
+        metadata.remove("managedFields");
+    }
+    serde_yaml::to_string(&object).unwrap_or_else(|_| "Failed to serialize".to_string())
+}
+
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// `spec.containers[0].image` -> key, key, index, key. A non-numeric index
+/// (`metadata.labels[app]`) is a map key.
+fn path_segments(path: &str) -> Vec<PathSegment> {
+    let mut segments = Vec::new();
+    let mut key = String::new();
+    let mut chars = path.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '.' | '[' => {
+                if !key.is_empty() {
+                    segments.push(PathSegment::Key(std::mem::take(&mut key)));
+                }
+                if c == '[' {
+                    let inner: String = chars.by_ref().take_while(|c| *c != ']').collect();
+                    segments.push(match inner.parse() {
+                        Ok(ix) => PathSegment::Index(ix),
+                        Err(_) => PathSegment::Key(inner),
+                    });
+                }
+            }
+            _ => key.push(c),
+        }
+    }
+    if !key.is_empty() {
+        segments.push(PathSegment::Key(key));
+    }
+    segments
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+/// Content column and text of a line, looking past a `- ` list marker.
+fn content_of(line: &str) -> (usize, &str) {
+    let indent = indent_of(line);
+    let rest = &line[indent..];
+    match rest.strip_prefix("- ") {
+        Some(item) => (indent + 2, item),
+        None => (indent, rest),
+    }
+}
+
+/// End of the block of lines nested under a key or item at `indent`. Lists
+/// are emitted at their parent key's indentation, so `- ` lines at `indent`
+/// still belong to it.
+fn block_end(lines: &[String], from: usize, indent: usize) -> usize {
+    (from..lines.len())
+        .find(|ix| {
+            let line = &lines[*ix];
+            if line.trim().is_empty() {
+                return false;
+            }
+            let line_indent = indent_of(line);
+            line_indent < indent || (line_indent == indent && !line[indent..].starts_with("- "))
+        })
+        .unwrap_or(lines.len())
+}
+
+fn find_key(lines: &[String], start: usize, end: usize, key: &str) -> Option<(usize, usize)> {
+    let entries: Vec<(usize, usize, &str)> = (start..end)
+        .filter(|ix| !lines[*ix].trim().is_empty())
+        .map(|ix| {
+            let (indent, content) = content_of(&lines[ix]);
+            (ix, indent, content)
+        })
+        .collect();
+    // Only keys at the block's own level, not nested ones of the same name
+    let level = entries.iter().map(|(_, indent, _)| *indent).min()?;
+    let quoted = format!("\"{}\":", key);
+    let plain = format!("{}:", key);
+
+    entries
+        .into_iter()
+        .find(|(_, indent, content)| {
+            *indent == level && (content.starts_with(&plain) || content.starts_with(&quoted))
+        })
+        .map(|(ix, indent, _)| (ix, indent))
+}
+
+fn find_item(lines: &[String], start: usize, end: usize, n: usize) -> Option<(usize, usize)> {
+    let items: Vec<usize> = (start..end)
+        .filter(|ix| lines[*ix].trim_start().starts_with("- "))
+        .collect();
+    let level = items.iter().map(|ix| indent_of(&lines[*ix])).min()?;
+    let line = items
+        .into_iter()
+        .filter(|ix| indent_of(&lines[*ix]) == level)
+        .nth(n)?;
+    Some((line, level + 2))
+}
+
+/// Best-effort line of an API field path such as
+/// `spec.template.spec.containers[0].image`, falling back to the deepest
+/// ancestor that could be found.
+pub fn locate_path(lines: &[String], path: &str) -> Option<usize> {
+    let (mut start, mut end) = (0, lines.len());
+    let mut found = None;
+
+    for segment in path_segments(path) {
+        let (located, is_item) = match segment {
+            PathSegment::Key(key) => (find_key(lines, start, end, &key), false),
+            PathSegment::Index(n) => (find_item(lines, start, end, n), true),
+        };
+        let Some((line, indent)) = located else {
+            break;
+        };
+        found = Some(line);
+        // An item's first key sits on its `- ` line
+        start = if is_item { line } else { line + 1 };
+        end = block_end(lines, line + 1, indent);
+    }
+
+    found
+}
+
+/// The YAML panel body, read-only when `editor` is `None`.
+pub fn render_yaml(
+    read_only: &str,
+    editor: Option<&YamlEditor>,
+    settings: &EditorSettings,
+    on_action: impl Fn(YamlAction, &mut Window, &mut App) + 'static + Clone,
+    colors: &ThemeColors,
+) -> impl IntoElement {
+    let owned_lines;
+    let lines: &[String] = match editor {
+        Some(editor) => &editor.lines,
+        None => {
+            owned_lines = read_only.lines().map(str::to_string).collect::<Vec<_>>();
+            &owned_lines
+        }
+    };
+    let cursor = editor.filter(|e| e.focused).map(|e| e.cursor);
+    let diagnostics = editor.map(|e| e.diagnostics.as_slice()).unwrap_or_default();
+    let tab = " ".repeat(settings.tab_size.max(1));
+
+    let on_focus = on_action.clone();
+    div()
+        .id("yaml-editor")
+        .flex()
+        .flex_col()
+        .p_3()
+        .bg(if Hsla::from(colors.bg_app).l > 0.5 {
+            Hsla::from(colors.bg_element_active).opacity(0.1) // Light mode, slight tint
+        } else {
+            Hsla::from(colors.bg_sidebar) // Dark mode
+        })
+        .rounded_md()
+        .border_1()
+        .border_color(match editor {
+            Some(editor) if editor.focused => colors.accent,
+            _ => Hsla::transparent_black(),
+        })
+        .text_xs()
+        .font_family(YAML_FONT)
+        .text_color(colors.text_secondary)
+        .max_h(px(500.0))
+        .overflow_y_scroll()
+        .when(!settings.word_wrap, |this| this.overflow_x_scroll())
+        .line_height(relative(1.6))
+        .when(editor.is_some(), |this| {
+            this.cursor(CursorStyle::IBeam)
+                .on_click(move |_, win, cx| on_focus(YamlAction::Focus, win, cx))
+        })
+        // Errors that couldn't be tied to a line
+        .children(
+            diagnostics
+                .iter()
+                .filter(|d| d.line.is_none())
+                .map(|d| diagnostic_row(&d.message, settings.show_line_numbers, colors)),
+        )
+        .children(lines.iter().enumerate().map(|(ix, line)| {
+            let line_errors: Vec<&Diagnostic> =
+                diagnostics.iter().filter(|d| d.line == Some(ix)).collect();
+            let display = |text: &str| text.replace('\t', &tab);
+
+            let content = match cursor {
+                Some((row, col)) if row == ix => div()
+                    .flex()
+                    .text_color(colors.text_primary)
+                    .child(display(&line[..col]))
+                    .child(div().flex_none().w(px(1.0)).h(px(14.0)).bg(colors.accent))
+                    .child(display(&line[col..])),
+                _ => div().child(highlight_line(
+                    display(line),
+                    settings.syntax_highlighting,
+                    colors,
+                )),
+            };
+
+            let on_click = on_action.clone();
+            div()
+                .flex()
+                .flex_col()
+                .child(
+                    div()
+                        .id(("yaml-line", ix))
+                        .flex()
+                        .when(!line_errors.is_empty(), |this| {
+                            this.bg(Hsla::from(colors.status_error).opacity(0.15))
+                        })
+                        .when(settings.show_line_numbers, |this| {
+                            this.child(line_number(ix + 1, colors))
+                        })
+                        .child(
+                            content
+                                .flex_1()
+                                .when(!settings.word_wrap, |this| this.whitespace_nowrap()),
+                        )
+                        .when(editor.is_some(), |this| {
+                            this.on_click(move |_, win, cx| {
+                                cx.stop_propagation();
+                                on_click(YamlAction::ClickLine(ix), win, cx)
+                            })
+                        }),
+                )
+                .children(
+                    line_errors
+                        .into_iter()
+                        .map(|d| diagnostic_row(&d.message, settings.show_line_numbers, colors)),
+                )
+        }))
+}
+
+fn line_number(number: usize, colors: &ThemeColors) -> impl IntoElement {
+    div()
+        .flex()
+        .flex_none()
+        .justify_end()
+        .w(px(36.0))
+        .pr_3()
+        .text_color(colors.text_muted)
+        .child(number.to_string())
+}
+
+fn diagnostic_row(message: &str, indent: bool, colors: &ThemeColors) -> impl IntoElement {
+    div()
+        .flex()
+        .when(indent, |this| this.pl(px(36.0)))
+        .text_color(colors.status_error)
+        .child(format!("⚠ {}", message))
+}
+
+/// Colors keys and comments; values keep the default text color.
+fn highlight_line(text: String, enabled: bool, colors: &ThemeColors) -> StyledText {
+    let mut highlights = Vec::new();
+    if enabled {
+        let (indent, content) = content_of(&text);
+        if content.starts_with('#') {
+            highlights.push((
+                indent..text.len(),
+                HighlightStyle {
+                    color: Some(colors.text_muted),
+                    ..Default::default()
+                },
+            ));
+        } else if let Some(colon) = content
+            .find(": ")
+            .or_else(|| content.ends_with(':').then(|| content.len() - 1))
+        {
+            highlights.push((
+                indent..indent + colon,
+                HighlightStyle {
+                    color: Some(colors.accent),
+                    ..Default::default()
+                },
+            ));
+        }
+    }
+    StyledText::new(text).with_highlights(highlights)
+}